@@ -1,3 +1,5 @@
+use crate::frame::Fixed;
+use crate::processor::Processor;
 use crate::sample::FloatSample;
 
 /// Coefficients for a digital biquad filter.
@@ -19,6 +21,81 @@ where
     pub a2: X,
 }
 
+impl<X> Coefficients<X>
+where
+    X: FloatSample,
+{
+    /// Creates [`Coefficients`] from `f64` values, normalizing by `a0`.
+    fn normalized(b0: f64, b1: f64, b2: f64, a0: f64, a1: f64, a2: f64) -> Self {
+        Self {
+            b0: X::from_sample(b0 / a0),
+            b1: X::from_sample(b1 / a0),
+            b2: X::from_sample(b2 / a0),
+            a1: X::from_sample(a1 / a0),
+            a2: X::from_sample(a2 / a0),
+        }
+    }
+
+    /// Creates the [`Coefficients`] of a first-order allpass filter, whose
+    /// phase shift is 90 degrees at `frequency`.
+    ///
+    /// ```
+    /// use sampara::biquad::Coefficients;
+    ///
+    /// fn main() {
+    ///     // At a quarter of the sample rate, the filter is a pure delay.
+    ///     let coeffs = Coefficients::<f64>::first_order_allpass(12000.0, 48000.0);
+    ///
+    ///     assert!(coeffs.b0.abs() < 1e-12 && coeffs.a1.abs() < 1e-12);
+    ///     assert_eq!((coeffs.b1, coeffs.b2, coeffs.a2), (1.0, 0.0, 0.0));
+    /// }
+    /// ```
+    pub fn first_order_allpass(frequency: f64, sample_rate: f64) -> Self {
+        let t = (core::f64::consts::PI * frequency / sample_rate).tan();
+        let a = (t - 1.0) / (t + 1.0);
+
+        Self::normalized(a, 1.0, 0.0, 1.0, a, 0.0)
+    }
+
+    /// Creates the [`Coefficients`] of a high shelf filter, with a gain in
+    /// decibels above `frequency`, from the Audio EQ Cookbook (with a shelf
+    /// slope of 1).
+    pub fn high_shelf(frequency: f64, gain: f64, sample_rate: f64) -> Self {
+        let a = 10.0f64.powf(gain / 40.0);
+        let w0 = 2.0 * core::f64::consts::PI * frequency / sample_rate;
+        let cos = w0.cos();
+        let beta = 2.0 * a.sqrt() * w0.sin() / core::f64::consts::SQRT_2;
+
+        Self::normalized(
+            a * ((a + 1.0) + (a - 1.0) * cos + beta),
+            -2.0 * a * ((a - 1.0) + (a + 1.0) * cos),
+            a * ((a + 1.0) + (a - 1.0) * cos - beta),
+            (a + 1.0) - (a - 1.0) * cos + beta,
+            2.0 * ((a - 1.0) - (a + 1.0) * cos),
+            (a + 1.0) - (a - 1.0) * cos - beta,
+        )
+    }
+
+    /// Creates the [`Coefficients`] of a second-order allpass filter, whose
+    /// phase shift is 180 degrees at `frequency`, from the Audio EQ Cookbook.
+    /// A higher `q` makes the phase change more abruptly.
+    pub fn allpass(frequency: f64, q: f64, sample_rate: f64) -> Self {
+        let w0 = 2.0 * core::f64::consts::PI * frequency / sample_rate;
+        let alpha = w0.sin() / (2.0 * q);
+        let cos = w0.cos();
+
+        Self::normalized(
+            1.0 - alpha,
+            -2.0 * cos,
+            1.0 + alpha,
+            1.0 + alpha,
+            -2.0 * cos,
+            1.0 - alpha,
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
 pub struct Biquad<S>
 where
     S: FloatSample,
@@ -35,6 +112,49 @@ impl<S> Biquad<S>
 where
     S: FloatSample,
 {
+    /// Creates a new [`Biquad`] with the given [`Coefficients`], and with its
+    /// state at equilibrium.
+    ///
+    /// ```
+    /// use sampara::biquad::{Biquad, Coefficients};
+    /// use sampara::processor::Processor;
+    ///
+    /// fn main() {
+    ///     // A two-point moving average.
+    ///     let coeffs = Coefficients { b0: 0.5, b1: 0.5, b2: 0.0, a1: 0.0, a2: 0.0 };
+    ///
+    ///     let mut biquad = Biquad::new(coeffs);
+    ///     assert_eq!(biquad.process(1.0), 0.5);
+    ///     assert_eq!(biquad.process(0.0), 0.5);
+    ///     assert_eq!(biquad.process(0.0), 0.0);
+    ///
+    ///     // Filter a stereo block in place, with one filter per channel.
+    ///     let mut bank = [Biquad::new(coeffs), Biquad::new(coeffs)];
+    ///     let mut block = [[1.0f32, 0.0].into(), [0.0, 1.0].into()];
+    ///     Processor::process(&mut bank, &mut block);
+    ///
+    ///     assert_eq!(block, [[0.5, 0.0].into(), [0.5, 0.5].into()]);
+    /// }
+    /// ```
+    pub fn new(coeffs: Coefficients<S>) -> Self {
+        Self {
+            coeffs,
+            t0: S::EQUILIBRIUM,
+            t1: S::EQUILIBRIUM,
+        }
+    }
+
+    /// Returns the [`Coefficients`] of this filter.
+    pub fn coefficients(&self) -> &Coefficients<S> {
+        &self.coeffs
+    }
+
+    /// Replaces the [`Coefficients`] of this filter, while preserving its
+    /// state.
+    pub fn set_coefficients(&mut self, coeffs: Coefficients<S>) {
+        self.coeffs = coeffs;
+    }
+
     pub fn reset(&mut self) {
         self.t0 = S::EQUILIBRIUM;
         self.t1 = S::EQUILIBRIUM;
@@ -61,3 +181,55 @@ where
         output
     }
 }
+
+impl<S> From<Coefficients<S>> for Biquad<S>
+where
+    S: FloatSample,
+{
+    fn from(coeffs: Coefficients<S>) -> Self {
+        Self::new(coeffs)
+    }
+}
+
+impl<S> Processor for Biquad<S>
+where
+    S: FloatSample,
+{
+    type Frame = Fixed<S, 1>;
+
+    #[inline]
+    fn process(&mut self, block: &mut [Self::Frame]) {
+        for frame in block.iter_mut() {
+            let [x] = frame.into_array();
+            *frame = Fixed::from([Biquad::process(self, x)]);
+        }
+    }
+
+    fn reset(&mut self) {
+        Biquad::reset(self)
+    }
+}
+
+/// A bank of [`Biquad`]s processes each channel of a [`Fixed`] frame with its
+/// own filter.
+impl<S, const N: usize> Processor for [Biquad<S>; N]
+where
+    S: FloatSample,
+{
+    type Frame = Fixed<S, N>;
+
+    #[inline]
+    fn process(&mut self, block: &mut [Self::Frame]) {
+        for frame in block.iter_mut() {
+            let mut samples = frame.into_array();
+            for (biquad, sample) in self.iter_mut().zip(samples.iter_mut()) {
+                *sample = Biquad::process(biquad, *sample);
+            }
+            *frame = Fixed::from(samples);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.iter_mut().for_each(Biquad::reset)
+    }
+}
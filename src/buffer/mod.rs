@@ -0,0 +1,692 @@
+//! Owned and borrowed multichannel sample buffers, in either planar or
+//! interleaved layouts.
+
+use core::marker::PhantomData;
+
+use crate::frame::{Dynamic, Fixed, Frame};
+use crate::sample::Sample;
+use crate::signal::{CollectTarget, ExactSizeSignal, SeekableSignal, Signal};
+
+/// The order in which the [`Sample`]s of a multichannel buffer are stored.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Layout {
+    /// All channels of a frame are stored next to each other, followed by the
+    /// channels of the next frame: `L0 R0 L1 R1 L2 R2 ...`.
+    Interleaved,
+
+    /// All frames of a channel are stored next to each other, followed by the
+    /// frames of the next channel: `L0 L1 L2 ... R0 R1 R2 ...`.
+    Planar,
+}
+
+impl Layout {
+    /// Returns the offset between consecutive frames and the offset between
+    /// consecutive channels in a buffer of this layout.
+    #[inline]
+    fn strides(self, channels: usize, frames: usize) -> (usize, usize) {
+        match self {
+            Layout::Interleaved => (channels, 1),
+            Layout::Planar => (1, frames),
+        }
+    }
+}
+
+/// A multichannel buffer of [`Sample`]s, stored in a single allocation in
+/// either a planar or interleaved [`Layout`].
+///
+/// ```
+/// use sampara::Signal;
+/// use sampara::buffer::{AudioBuffer, Layout};
+///
+/// fn main() {
+///     let buffer = AudioBuffer::from_interleaved(vec![1, -1, 2, -2, 3, -3], 2);
+///     assert_eq!(buffer.frames(), 3);
+///
+///     let planar = buffer.to_layout(Layout::Planar);
+///     assert_eq!(planar.as_slice(), &[1, 2, 3, -1, -2, -3]);
+///
+///     let mut signal = planar.signal::<2>();
+///     assert_eq!(signal.next(), Some([1, -1].into()));
+///     assert_eq!(signal.next(), Some([2, -2].into()));
+///     assert_eq!(signal.next(), Some([3, -3].into()));
+///     assert_eq!(signal.next(), None);
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct AudioBuffer<S: Sample> {
+    data: Vec<S>,
+    channels: usize,
+    frames: usize,
+    layout: Layout,
+}
+
+impl<S: Sample> AudioBuffer<S> {
+    /// Creates a new [`AudioBuffer`] with a given number of channels and
+    /// frames, filled with [`Sample::EQUILIBRIUM`].
+    pub fn new(channels: usize, frames: usize, layout: Layout) -> Self {
+        Self {
+            data: vec![S::EQUILIBRIUM; channels * frames],
+            channels,
+            frames,
+            layout,
+        }
+    }
+
+    /// Creates a new [`AudioBuffer`] from interleaved [`Sample`]s. Any
+    /// trailing [`Sample`]s that do not fully complete a frame are discarded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `channels` is 0.
+    pub fn from_interleaved(mut samples: Vec<S>, channels: usize) -> Self {
+        assert!(channels > 0, "number of channels must be non-zero");
+
+        let frames = samples.len() / channels;
+        samples.truncate(frames * channels);
+
+        Self {
+            data: samples,
+            channels,
+            frames,
+            layout: Layout::Interleaved,
+        }
+    }
+
+    /// Creates a new planar [`AudioBuffer`] from a separate [`Vec`] of
+    /// [`Sample`]s for each channel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the channels are not all of the same length.
+    pub fn from_planar(channels: Vec<Vec<S>>) -> Self {
+        let frames = channels.first().map_or(0, Vec::len);
+        assert!(
+            channels.iter().all(|c| c.len() == frames),
+            "all channels must have the same length"
+        );
+
+        Self {
+            channels: channels.len(),
+            frames,
+            data: channels.into_iter().flatten().collect(),
+            layout: Layout::Planar,
+        }
+    }
+
+    /// Returns the number of channels in this buffer.
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// Returns the number of frames in this buffer.
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Returns the [`Layout`] of this buffer.
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    /// Returns all of the [`Sample`]s in this buffer, in its [`Layout`].
+    pub fn as_slice(&self) -> &[S] {
+        &self.data
+    }
+
+    /// Like [`Self::as_slice`], but returns a mutable slice.
+    pub fn as_mut_slice(&mut self) -> &mut [S] {
+        &mut self.data
+    }
+
+    /// Consumes this buffer, returning its [`Sample`]s in its [`Layout`].
+    pub fn into_vec(self) -> Vec<S> {
+        self.data
+    }
+
+    #[inline]
+    fn index(&self, frame: usize, channel: usize) -> Option<usize> {
+        if frame < self.frames && channel < self.channels {
+            let (frame_stride, channel_stride) = self.layout.strides(self.channels, self.frames);
+            Some(frame * frame_stride + channel * channel_stride)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the [`Sample`] at a given frame and channel.
+    pub fn get(&self, frame: usize, channel: usize) -> Option<&S> {
+        self.index(frame, channel).map(|i| &self.data[i])
+    }
+
+    /// Like [`Self::get`], but returns a mutable reference.
+    pub fn get_mut(&mut self, frame: usize, channel: usize) -> Option<&mut S> {
+        self.index(frame, channel).map(|i| &mut self.data[i])
+    }
+
+    /// Returns an iterator over the [`Sample`]s of a single channel, for
+    /// either [`Layout`].
+    pub fn channel(&self, channel: usize) -> Option<impl Iterator<Item = &S>> {
+        if channel >= self.channels {
+            return None;
+        }
+
+        let (frame_stride, channel_stride) = self.layout.strides(self.channels, self.frames);
+        let start = (channel * channel_stride).min(self.data.len());
+
+        Some(
+            self.data[start..]
+                .iter()
+                .step_by(frame_stride)
+                .take(self.frames),
+        )
+    }
+
+    /// Returns an iterator over each channel as a contiguous slice, if this
+    /// buffer has a [`Layout::Planar`] layout.
+    pub fn planar_channels(&self) -> Option<core::slice::Chunks<'_, S>> {
+        match self.layout {
+            Layout::Planar => Some(self.data.chunks(self.frames.max(1))),
+            Layout::Interleaved => None,
+        }
+    }
+
+    /// Like [`Self::planar_channels`], but yields mutable slices.
+    pub fn planar_channels_mut(&mut self) -> Option<core::slice::ChunksMut<'_, S>> {
+        match self.layout {
+            Layout::Planar => Some(self.data.chunks_mut(self.frames.max(1))),
+            Layout::Interleaved => None,
+        }
+    }
+
+    /// Returns a copy of this buffer, converted to a given [`Layout`].
+    pub fn to_layout(&self, layout: Layout) -> Self {
+        if layout == self.layout {
+            return self.clone();
+        }
+
+        let mut converted = Self::new(self.channels, self.frames, layout);
+        for frame in 0..self.frames {
+            for channel in 0..self.channels {
+                if let (Some(dst), Some(src)) =
+                    (converted.index(frame, channel), self.index(frame, channel))
+                {
+                    converted.data[dst] = self.data[src];
+                }
+            }
+        }
+
+        converted
+    }
+
+    /// Converts this buffer to a given [`Layout`].
+    pub fn set_layout(&mut self, layout: Layout) {
+        if layout != self.layout {
+            *self = self.to_layout(layout);
+        }
+    }
+
+    /// Returns a [`Signal`] that yields each frame of this buffer as a
+    /// [`Fixed`] frame, without copying the underlying [`Sample`]s.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is not equal to the number of channels in this buffer.
+    pub fn signal<const N: usize>(&self) -> Frames<'_, S, Fixed<S, N>> {
+        assert_eq!(N, self.channels, "frame size must match number of channels");
+        Frames::new(&self.data, self.channels, self.frames, self.layout)
+    }
+
+    /// Returns a [`Signal`] that yields each frame of this buffer as a
+    /// [`Dynamic`] frame, without copying the underlying [`Sample`]s.
+    pub fn signal_dynamic(&self) -> Frames<'_, S, Dynamic<S>> {
+        Frames::new(&self.data, self.channels, self.frames, self.layout)
+    }
+
+    /// Writes [`Frame`]s from a [`Signal`] into this buffer, starting at the
+    /// first frame, until either the [`Signal`] is exhausted or this buffer is
+    /// full. Returns the number of frames written. This does not allocate.
+    ///
+    /// If the [`Frame`]s have more channels than this buffer, the extra
+    /// channels are dropped. If they have fewer, the rest of the channels of
+    /// each written frame are set to equilibrium.
+    ///
+    /// ```
+    /// use sampara::signal;
+    /// use sampara::buffer::{AudioBuffer, Layout};
+    ///
+    /// fn main() {
+    ///     let mut buffer = AudioBuffer::new(2, 4, Layout::Planar);
+    ///     let signal = signal::from_samples_fixed::<_, 2>(vec![1, -1, 2, -2]);
+    ///
+    ///     assert_eq!(buffer.write_signal(signal), 2);
+    ///     assert_eq!(buffer.as_slice(), &[1, 2, 0, 0, -1, -2, 0, 0]);
+    ///
+    ///     let signal = signal::from_samples_fixed::<_, 1>(vec![3]);
+    ///     assert_eq!(buffer.write_signal(signal), 1);
+    ///     assert_eq!(buffer.as_slice(), &[3, 2, 0, 0, 0, -2, 0, 0]);
+    /// }
+    /// ```
+    pub fn write_signal<G>(&mut self, mut signal: G) -> usize
+    where
+        G: Signal,
+        G::Frame: Frame<Sample = S>,
+    {
+        let (frame_stride, channel_stride) = self.layout.strides(self.channels, self.frames);
+
+        for frame_idx in 0..self.frames {
+            let Some(frame) = signal.next() else {
+                return frame_idx;
+            };
+
+            let mut samples = frame.iter();
+            for channel in 0..self.channels {
+                self.data[frame_idx * frame_stride + channel * channel_stride] =
+                    samples.next().copied().unwrap_or(S::EQUILIBRIUM);
+            }
+        }
+
+        self.frames
+    }
+}
+
+/// Overwrites frames from the start, as with [`AudioBuffer::write_signal`].
+/// Channels that a [`Frame`] does not have are set to equilibrium.
+impl<S, F> CollectTarget<F> for AudioBuffer<S>
+where
+    S: Sample,
+    F: Frame<Sample = S>,
+{
+    fn collect_signal<G>(&mut self, signal: G) -> usize
+    where
+        G: Signal<Frame = F>,
+    {
+        self.write_signal(signal)
+    }
+}
+
+/// A [`Signal`] that yields [`Frame`]s from a borrowed slice of [`Sample`]s,
+/// stored in either [`Layout`]. Only the yielded [`Frame`]s themselves are
+/// copied.
+pub struct Frames<'a, S, F>
+where
+    S: Sample,
+    F: Frame<Sample = S>,
+{
+    data: &'a [S],
+    channels: usize,
+    frames: usize,
+    frame_stride: usize,
+    channel_stride: usize,
+    pos: usize,
+    _marker: PhantomData<F>,
+}
+
+impl<'a, S, F> Frames<'a, S, F>
+where
+    S: Sample,
+    F: Frame<Sample = S>,
+{
+    fn new(data: &'a [S], channels: usize, frames: usize, layout: Layout) -> Self {
+        let (frame_stride, channel_stride) = layout.strides(channels, frames);
+
+        Self {
+            data,
+            channels,
+            frames,
+            frame_stride,
+            channel_stride,
+            pos: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the [`Sample`]s of the next frame, and advances.
+    #[inline]
+    fn next_samples(&mut self) -> Option<impl Iterator<Item = S> + use<'a, S, F>> {
+        if self.pos >= self.frames {
+            return None;
+        }
+
+        let data = self.data;
+        let start = self.pos * self.frame_stride;
+        let channel_stride = self.channel_stride;
+        self.pos += 1;
+
+        Some((0..self.channels).map(move |c| data[start + c * channel_stride]))
+    }
+}
+
+impl<'a, S, const N: usize> Signal for Frames<'a, S, Fixed<S, N>>
+where
+    S: Sample,
+{
+    type Frame = Fixed<S, N>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Frame> {
+        Fixed::from_samples(&mut self.next_samples()?)
+    }
+
+    #[inline]
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        let len = self.frames - self.pos;
+        (len, Some(len))
+    }
+}
+
+impl<'a, S, const N: usize> ExactSizeSignal for Frames<'a, S, Fixed<S, N>> where S: Sample {}
+
+impl<'a, S> Signal for Frames<'a, S, Dynamic<S>>
+where
+    S: Sample,
+{
+    type Frame = Dynamic<S>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Frame> {
+        let n = self.channels;
+        Dynamic::from_samples(&mut self.next_samples()?, n)
+    }
+
+    #[inline]
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        let len = self.frames - self.pos;
+        (len, Some(len))
+    }
+}
+
+impl<'a, S> ExactSizeSignal for Frames<'a, S, Dynamic<S>> where S: Sample {}
+
+impl<'a, S, F> SeekableSignal for Frames<'a, S, F>
+where
+    S: Sample,
+    F: Frame<Sample = S>,
+    Self: Signal,
+{
+    fn seek(&mut self, position: usize) {
+        self.pos = position.min(self.frames);
+    }
+
+    fn position(&self) -> usize {
+        self.pos
+    }
+}
+
+/// Creates a new [`Signal`] over a borrowed slice of interleaved [`Sample`]s,
+/// yielding [`Fixed`] frames. Any trailing [`Sample`]s that do not fully
+/// complete a frame are ignored.
+///
+/// ```
+/// use sampara::Signal;
+/// use sampara::buffer;
+///
+/// fn main() {
+///     let samples = [0.5, -0.5, 0.25, -0.25, 0.0];
+///     let mut signal = buffer::interleaved::<_, 2>(&samples);
+///
+///     assert_eq!(signal.next(), Some([0.5, -0.5].into()));
+///     assert_eq!(signal.next(), Some([0.25, -0.25].into()));
+///     assert_eq!(signal.next(), None);
+/// }
+/// ```
+pub fn interleaved<S, const N: usize>(samples: &[S]) -> Frames<'_, S, Fixed<S, N>>
+where
+    S: Sample,
+{
+    Frames::new(samples, N, samples.len() / N.max(1), Layout::Interleaved)
+}
+
+/// A [`Signal`] that yields [`Frame`]s from borrowed planar channel slices, as
+/// handed out by host audio APIs. Only the yielded [`Frame`]s themselves are
+/// copied.
+pub struct PlanarFrames<'a, C, F>
+where
+    C: AsRef<[F::Sample]>,
+    F: Frame,
+{
+    channels: &'a [C],
+    frames: usize,
+    pos: usize,
+    _marker: PhantomData<F>,
+}
+
+impl<'a, C, F> PlanarFrames<'a, C, F>
+where
+    C: AsRef<[F::Sample]>,
+    F: Frame,
+{
+    #[inline]
+    fn next_samples(&mut self) -> Option<impl Iterator<Item = F::Sample> + use<'a, C, F>> {
+        if self.pos >= self.frames {
+            return None;
+        }
+
+        let pos = self.pos;
+        self.pos += 1;
+
+        Some(self.channels.iter().map(move |c| c.as_ref()[pos]))
+    }
+}
+
+impl<'a, C, S, const N: usize> Signal for PlanarFrames<'a, C, Fixed<S, N>>
+where
+    C: AsRef<[S]>,
+    S: Sample,
+{
+    type Frame = Fixed<S, N>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Frame> {
+        Fixed::from_samples(&mut self.next_samples()?)
+    }
+
+    #[inline]
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        let len = self.frames - self.pos;
+        (len, Some(len))
+    }
+}
+
+impl<'a, C, S, const N: usize> ExactSizeSignal for PlanarFrames<'a, C, Fixed<S, N>>
+where
+    C: AsRef<[S]>,
+    S: Sample,
+{
+}
+
+impl<'a, C, S> Signal for PlanarFrames<'a, C, Dynamic<S>>
+where
+    C: AsRef<[S]>,
+    S: Sample,
+{
+    type Frame = Dynamic<S>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Frame> {
+        let n = self.channels.len();
+        Dynamic::from_samples(&mut self.next_samples()?, n)
+    }
+
+    #[inline]
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        let len = self.frames - self.pos;
+        (len, Some(len))
+    }
+}
+
+impl<'a, C, S> ExactSizeSignal for PlanarFrames<'a, C, Dynamic<S>>
+where
+    C: AsRef<[S]>,
+    S: Sample,
+{
+}
+
+impl<'a, C, F> SeekableSignal for PlanarFrames<'a, C, F>
+where
+    C: AsRef<[F::Sample]>,
+    F: Frame,
+    Self: Signal,
+{
+    fn seek(&mut self, position: usize) {
+        self.pos = position.min(self.frames);
+    }
+
+    fn position(&self) -> usize {
+        self.pos
+    }
+}
+
+/// Creates a new [`Signal`] over borrowed planar channel slices, such as the
+/// `&[&[f32]]` passed to host audio callbacks. The [`Signal`] yields as many
+/// frames as the shortest channel contains.
+///
+/// # Panics
+///
+/// Panics if `F` cannot have as many channels as there are slices, such as a
+/// [`Fixed`] frame whose size differs from the number of slices.
+///
+/// ```
+/// use sampara::{Dynamic, Fixed, Signal};
+/// use sampara::buffer;
+///
+/// fn main() {
+///     let left = [0.1, 0.2, 0.3];
+///     let right = [-0.1, -0.2];
+///     let channels: [&[f32]; 2] = [&left, &right];
+///
+///     let mut signal = buffer::planar::<_, Fixed<f32, 2>>(&channels);
+///     assert_eq!(signal.next(), Some([0.1, -0.1].into()));
+///     assert_eq!(signal.next(), Some([0.2, -0.2].into()));
+///     assert_eq!(signal.next(), None);
+///
+///     let mut signal = buffer::planar::<_, Dynamic<f32>>(&channels);
+///     assert_eq!(signal.next(), Some(vec![0.1, -0.1].into()));
+/// }
+/// ```
+pub fn planar<C, F>(channels: &[C]) -> PlanarFrames<'_, C, F>
+where
+    C: AsRef<[F::Sample]>,
+    F: Frame,
+{
+    assert!(
+        F::equil_with_channels(channels.len()).is_some(),
+        "frame size must match number of channels"
+    );
+
+    let frames = channels.iter().map(|c| c.as_ref().len()).min().unwrap_or(0);
+
+    PlanarFrames {
+        channels,
+        frames,
+        pos: 0,
+        _marker: PhantomData,
+    }
+}
+
+/// Writes [`Frame`]s from a [`Signal`] into borrowed planar channel slices,
+/// such as the `&mut [&mut [f32]]` passed to host audio callbacks, until
+/// either the [`Signal`] is exhausted or the shortest channel is full. Returns
+/// the number of frames written. This does not allocate.
+///
+/// If the [`Frame`]s have more channels than there are slices, the extra
+/// channels are dropped. If they have fewer, the rest of the slices are set to
+/// equilibrium for each written frame.
+///
+/// ```
+/// use sampara::signal;
+/// use sampara::buffer;
+///
+/// fn main() {
+///     let mut left = [0.0; 3];
+///     let mut right = [0.0; 3];
+///     let mut channels: [&mut [f32]; 2] = [&mut left, &mut right];
+///
+///     let signal = signal::from_samples_fixed::<_, 2>(vec![0.5, -0.5, 0.25, -0.25]);
+///     assert_eq!(buffer::write_planar(signal, &mut channels), 2);
+///
+///     assert_eq!(left, [0.5, 0.25, 0.0]);
+///     assert_eq!(right, [-0.5, -0.25, 0.0]);
+/// }
+/// ```
+pub fn write_planar<G, C>(mut signal: G, channels: &mut [C]) -> usize
+where
+    G: Signal,
+    C: AsMut<[<G::Frame as Frame>::Sample]>,
+{
+    let frames = channels
+        .iter_mut()
+        .map(|c| c.as_mut().len())
+        .min()
+        .unwrap_or(0);
+
+    for frame_idx in 0..frames {
+        let Some(frame) = signal.next() else {
+            return frame_idx;
+        };
+
+        let mut samples = frame.iter();
+        for channel in channels.iter_mut() {
+            channel.as_mut()[frame_idx] = samples.next().copied().unwrap_or(Sample::EQUILIBRIUM);
+        }
+    }
+
+    frames
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout_round_trip() {
+        let planar = AudioBuffer::from_planar(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]]);
+        let interleaved = planar.to_layout(Layout::Interleaved);
+
+        assert_eq!(interleaved.as_slice(), &[1, 4, 7, 2, 5, 8, 3, 6, 9]);
+        assert_eq!(interleaved.to_layout(Layout::Planar), planar);
+
+        for frame in 0..3 {
+            for channel in 0..3 {
+                assert_eq!(planar.get(frame, channel), interleaved.get(frame, channel));
+            }
+        }
+
+        assert_eq!(planar.get(3, 0), None);
+        assert_eq!(planar.get(0, 3), None);
+    }
+
+    #[test]
+    fn channel_iter() {
+        let mut buffer = AudioBuffer::from_interleaved(vec![1, 4, 2, 5, 3, 6], 2);
+
+        for layout in [Layout::Interleaved, Layout::Planar] {
+            buffer.set_layout(layout);
+
+            let left = buffer.channel(0).unwrap().copied().collect::<Vec<_>>();
+            let right = buffer.channel(1).unwrap().copied().collect::<Vec<_>>();
+            assert_eq!(left, vec![1, 2, 3]);
+            assert_eq!(right, vec![4, 5, 6]);
+            assert!(buffer.channel(2).is_none());
+        }
+
+        let channels = buffer.planar_channels().unwrap().collect::<Vec<_>>();
+        assert_eq!(channels, vec![&[1, 2, 3], &[4, 5, 6]]);
+    }
+
+    #[test]
+    fn dynamic_view() {
+        let buffer = AudioBuffer::from_planar(vec![vec![1i8, 2], vec![3, 4], vec![5, 6]]);
+        let mut signal = buffer.signal_dynamic();
+
+        assert_eq!(signal.next(), Some(Dynamic::from([1, 3, 5])));
+        assert_eq!(signal.next(), Some(Dynamic::from([2, 4, 6])));
+        assert_eq!(signal.next(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn planar_too_few_channels() {
+        let channels: [&[f32]; 1] = [&[0.5, 0.25]];
+        let _ = planar::<_, Fixed<f32, 2>>(&channels);
+    }
+}
@@ -0,0 +1,448 @@
+use crate::frame::Frame;
+
+/// A speaker position, as defined for the channel masks of
+/// WAVE_FORMAT_EXTENSIBLE files. The discriminant of each speaker is the
+/// index of its bit in the mask.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Speaker {
+    FrontLeft = 0,
+    FrontRight = 1,
+    FrontCenter = 2,
+    LowFrequency = 3,
+    BackLeft = 4,
+    BackRight = 5,
+    FrontLeftOfCenter = 6,
+    FrontRightOfCenter = 7,
+    BackCenter = 8,
+    SideLeft = 9,
+    SideRight = 10,
+    TopCenter = 11,
+    TopFrontLeft = 12,
+    TopFrontCenter = 13,
+    TopFrontRight = 14,
+    TopBackLeft = 15,
+    TopBackCenter = 16,
+    TopBackRight = 17,
+}
+
+impl Speaker {
+    /// Every speaker, in the order of their bits in a channel mask.
+    pub const ALL: [Speaker; 18] = [
+        Speaker::FrontLeft,
+        Speaker::FrontRight,
+        Speaker::FrontCenter,
+        Speaker::LowFrequency,
+        Speaker::BackLeft,
+        Speaker::BackRight,
+        Speaker::FrontLeftOfCenter,
+        Speaker::FrontRightOfCenter,
+        Speaker::BackCenter,
+        Speaker::SideLeft,
+        Speaker::SideRight,
+        Speaker::TopCenter,
+        Speaker::TopFrontLeft,
+        Speaker::TopFrontCenter,
+        Speaker::TopFrontRight,
+        Speaker::TopBackLeft,
+        Speaker::TopBackCenter,
+        Speaker::TopBackRight,
+    ];
+
+    /// The bit of this speaker in a WAVE_FORMAT_EXTENSIBLE channel mask.
+    pub fn mask(&self) -> u32 {
+        1 << *self as u32
+    }
+
+    /// The nominal position of this speaker, as an azimuth and an elevation
+    /// in degrees. The azimuth is measured clockwise from the front, so that
+    /// speakers on the right are positive. The low frequency channel has no
+    /// position.
+    pub fn position(&self) -> Option<(f64, f64)> {
+        let position = match self {
+            Speaker::FrontLeft => (-30.0, 0.0),
+            Speaker::FrontRight => (30.0, 0.0),
+            Speaker::FrontCenter => (0.0, 0.0),
+            Speaker::LowFrequency => return None,
+            Speaker::BackLeft => (-135.0, 0.0),
+            Speaker::BackRight => (135.0, 0.0),
+            Speaker::FrontLeftOfCenter => (-15.0, 0.0),
+            Speaker::FrontRightOfCenter => (15.0, 0.0),
+            Speaker::BackCenter => (180.0, 0.0),
+            Speaker::SideLeft => (-90.0, 0.0),
+            Speaker::SideRight => (90.0, 0.0),
+            Speaker::TopCenter => (0.0, 90.0),
+            Speaker::TopFrontLeft => (-45.0, 45.0),
+            Speaker::TopFrontCenter => (0.0, 45.0),
+            Speaker::TopFrontRight => (45.0, 45.0),
+            Speaker::TopBackLeft => (-135.0, 45.0),
+            Speaker::TopBackCenter => (180.0, 45.0),
+            Speaker::TopBackRight => (135.0, 45.0),
+        };
+
+        Some(position)
+    }
+
+    /// The rank of this speaker in a channel ordering convention.
+    fn rank(&self, order: Order) -> usize {
+        use Speaker::*;
+
+        // The ear level speakers of the film and AAC orders, followed by the
+        // height speakers in the order of their bits.
+        const FILM: [Speaker; 11] = [
+            FrontLeft,
+            FrontLeftOfCenter,
+            FrontCenter,
+            FrontRightOfCenter,
+            FrontRight,
+            SideLeft,
+            SideRight,
+            BackLeft,
+            BackCenter,
+            BackRight,
+            LowFrequency,
+        ];
+        const AAC: [Speaker; 11] = [
+            FrontCenter,
+            FrontLeftOfCenter,
+            FrontRightOfCenter,
+            FrontLeft,
+            FrontRight,
+            SideLeft,
+            SideRight,
+            BackLeft,
+            BackRight,
+            BackCenter,
+            LowFrequency,
+        ];
+
+        let ear_level = match order {
+            Order::Smpte => return *self as usize,
+            Order::Film => &FILM,
+            Order::Aac => &AAC,
+        };
+
+        ear_level
+            .iter()
+            .position(|s| s == self)
+            .unwrap_or(*self as usize)
+    }
+}
+
+/// A convention for the order of the channels of a [`Layout`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Order {
+    /// The order of the bits of a channel mask, as used by SMPTE, WAVE and
+    /// FLAC files: L R C LFE Ls Rs for 5.1.
+    Smpte,
+
+    /// The order used in film post-production, and by Pro Tools and DTS:
+    /// L C R Ls Rs LFE for 5.1.
+    Film,
+
+    /// The order of AAC channel configurations: C L R Ls Rs LFE for 5.1.
+    Aac,
+}
+
+/// The speaker of each channel of a [`Frame`].
+///
+/// A [`Frame`] only knows its number of channels, so a [`Layout`] can be
+/// carried alongside a [`Signal`](crate::Signal) with
+/// [`Signal::with_layout`](crate::Signal::with_layout), to look up channels
+/// by speaker and to reorder them between conventions.
+///
+/// ```
+/// use sampara::Fixed;
+/// use sampara::channel::{Layout, Order, Speaker};
+///
+/// fn main() {
+///     let layout = Layout::surround_5_1();
+///     assert_eq!(layout.mask(), 0x3f);
+///     assert_eq!(layout.channel(Speaker::LowFrequency), Some(3));
+///
+///     let film = layout.clone().ordered(Order::Film);
+///     assert_eq!(film.channel(Speaker::LowFrequency), Some(5));
+///
+///     let frame = Fixed::from([0.1, 0.2, 0.3, 0.4, 0.5, 0.6]);
+///     let speakers = layout.speakers_of(&frame);
+///     assert_eq!(speakers.left(), Some(&0.1));
+///     assert_eq!(speakers.lfe(), Some(&0.4));
+///     assert_eq!(speakers.side_left(), None);
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Layout {
+    speakers: Vec<Speaker>,
+}
+
+impl Layout {
+    /// Creates a new [`Layout`] from the speaker of each channel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a speaker appears more than once.
+    pub fn new(speakers: Vec<Speaker>) -> Self {
+        for (i, speaker) in speakers.iter().enumerate() {
+            assert!(
+                !speakers[..i].contains(speaker),
+                "{speaker:?} appears more than once in the layout"
+            );
+        }
+
+        Self { speakers }
+    }
+
+    /// Creates a new [`Layout`] from a WAVE_FORMAT_EXTENSIBLE channel mask,
+    /// with the channels in the order of their bits. Bits that do not
+    /// correspond to a [`Speaker`] are ignored.
+    pub fn from_mask(mask: u32) -> Self {
+        Self {
+            speakers: Speaker::ALL
+                .into_iter()
+                .filter(|s| mask & s.mask() != 0)
+                .collect(),
+        }
+    }
+
+    /// A single front center channel.
+    pub fn mono() -> Self {
+        Self::from_mask(0x4)
+    }
+
+    /// L R.
+    pub fn stereo() -> Self {
+        Self::from_mask(0x3)
+    }
+
+    /// L R C LFE Ls Rs, with the surround channels at the back.
+    pub fn surround_5_1() -> Self {
+        Self::from_mask(0x3f)
+    }
+
+    /// L R C LFE Lb Rb Ls Rs.
+    pub fn surround_7_1() -> Self {
+        Self::from_mask(0x63f)
+    }
+
+    /// The 5.1 layout with two height channels, as a Dolby Atmos bed.
+    pub fn atmos_5_1_2() -> Self {
+        Self::from_mask(0x3f | 0x5000)
+    }
+
+    /// The 5.1 layout with four height channels, as a Dolby Atmos bed.
+    pub fn atmos_5_1_4() -> Self {
+        Self::from_mask(0x3f | 0x2d000)
+    }
+
+    /// The 7.1 layout with two height channels, as a Dolby Atmos bed.
+    pub fn atmos_7_1_2() -> Self {
+        Self::from_mask(0x63f | 0x5000)
+    }
+
+    /// The 7.1 layout with four height channels, as a Dolby Atmos bed.
+    pub fn atmos_7_1_4() -> Self {
+        Self::from_mask(0x63f | 0x2d000)
+    }
+
+    /// Reorders the channels to follow an ordering convention.
+    pub fn ordered(mut self, order: Order) -> Self {
+        self.speakers.sort_by_key(|s| s.rank(order));
+        self
+    }
+
+    /// The WAVE_FORMAT_EXTENSIBLE channel mask of the speakers. The mask does
+    /// not record their order.
+    pub fn mask(&self) -> u32 {
+        self.speakers.iter().fold(0, |mask, s| mask | s.mask())
+    }
+
+    pub fn speakers(&self) -> &[Speaker] {
+        &self.speakers
+    }
+
+    /// The number of channels.
+    pub fn channels(&self) -> usize {
+        self.speakers.len()
+    }
+
+    /// The channel of a speaker, if the layout has it.
+    pub fn channel(&self, speaker: Speaker) -> Option<usize> {
+        self.speakers.iter().position(|&s| s == speaker)
+    }
+
+    /// For each channel of another [`Layout`], the channel of this layout
+    /// with the same speaker, or [`None`] if the layouts do not have the
+    /// same speakers.
+    pub fn mapping_to(&self, other: &Layout) -> Option<Vec<usize>> {
+        if self.channels() != other.channels() {
+            return None;
+        }
+
+        other.speakers.iter().map(|&s| self.channel(s)).collect()
+    }
+
+    /// The [`Sample`](crate::Sample) of a [`Frame`] for a speaker.
+    pub fn get<'a, F: Frame>(&self, frame: &'a F, speaker: Speaker) -> Option<&'a F::Sample> {
+        frame.get(self.channel(speaker)?)
+    }
+
+    /// Like [`Self::get`], but returns a mutable reference instead.
+    pub fn get_mut<'a, F: Frame>(
+        &self,
+        frame: &'a mut F,
+        speaker: Speaker,
+    ) -> Option<&'a mut F::Sample> {
+        frame.get_mut(self.channel(speaker)?)
+    }
+
+    /// Views a [`Frame`] through this layout, with named accessors for each
+    /// speaker.
+    pub fn speakers_of<'a, F: Frame>(&'a self, frame: &'a F) -> Speakers<'a, F> {
+        Speakers {
+            layout: self,
+            frame,
+        }
+    }
+}
+
+/// A [`Frame`] viewed through a [`Layout`].
+///
+/// This is created by [`Layout::speakers_of`].
+#[derive(Copy, Clone, Debug)]
+pub struct Speakers<'a, F> {
+    layout: &'a Layout,
+    frame: &'a F,
+}
+
+macro_rules! speaker_accessors {
+    ($($(#[$attr:meta])* $name:ident => $speaker:ident;)*) => {
+        $(
+            $(#[$attr])*
+            pub fn $name(&self) -> Option<&'a F::Sample> {
+                self.get(Speaker::$speaker)
+            }
+        )*
+    };
+}
+
+impl<'a, F: Frame> Speakers<'a, F> {
+    pub fn get(&self, speaker: Speaker) -> Option<&'a F::Sample> {
+        self.layout.get(self.frame, speaker)
+    }
+
+    speaker_accessors! {
+        /// The front left channel.
+        left => FrontLeft;
+        /// The front right channel.
+        right => FrontRight;
+        /// The front center channel.
+        center => FrontCenter;
+        /// The low frequency effects channel.
+        lfe => LowFrequency;
+        /// The back left channel, which is the left surround channel of 5.1.
+        back_left => BackLeft;
+        /// The back right channel, which is the right surround channel of
+        /// 5.1.
+        back_right => BackRight;
+        /// The side left channel.
+        side_left => SideLeft;
+        /// The side right channel.
+        side_right => SideRight;
+        /// The top front left channel.
+        top_front_left => TopFrontLeft;
+        /// The top front right channel.
+        top_front_right => TopFrontRight;
+        /// The top back left channel.
+        top_back_left => TopBackLeft;
+        /// The top back right channel.
+        top_back_right => TopBackRight;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orders() {
+        use Speaker::*;
+
+        let surround = Layout::surround_5_1();
+        assert_eq!(
+            surround.clone().ordered(Order::Film).speakers(),
+            [
+                FrontLeft,
+                FrontCenter,
+                FrontRight,
+                BackLeft,
+                BackRight,
+                LowFrequency
+            ]
+        );
+        assert_eq!(
+            surround.clone().ordered(Order::Aac).speakers(),
+            [
+                FrontCenter,
+                FrontLeft,
+                FrontRight,
+                BackLeft,
+                BackRight,
+                LowFrequency
+            ]
+        );
+        assert_eq!(
+            surround.clone().ordered(Order::Aac).ordered(Order::Smpte),
+            surround
+        );
+
+        assert_eq!(
+            Layout::atmos_7_1_4().ordered(Order::Film).speakers(),
+            [
+                FrontLeft,
+                FrontCenter,
+                FrontRight,
+                SideLeft,
+                SideRight,
+                BackLeft,
+                BackRight,
+                LowFrequency,
+                TopFrontLeft,
+                TopFrontRight,
+                TopBackLeft,
+                TopBackRight,
+            ]
+        );
+    }
+
+    #[test]
+    fn masks() {
+        for layout in [
+            Layout::mono(),
+            Layout::stereo(),
+            Layout::surround_5_1(),
+            Layout::surround_7_1(),
+            Layout::atmos_5_1_2(),
+            Layout::atmos_5_1_4(),
+            Layout::atmos_7_1_2(),
+            Layout::atmos_7_1_4(),
+        ] {
+            assert_eq!(Layout::from_mask(layout.mask()), layout);
+        }
+
+        assert_eq!(Layout::atmos_7_1_4().channels(), 12);
+        assert_eq!(Layout::atmos_5_1_2().channels(), 8);
+        assert_eq!(Layout::from_mask(0x8000_0003), Layout::stereo());
+    }
+
+    #[test]
+    fn mapping() {
+        let smpte = Layout::surround_5_1();
+        let film = smpte.clone().ordered(Order::Film);
+
+        assert_eq!(smpte.mapping_to(&film), Some(vec![0, 2, 1, 4, 5, 3]));
+        assert_eq!(smpte.mapping_to(&Layout::surround_7_1()), None);
+        assert_eq!(
+            Layout::stereo().mapping_to(&Layout::new(vec![Speaker::FrontLeft, Speaker::BackLeft])),
+            None
+        );
+    }
+}
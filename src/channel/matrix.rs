@@ -0,0 +1,181 @@
+use std::f64::consts::FRAC_1_SQRT_2;
+
+/// A matrix of gains, which maps the channels of an input [`Frame`] to the
+/// channels of an output [`Frame`]. Each output channel is the sum of the
+/// input channels, each multiplied by its gain.
+///
+/// The presets follow the downmix equations of ITU-R BS.775, with channels
+/// in the order of WAVE and SMPTE files: L, R, C, LFE, Ls, Rs for 5.1, and
+/// L, R, C, LFE, Lb, Rb, Ls, Rs for 7.1.
+///
+/// ```
+/// use sampara::channel::ChannelMatrix;
+///
+/// fn main() {
+///     let matrix = ChannelMatrix::from([[1.0, 0.5], [0.0, 0.5]]);
+///     assert_eq!((matrix.inputs(), matrix.outputs()), (2, 2));
+///     assert_eq!(matrix.gain(0, 1), 0.5);
+///
+///     let mut output = [0.0; 2];
+///     matrix.apply(&[1.0, 2.0], &mut output);
+///     assert_eq!(output, [2.0, 1.0]);
+/// }
+/// ```
+///
+/// [`Frame`]: crate::Frame
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChannelMatrix {
+    inputs: usize,
+    outputs: usize,
+    // The gains, one row of inputs for each output.
+    gains: Vec<f64>,
+}
+
+impl ChannelMatrix {
+    /// Creates a new [`ChannelMatrix`] with all gains set to zero.
+    pub fn new(inputs: usize, outputs: usize) -> Self {
+        Self {
+            inputs,
+            outputs,
+            gains: vec![0.0; inputs * outputs],
+        }
+    }
+
+    /// Creates a new [`ChannelMatrix`] that passes `channels` channels
+    /// through unchanged.
+    pub fn identity(channels: usize) -> Self {
+        let mut matrix = Self::new(channels, channels);
+        for c in 0..channels {
+            matrix.set_gain(c, c, 1.0);
+        }
+        matrix
+    }
+
+    /// Creates a new [`ChannelMatrix`] from its rows, one for each output
+    /// channel, each with a gain for every input channel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rows do not all have the same length.
+    pub fn from_rows(rows: &[&[f64]]) -> Self {
+        let inputs = rows.first().map_or(0, |row| row.len());
+        assert!(
+            rows.iter().all(|row| row.len() == inputs),
+            "all rows of a channel matrix must have the same length"
+        );
+
+        Self {
+            inputs,
+            outputs: rows.len(),
+            gains: rows.concat(),
+        }
+    }
+
+    /// Downmixes 5.1 to stereo: the center and surround channels are mixed
+    /// into the front channels at -3 dB, and the LFE channel is dropped.
+    pub fn surround_5_1_to_stereo() -> Self {
+        const G: f64 = FRAC_1_SQRT_2;
+        Self::from([[1.0, 0.0, G, 0.0, G, 0.0], [0.0, 1.0, G, 0.0, 0.0, G]])
+    }
+
+    /// Downmixes stereo to mono, at -3 dB.
+    pub fn stereo_to_mono() -> Self {
+        const G: f64 = FRAC_1_SQRT_2;
+        Self::from([[G, G]])
+    }
+
+    /// Upmixes mono to stereo, at -3 dB in each channel.
+    pub fn mono_to_stereo() -> Self {
+        const G: f64 = FRAC_1_SQRT_2;
+        Self::from([[G], [G]])
+    }
+
+    /// Downmixes 7.1 to 5.1: the back and side channels are mixed into the
+    /// surround channels at -3 dB.
+    pub fn surround_7_1_to_5_1() -> Self {
+        const G: f64 = FRAC_1_SQRT_2;
+        Self::from([
+            [1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0],
+            [0.0, 0.0, 0.0, 0.0, G, 0.0, G, 0.0],
+            [0.0, 0.0, 0.0, 0.0, 0.0, G, 0.0, G],
+        ])
+    }
+
+    /// The number of input channels.
+    pub fn inputs(&self) -> usize {
+        self.inputs
+    }
+
+    /// The number of output channels.
+    pub fn outputs(&self) -> usize {
+        self.outputs
+    }
+
+    /// The gain from an input channel to an output channel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either channel is out of range.
+    pub fn gain(&self, output: usize, input: usize) -> f64 {
+        self.gains[self.index(output, input)]
+    }
+
+    /// Sets the gain from an input channel to an output channel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either channel is out of range.
+    pub fn set_gain(&mut self, output: usize, input: usize, gain: f64) {
+        let index = self.index(output, input);
+        self.gains[index] = gain;
+    }
+
+    /// Computes the output channels from the input channels.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slices do not have the number of input and output
+    /// channels of the matrix.
+    pub fn apply(&self, input: &[f64], output: &mut [f64]) {
+        assert_eq!(input.len(), self.inputs, "wrong number of input channels");
+        assert_eq!(
+            output.len(),
+            self.outputs,
+            "wrong number of output channels"
+        );
+
+        if self.inputs == 0 {
+            output.fill(0.0);
+            return;
+        }
+
+        for (y, row) in output.iter_mut().zip(self.gains.chunks_exact(self.inputs)) {
+            *y = row.iter().zip(input).map(|(g, x)| g * x).sum();
+        }
+    }
+
+    fn index(&self, output: usize, input: usize) -> usize {
+        assert!(
+            output < self.outputs && input < self.inputs,
+            "channel ({output}, {input}) out of range for a {}x{} matrix",
+            self.outputs,
+            self.inputs
+        );
+        output * self.inputs + input
+    }
+}
+
+impl<const M: usize, const N: usize> From<[[f64; M]; N]> for ChannelMatrix {
+    /// Creates a new [`ChannelMatrix`] from `N` rows of `M` gains, mapping `M`
+    /// input channels to `N` output channels.
+    fn from(rows: [[f64; M]; N]) -> Self {
+        Self {
+            inputs: M,
+            outputs: N,
+            gains: rows.concat(),
+        }
+    }
+}
@@ -0,0 +1,278 @@
+//! Channel layouts and mixing: naming the speaker of each channel, reordering
+//! channels between conventions, and mapping signals from one number of
+//! channels to another.
+
+mod layout;
+mod matrix;
+
+pub use self::layout::{Layout, Order, Speaker, Speakers};
+pub use self::matrix::ChannelMatrix;
+
+use crate::frame::Frame;
+use crate::sample::FromSample;
+use crate::sample::to_f64;
+use crate::signal::{ExactSizeSignal, Signal};
+
+/// A [`Signal`] that maps the channels of an underlying [`Signal`] through a
+/// [`ChannelMatrix`], yielding [`Frame`]s of type `F`.
+///
+/// This is created by [`Signal::remix`].
+pub struct Remix<S, F>
+where
+    S: Signal,
+{
+    signal: S,
+    matrix: ChannelMatrix,
+    template: F,
+    input: Vec<f64>,
+    output: Vec<f64>,
+}
+
+impl<S, F> Remix<S, F>
+where
+    S: Signal,
+    F: Frame<Sample = <S::Frame as Frame>::Sample>,
+    F::Sample: FromSample<f64>,
+{
+    pub(crate) fn new(signal: S, matrix: ChannelMatrix) -> Self {
+        let template = F::equil_with_channels(matrix.outputs()).unwrap_or_else(|| {
+            panic!(
+                "the output frame type cannot have {} channels",
+                matrix.outputs()
+            )
+        });
+
+        Self {
+            signal,
+            input: Vec::with_capacity(matrix.inputs()),
+            output: vec![0.0; matrix.outputs()],
+            matrix,
+            template,
+        }
+    }
+
+    pub fn matrix(&self) -> &ChannelMatrix {
+        &self.matrix
+    }
+
+    /// Replaces the gains of the [`ChannelMatrix`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new matrix has a different number of input or output
+    /// channels.
+    pub fn set_matrix(&mut self, matrix: ChannelMatrix) {
+        assert!(
+            matrix.inputs() == self.matrix.inputs() && matrix.outputs() == self.matrix.outputs(),
+            "the new matrix must have the same number of channels"
+        );
+        self.matrix = matrix;
+    }
+}
+
+impl<S, F> Signal for Remix<S, F>
+where
+    S: Signal,
+    F: Frame<Sample = <S::Frame as Frame>::Sample>,
+    F::Sample: FromSample<f64>,
+{
+    type Frame = F;
+
+    fn next(&mut self) -> Option<Self::Frame> {
+        let frame = self.signal.next()?;
+
+        self.input.clear();
+        self.input.extend(frame.iter().map(to_f64));
+        self.matrix.apply(&self.input, &mut self.output);
+
+        let mut output = self.template.clone();
+        for (sample, &y) in output.iter_mut().zip(&self.output) {
+            *sample = FromSample::from_sample(y);
+        }
+
+        Some(output)
+    }
+
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        self.signal.len_hint()
+    }
+}
+
+impl<S, F> ExactSizeSignal for Remix<S, F>
+where
+    S: ExactSizeSignal,
+    F: Frame<Sample = <S::Frame as Frame>::Sample>,
+    F::Sample: FromSample<f64>,
+{
+}
+
+/// A [`Signal`] that carries the [`Layout`] of its channels.
+///
+/// This is created by [`Signal::with_layout`].
+pub struct WithLayout<S> {
+    signal: S,
+    layout: Layout,
+}
+
+impl<S> WithLayout<S>
+where
+    S: Signal,
+{
+    pub(crate) fn new(signal: S, layout: Layout) -> Self {
+        Self { signal, layout }
+    }
+
+    pub fn layout(&self) -> &Layout {
+        &self.layout
+    }
+
+    pub fn into_inner(self) -> S {
+        self.signal
+    }
+
+    /// Reorders the channels to match another [`Layout`] with the same
+    /// speakers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the layouts do not have the same speakers.
+    ///
+    /// ```
+    /// use sampara::{signal, Signal};
+    /// use sampara::channel::{Layout, Order, Speaker};
+    ///
+    /// fn main() {
+    ///     // L R C LFE Ls Rs.
+    ///     let input = vec![1i16, 2, 3, 4, 5, 6];
+    ///     let mut signal = signal::from_samples_fixed::<_, 6>(input)
+    ///         .with_layout(Layout::surround_5_1())
+    ///         .ordered(Order::Film);
+    ///
+    ///     assert_eq!(signal.layout().channel(Speaker::FrontCenter), Some(1));
+    ///     assert_eq!(signal.next(), Some([1, 3, 2, 5, 6, 4].into()));
+    /// }
+    /// ```
+    pub fn reorder(self, layout: Layout) -> WithLayout<Reorder<S>> {
+        let mapping = self.layout.mapping_to(&layout).unwrap_or_else(|| {
+            panic!(
+                "cannot reorder {:?} to {:?}",
+                self.layout.speakers(),
+                layout.speakers()
+            )
+        });
+
+        WithLayout::new(
+            Reorder {
+                signal: self.signal,
+                mapping,
+            },
+            layout,
+        )
+    }
+
+    /// Reorders the channels to follow an ordering convention.
+    pub fn ordered(self, order: Order) -> WithLayout<Reorder<S>> {
+        let layout = self.layout.clone().ordered(order);
+        self.reorder(layout)
+    }
+}
+
+impl<S> Signal for WithLayout<S>
+where
+    S: Signal,
+{
+    type Frame = S::Frame;
+
+    fn next(&mut self) -> Option<Self::Frame> {
+        let frame = self.signal.next()?;
+        assert_eq!(
+            frame.len(),
+            self.layout.channels(),
+            "the frame does not match the number of channels of the layout"
+        );
+
+        Some(frame)
+    }
+
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        self.signal.len_hint()
+    }
+}
+
+impl<S> ExactSizeSignal for WithLayout<S> where S: ExactSizeSignal {}
+
+/// A [`Signal`] that reorders the channels of an underlying [`Signal`].
+///
+/// This is created by [`WithLayout::reorder`].
+pub struct Reorder<S> {
+    signal: S,
+    // The input channel of each output channel.
+    mapping: Vec<usize>,
+}
+
+impl<S> Signal for Reorder<S>
+where
+    S: Signal,
+{
+    type Frame = S::Frame;
+
+    fn next(&mut self) -> Option<Self::Frame> {
+        let frame = self.signal.next()?;
+
+        let mut output = frame.clone();
+        for (sample, &channel) in output.iter_mut().zip(&self.mapping) {
+            if let Some(&input) = frame.get(channel) {
+                *sample = input;
+            }
+        }
+
+        Some(output)
+    }
+
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        self.signal.len_hint()
+    }
+}
+
+impl<S> ExactSizeSignal for Reorder<S> where S: ExactSizeSignal {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signal;
+
+    use std::f64::consts::FRAC_1_SQRT_2;
+
+    #[test]
+    fn presets() {
+        let mut output = [0.0; 2];
+        ChannelMatrix::surround_5_1_to_stereo().apply(&[1.0, 2.0, 1.0, 5.0, 1.0, 0.0], &mut output);
+        assert_eq!(output, [1.0 + 2.0 * FRAC_1_SQRT_2, 2.0 + FRAC_1_SQRT_2]);
+
+        let mut output = [0.0; 6];
+        ChannelMatrix::surround_7_1_to_5_1()
+            .apply(&[1.0, 2.0, 3.0, 4.0, 1.0, 0.0, 1.0, 2.0], &mut output);
+        assert_eq!(
+            output,
+            [1.0, 2.0, 3.0, 4.0, 2.0 * FRAC_1_SQRT_2, 2.0 * FRAC_1_SQRT_2]
+        );
+
+        // The -3 dB of the upmix and of the downmix cancel out, so a round
+        // trip keeps the amplitude.
+        let mut stereo = [0.0; 2];
+        let mut mono = [0.0; 1];
+        ChannelMatrix::mono_to_stereo().apply(&[1.0], &mut stereo);
+        ChannelMatrix::stereo_to_mono().apply(&stereo, &mut mono);
+        assert!((mono[0] - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn integer_samples() {
+        let mut signal = signal::from_samples_fixed::<_, 2>(vec![100i16, -50, i16::MAX, i16::MAX])
+            .remix::<crate::Fixed<i16, 1>>(ChannelMatrix::from([[1.0, 1.0]]));
+
+        assert_eq!(signal.next(), Some([50].into()));
+        assert_eq!(signal.next(), Some([i16::MAX].into()));
+        assert_eq!(signal.next(), None);
+    }
+}
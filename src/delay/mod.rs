@@ -0,0 +1,490 @@
+//! Delay lines, with integer and fractional delays.
+//!
+//! A [`DelayLine`] stores the most recent values pushed into it, and can be
+//! read at any delay up to its maximum. Fractional delays are read through a
+//! [`Tap`], which interpolates between the stored values.
+
+use std::f64::consts::PI;
+
+use crate::frame::Frame;
+use crate::sample::{FloatSample, Sample};
+use crate::signal::{ExactSizeSignal, Signal, len_hint_with_tail};
+use crate::spectrum::Window;
+
+/// The number of points on each side of the delay position that are used by
+/// [`Interpolation::Sinc`].
+const SINC_HALF_WIDTH: usize = 8;
+
+/// The window applied to the sinc kernel of [`Interpolation::Sinc`].
+const SINC_WINDOW: Window = Window::Kaiser { beta: 7.0 };
+
+/// A circular buffer that delays the values pushed into it.
+///
+/// A delay of 0 reads the most recently pushed value.
+///
+/// ```
+/// use sampara::delay::DelayLine;
+///
+/// fn main() {
+///     let mut line = DelayLine::new(4);
+///
+///     for x in [1.0f32, 2.0, 3.0] {
+///         line.push(x);
+///     }
+///
+///     assert_eq!(line.read(0), 3.0);
+///     assert_eq!(line.read(2), 1.0);
+///     assert_eq!(line.read(4), 0.0);
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct DelayLine<T> {
+    buffer: Vec<T>,
+    // The index of the most recently pushed value.
+    write: usize,
+    max_delay: usize,
+}
+
+impl<T> DelayLine<T>
+where
+    T: Clone,
+{
+    /// Creates a new [`DelayLine`] with a maximum delay of `max_delay`, filled
+    /// with copies of `value`.
+    pub fn filled(max_delay: usize, value: T) -> Self {
+        // Fractional reads look a few values past the maximum delay, and a
+        // power of two length allows wrapping with a mask.
+        let len = (max_delay + SINC_HALF_WIDTH + 2).next_power_of_two();
+
+        Self {
+            buffer: vec![value; len],
+            write: 0,
+            max_delay,
+        }
+    }
+
+    /// The maximum delay that can be read.
+    pub fn max_delay(&self) -> usize {
+        self.max_delay
+    }
+
+    /// Pushes a new value, which is then read at a delay of 0.
+    pub fn push(&mut self, value: T) {
+        self.write = (self.write + 1) & (self.buffer.len() - 1);
+        self.buffer[self.write] = value;
+    }
+
+    /// Reads the value that was pushed `delay` pushes ago.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `delay` is greater than the maximum delay.
+    pub fn read(&self, delay: usize) -> T {
+        assert!(
+            delay <= self.max_delay,
+            "delay {delay} is greater than the maximum delay {}",
+            self.max_delay
+        );
+
+        self.get(delay).clone()
+    }
+
+    /// Returns the value at a delay, without checking the maximum delay.
+    fn get(&self, delay: usize) -> &T {
+        &self.buffer[self.write.wrapping_sub(delay) & (self.buffer.len() - 1)]
+    }
+
+    /// Fills this [`DelayLine`] with copies of `value`.
+    pub fn fill(&mut self, value: T) {
+        self.buffer.fill(value);
+    }
+}
+
+impl<T> DelayLine<T>
+where
+    T: Sample,
+{
+    /// Creates a new [`DelayLine`] with a maximum delay of `max_delay`, filled
+    /// with equilibrium.
+    pub fn new(max_delay: usize) -> Self {
+        Self::filled(max_delay, T::EQUILIBRIUM)
+    }
+
+    pub fn reset(&mut self) {
+        self.fill(T::EQUILIBRIUM);
+    }
+}
+
+impl<T> DelayLine<T>
+where
+    T: FloatSample,
+{
+    /// Reads at a fractional delay, interpolating linearly between the two
+    /// nearest values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `delay` is negative or greater than the maximum delay.
+    pub fn read_linear(&self, delay: f64) -> T {
+        let (index, frac) = self.split(delay);
+
+        let a = *self.get(index);
+        let b = *self.get(index + 1);
+
+        a + (b - a) * T::from_sample(frac)
+    }
+
+    /// Reads at a fractional delay, using cubic Lagrange interpolation
+    /// between the four nearest values. Delays below 1 have no newer value
+    /// on their near side, so they use the four most recent values, with the
+    /// position between the first two of them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `delay` is negative or greater than the maximum delay.
+    pub fn read_lagrange(&self, delay: f64) -> T {
+        let (index, _) = self.split(delay);
+
+        // The interpolated position is between the second and third points,
+        // except when there is no newer value to use as the first point.
+        let base = index.max(1) - 1;
+        let t = delay - base as f64;
+
+        (0..4)
+            .map(|k| {
+                let weight: f64 = (0..4)
+                    .filter(|&j| j != k)
+                    .map(|j| (t - j as f64) / (k as f64 - j as f64))
+                    .product();
+
+                *self.get(base + k) * T::from_sample(weight)
+            })
+            .fold(T::EQUILIBRIUM, |acc, x| acc + x)
+    }
+
+    /// Reads at a fractional delay, using a windowed sinc kernel over the 16
+    /// nearest values. This has the flattest frequency response, but is the
+    /// most expensive. Values newer than the most recent are taken to be
+    /// equal to it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `delay` is negative or greater than the maximum delay.
+    pub fn read_sinc(&self, delay: f64) -> T {
+        let (index, frac) = self.split(delay);
+
+        if frac == 0.0 {
+            return *self.get(index);
+        }
+
+        let half_width = SINC_HALF_WIDTH as f64;
+
+        (0..2 * SINC_HALF_WIDTH)
+            .map(|k| {
+                // The offset of this point from the delay position.
+                let offset = k as f64 - (SINC_HALF_WIDTH - 1) as f64 - frac;
+                let x = PI * offset;
+                let weight = x.sin() / x * SINC_WINDOW.eval(0.5 + offset / (2.0 * half_width));
+
+                let point = (index + k).saturating_sub(SINC_HALF_WIDTH - 1);
+                *self.get(point) * T::from_sample(weight)
+            })
+            .fold(T::EQUILIBRIUM, |acc, x| acc + x)
+    }
+
+    /// Splits a fractional delay into its integer and fractional parts.
+    fn split(&self, delay: f64) -> (usize, f64) {
+        assert!(
+            (0.0..=self.max_delay as f64).contains(&delay),
+            "delay {delay} is outside of the range [0, {}]",
+            self.max_delay
+        );
+
+        let index = delay.floor();
+        (index as usize, delay - index)
+    }
+}
+
+/// How a [`Tap`] reads between the values of a [`DelayLine`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Interpolation {
+    /// Rounds the delay to the nearest integer.
+    None,
+
+    /// Linear interpolation between the two nearest values. This is cheap,
+    /// but attenuates high frequencies at fractional delays.
+    Linear,
+
+    /// Cubic Lagrange interpolation between the four nearest values.
+    Lagrange,
+
+    /// A first-order Thiran allpass filter, which has a flat magnitude
+    /// response, but whose state makes it best suited to delays that change
+    /// slowly. Delays below 0.5 are read as 0.5.
+    Allpass,
+
+    /// A windowed sinc kernel over the 16 nearest values.
+    Sinc,
+}
+
+/// A read position in a [`DelayLine`], at a fractional delay.
+///
+/// Any number of [`Tap`]s can read from the same [`DelayLine`]. Since
+/// [`Interpolation::Allpass`] is a recursive filter, each [`Tap`] must be
+/// read exactly once after each push.
+///
+/// ```
+/// use sampara::delay::{DelayLine, Interpolation, Tap};
+///
+/// fn main() {
+///     let mut line = DelayLine::new(16);
+///     let mut taps = [
+///         Tap::new(2.5, Interpolation::Linear),
+///         Tap::new(5.0, Interpolation::Lagrange),
+///     ];
+///
+///     let mut outputs = Vec::new();
+///     for i in 0..8 {
+///         line.push(i as f64);
+///         outputs.push(taps.iter_mut().map(|t| t.read(&line)).collect::<Vec<_>>());
+///     }
+///
+///     // A ramp is delayed exactly by both kinds of interpolation.
+///     assert_eq!(outputs[7], vec![4.5, 2.0]);
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Tap<T>
+where
+    T: FloatSample,
+{
+    delay: f64,
+    interpolation: Interpolation,
+    // The integer delay and coefficient of the allpass filter.
+    allpass_delay: usize,
+    allpass_coeff: T,
+    // The previous output, for the allpass filter.
+    previous: T,
+}
+
+impl<T> Tap<T>
+where
+    T: FloatSample,
+{
+    /// Creates a new [`Tap`] at a fractional delay.
+    pub fn new(delay: f64, interpolation: Interpolation) -> Self {
+        let mut tap = Self {
+            delay,
+            interpolation,
+            allpass_delay: 0,
+            allpass_coeff: T::EQUILIBRIUM,
+            previous: T::EQUILIBRIUM,
+        };
+
+        tap.set_delay(delay);
+        tap
+    }
+
+    pub fn delay(&self) -> f64 {
+        self.delay
+    }
+
+    /// Sets a new delay, which takes effect on the next read.
+    pub fn set_delay(&mut self, delay: f64) {
+        self.delay = delay;
+
+        if self.interpolation == Interpolation::Allpass {
+            // The fractional part of the allpass delay is kept in [0.5, 1.5),
+            // where its phase delay is closest to flat.
+            let delay = delay.max(0.5);
+            let integer = (delay - 0.5).floor();
+            let frac = delay - integer;
+
+            self.allpass_delay = integer as usize;
+            self.allpass_coeff = T::from_sample((1.0 - frac) / (1.0 + frac));
+        }
+    }
+
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    pub fn reset(&mut self) {
+        self.previous = T::EQUILIBRIUM;
+    }
+
+    /// Reads the value at the delay of this [`Tap`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the delay is negative or greater than the maximum delay of
+    /// the [`DelayLine`].
+    pub fn read(&mut self, line: &DelayLine<T>) -> T {
+        match self.interpolation {
+            Interpolation::None => {
+                let (index, frac) = line.split(self.delay);
+                line.read(index + (frac >= 0.5) as usize)
+            }
+            Interpolation::Linear => line.read_linear(self.delay),
+            Interpolation::Lagrange => line.read_lagrange(self.delay),
+            Interpolation::Sinc => line.read_sinc(self.delay),
+            Interpolation::Allpass => {
+                let _ = line.split(self.delay);
+
+                let a = self.allpass_coeff;
+                let x0 = *line.get(self.allpass_delay);
+                let x1 = *line.get(self.allpass_delay + 1);
+
+                self.previous = a * x0 + x1 - a * self.previous;
+                self.previous
+            }
+        }
+    }
+}
+
+/// A [`Signal`] that delays an underlying [`Signal`] by a whole number of
+/// [`Frame`]s.
+///
+/// This is created by [`Signal::delay`].
+pub struct Delay<S>
+where
+    S: Signal,
+{
+    signal: S,
+    samples: usize,
+    line: Option<DelayLine<S::Frame>>,
+    // A frame of equilibrium, used to flush the delay once the input ends.
+    silence: Option<S::Frame>,
+    // The number of frames left to output, once the input has run out.
+    tail: Option<usize>,
+}
+
+impl<S> Delay<S>
+where
+    S: Signal,
+{
+    pub(crate) fn new(signal: S, samples: usize) -> Self {
+        Self {
+            signal,
+            samples,
+            line: None,
+            silence: None,
+            tail: None,
+        }
+    }
+
+    /// The delay, in [`Frame`]s.
+    pub fn samples(&self) -> usize {
+        self.samples
+    }
+}
+
+impl<S> Signal for Delay<S>
+where
+    S: Signal,
+{
+    type Frame = S::Frame;
+
+    fn next(&mut self) -> Option<Self::Frame> {
+        let frame = match self.tail {
+            Some(0) => return None,
+            Some(ref mut tail) => {
+                *tail -= 1;
+                self.silence.clone()?
+            }
+            None => match self.signal.next() {
+                Some(frame) => frame,
+                None => {
+                    self.tail = Some(self.samples);
+                    return self.next();
+                }
+            },
+        };
+
+        let line = match &mut self.line {
+            Some(line) => line,
+            None => {
+                let mut silence = frame.clone();
+                silence
+                    .iter_mut()
+                    .for_each(|s| *s = <S::Frame as Frame>::Sample::EQUILIBRIUM);
+
+                self.silence = Some(silence.clone());
+                self.line.insert(DelayLine::filled(self.samples, silence))
+            }
+        };
+
+        line.push(frame);
+        Some(line.read(self.samples))
+    }
+
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        let (input, tail) = match self.tail {
+            Some(tail) => ((0, Some(0)), tail),
+            None => (self.signal.len_hint(), self.samples),
+        };
+
+        len_hint_with_tail(input, tail, self.silence.is_some())
+    }
+}
+
+impl<S> ExactSizeSignal for Delay<S> where S: ExactSizeSignal {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(line: &mut DelayLine<f64>, len: usize, freq: f64) {
+        for i in 0..len {
+            line.push((2.0 * PI * freq * i as f64).sin());
+        }
+    }
+
+    #[test]
+    fn fractional_reads() {
+        let mut line = DelayLine::new(64);
+        let (len, freq) = (100, 0.05);
+        sine(&mut line, len, freq);
+
+        let expected = |delay: f64| (2.0 * PI * freq * ((len - 1) as f64 - delay)).sin();
+
+        for delay in [3.25, 10.5, 20.75, 40.1] {
+            let linear = line.read_linear(delay);
+            let lagrange = line.read_lagrange(delay);
+            let sinc = line.read_sinc(delay);
+
+            assert!((linear - expected(delay)).abs() < 2e-2, "{delay}");
+            assert!((lagrange - expected(delay)).abs() < 1e-3, "{delay}");
+
+            // Short delays are missing the newer half of the sinc kernel.
+            if delay >= SINC_HALF_WIDTH as f64 {
+                assert!((sinc - expected(delay)).abs() < 1e-3, "{delay}");
+            }
+        }
+    }
+
+    #[test]
+    fn allpass() {
+        let mut line = DelayLine::new(64);
+        let mut tap = Tap::new(10.3, Interpolation::Allpass);
+        let freq = 0.02;
+
+        // Once the filter has settled, a low frequency sine is delayed by the
+        // fractional delay.
+        let mut output = 0.0;
+        for i in 0..200 {
+            line.push((2.0 * PI * freq * i as f64).sin());
+            output = tap.read(&line);
+        }
+
+        let expected = (2.0 * PI * freq * (199.0 - 10.3)).sin();
+        assert!((output - expected).abs() < 1e-2, "{output} {expected}");
+    }
+
+    #[test]
+    #[should_panic]
+    fn read_past_max_delay() {
+        let line = DelayLine::<f32>::new(8);
+        line.read_linear(8.5);
+    }
+}
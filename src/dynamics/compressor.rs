@@ -0,0 +1,416 @@
+use crate::dynamics::{Detection, Detector, DynamicsProcessor, Link, coefficient, from_db, to_db};
+
+/// The parameters of a [`Compressor`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CompressorParams {
+    /// The level above which the gain is reduced, in decibels.
+    pub threshold: f64,
+
+    /// The ratio of input level change to output level change above the
+    /// threshold. A ratio of 1 does nothing, and an infinite ratio limits.
+    pub ratio: f64,
+
+    /// The width of the soft knee around the threshold, in decibels. A width
+    /// of 0 is a hard knee.
+    pub knee: f64,
+
+    /// The time taken for the gain to be reduced, in seconds.
+    pub attack: f64,
+
+    /// The time taken for the gain to recover, in seconds.
+    pub release: f64,
+
+    /// The gain applied after compression, in decibels.
+    pub makeup: f64,
+
+    /// How the level of the key signal is measured.
+    pub detection: Detection,
+
+    /// How the levels of multiple channels are combined.
+    pub link: Link,
+}
+
+impl CompressorParams {
+    /// Returns the static gain reduction, in decibels, for a level in
+    /// decibels.
+    fn curve(&self, level: f64) -> f64 {
+        let CompressorParams {
+            threshold,
+            ratio,
+            knee,
+            ..
+        } = *self;
+
+        let over = level - threshold;
+        let slope = ratio.recip() - 1.0;
+
+        if 2.0 * over <= -knee {
+            0.0
+        } else if 2.0 * over.abs() < knee {
+            slope * (over + knee / 2.0).powi(2) / (2.0 * knee)
+        } else {
+            slope * over
+        }
+    }
+}
+
+impl Default for CompressorParams {
+    fn default() -> Self {
+        Self {
+            threshold: -20.0,
+            ratio: 4.0,
+            knee: 6.0,
+            attack: 0.01,
+            release: 0.1,
+            makeup: 0.0,
+            detection: Detection::Peak,
+            link: Link::Max,
+        }
+    }
+}
+
+/// A feed-forward compressor, which reduces the gain of levels above a
+/// threshold.
+///
+/// The gain reduction is computed in decibels from the level of the key
+/// signal, and is then smoothed with separate attack and release times.
+///
+/// ```
+/// use sampara::{signal, Signal};
+/// use sampara::dynamics::{Compressor, CompressorParams};
+///
+/// fn main() {
+///     let params = CompressorParams {
+///         threshold: -12.0,
+///         ratio: 4.0,
+///         knee: 0.0,
+///         attack: 0.0,
+///         ..Default::default()
+///     };
+///
+///     // A constant level of 0 dB is 12 dB over the threshold, and so is
+///     // reduced to 3 dB over the threshold.
+///     let compressor = Compressor::new(params, 48000.0);
+///     let mut signal = signal::from_samples_fixed::<_, 1>(vec![1.0f64; 8]).dynamics(compressor);
+///
+///     let [x] = signal.nth(7).unwrap().into_array();
+///     assert!((20.0 * x.log10() - -9.0).abs() < 1e-9);
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Compressor {
+    params: CompressorParams,
+    sample_rate: f64,
+    detector: Detector,
+    attack: f64,
+    release: f64,
+    levels: Vec<f64>,
+    // The smoothed gain reduction of each channel, in decibels.
+    reduction: Vec<f64>,
+}
+
+impl Compressor {
+    /// Creates a new [`Compressor`] for a given sample rate.
+    pub fn new(params: CompressorParams, sample_rate: f64) -> Self {
+        Self {
+            params,
+            sample_rate,
+            detector: Detector::new(params.detection, params.link, sample_rate),
+            attack: coefficient(params.attack, sample_rate),
+            release: coefficient(params.release, sample_rate),
+            levels: Vec::new(),
+            reduction: Vec::new(),
+        }
+    }
+
+    pub fn params(&self) -> &CompressorParams {
+        &self.params
+    }
+
+    /// Sets new parameters. The current gain reduction is kept, unless the
+    /// detection or link mode changes.
+    pub fn set_params(&mut self, params: CompressorParams) {
+        if (params.detection, params.link) != (self.params.detection, self.params.link) {
+            self.detector = Detector::new(params.detection, params.link, self.sample_rate);
+        }
+
+        self.attack = coefficient(params.attack, self.sample_rate);
+        self.release = coefficient(params.release, self.sample_rate);
+        self.params = params;
+    }
+}
+
+impl DynamicsProcessor for Compressor {
+    fn process(&mut self, key: &[f64], gains: &mut [f64]) {
+        self.detector.levels(key, &mut self.levels);
+        self.reduction.resize(key.len(), 0.0);
+
+        for ((gain, reduction), &level) in
+            gains.iter_mut().zip(&mut self.reduction).zip(&self.levels)
+        {
+            let target = self.params.curve(to_db(level));
+
+            let coeff = if target < *reduction {
+                self.attack
+            } else {
+                self.release
+            };
+            *reduction = coeff * *reduction + (1.0 - coeff) * target;
+
+            *gain = from_db(*reduction + self.params.makeup);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.detector.reset();
+        self.reduction.clear();
+    }
+}
+
+/// The parameters of an [`Expander`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ExpanderParams {
+    /// The level below which the gain is reduced, in decibels.
+    pub threshold: f64,
+
+    /// The ratio of output level change to input level change below the
+    /// threshold. A ratio of 1 does nothing.
+    pub ratio: f64,
+
+    /// The width of the soft knee around the threshold, in decibels. A width
+    /// of 0 is a hard knee.
+    pub knee: f64,
+
+    /// The maximum gain reduction, in negative decibels.
+    pub range: f64,
+
+    /// The time taken for the gain to recover once the level rises, in
+    /// seconds.
+    pub attack: f64,
+
+    /// The time taken for the gain to be reduced once the level falls, in
+    /// seconds.
+    pub release: f64,
+
+    /// How the level of the key signal is measured.
+    pub detection: Detection,
+
+    /// How the levels of multiple channels are combined.
+    pub link: Link,
+}
+
+impl ExpanderParams {
+    /// Returns the static gain reduction, in decibels, for a level in
+    /// decibels.
+    fn curve(&self, level: f64) -> f64 {
+        let ExpanderParams {
+            threshold,
+            ratio,
+            knee,
+            range,
+            ..
+        } = *self;
+
+        let over = level - threshold;
+        let slope = ratio - 1.0;
+
+        let reduction = if 2.0 * over >= knee {
+            0.0
+        } else if 2.0 * over.abs() < knee {
+            -slope * (over - knee / 2.0).powi(2) / (2.0 * knee)
+        } else {
+            slope * over
+        };
+
+        reduction.max(range)
+    }
+}
+
+impl Default for ExpanderParams {
+    fn default() -> Self {
+        Self {
+            threshold: -40.0,
+            ratio: 2.0,
+            knee: 6.0,
+            range: -60.0,
+            attack: 0.001,
+            release: 0.1,
+            detection: Detection::Peak,
+            link: Link::Max,
+        }
+    }
+}
+
+/// A downward expander, which reduces the gain of levels below a threshold,
+/// increasing the dynamic range.
+///
+/// ```
+/// use sampara::{signal, Signal};
+/// use sampara::dynamics::{Expander, ExpanderParams};
+///
+/// fn main() {
+///     let params = ExpanderParams {
+///         threshold: -20.0,
+///         ratio: 2.0,
+///         knee: 0.0,
+///         release: 0.0,
+///         ..Default::default()
+///     };
+///
+///     // A constant level of -30 dB is 10 dB under the threshold, and so is
+///     // reduced to 20 dB under the threshold.
+///     let expander = Expander::new(params, 48000.0);
+///     let input = vec![10.0f64.powf(-30.0 / 20.0); 8];
+///     let mut signal = signal::from_samples_fixed::<_, 1>(input).dynamics(expander);
+///
+///     let [x] = signal.nth(7).unwrap().into_array();
+///     assert!((20.0 * x.log10() - -40.0).abs() < 1e-9);
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Expander {
+    params: ExpanderParams,
+    sample_rate: f64,
+    detector: Detector,
+    attack: f64,
+    release: f64,
+    levels: Vec<f64>,
+    // The smoothed gain reduction of each channel, in decibels.
+    reduction: Vec<f64>,
+}
+
+impl Expander {
+    /// Creates a new [`Expander`] for a given sample rate.
+    pub fn new(params: ExpanderParams, sample_rate: f64) -> Self {
+        Self {
+            params,
+            sample_rate,
+            detector: Detector::new(params.detection, params.link, sample_rate),
+            attack: coefficient(params.attack, sample_rate),
+            release: coefficient(params.release, sample_rate),
+            levels: Vec::new(),
+            reduction: Vec::new(),
+        }
+    }
+
+    pub fn params(&self) -> &ExpanderParams {
+        &self.params
+    }
+
+    /// Sets new parameters. The current gain reduction is kept, unless the
+    /// detection or link mode changes.
+    pub fn set_params(&mut self, params: ExpanderParams) {
+        if (params.detection, params.link) != (self.params.detection, self.params.link) {
+            self.detector = Detector::new(params.detection, params.link, self.sample_rate);
+        }
+
+        self.attack = coefficient(params.attack, self.sample_rate);
+        self.release = coefficient(params.release, self.sample_rate);
+        self.params = params;
+    }
+}
+
+impl DynamicsProcessor for Expander {
+    fn process(&mut self, key: &[f64], gains: &mut [f64]) {
+        self.detector.levels(key, &mut self.levels);
+        self.reduction.resize(key.len(), 0.0);
+
+        for ((gain, reduction), &level) in
+            gains.iter_mut().zip(&mut self.reduction).zip(&self.levels)
+        {
+            let target = self.params.curve(to_db(level));
+
+            let coeff = if target > *reduction {
+                self.attack
+            } else {
+                self.release
+            };
+            *reduction = coeff * *reduction + (1.0 - coeff) * target;
+
+            *gain = from_db(*reduction);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.detector.reset();
+        self.reduction.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compressor_curve() {
+        let compressor = Compressor::new(
+            CompressorParams {
+                threshold: -20.0,
+                ratio: 4.0,
+                knee: 10.0,
+                ..Default::default()
+            },
+            48000.0,
+        );
+
+        // Below and above the knee, the curve is a straight line.
+        assert_eq!(compressor.params.curve(-40.0), 0.0);
+        assert_eq!(compressor.params.curve(-25.0), 0.0);
+        assert!((compressor.params.curve(0.0) - -15.0).abs() < 1e-12);
+
+        // The curve is continuous at both edges of the knee.
+        assert!(compressor.params.curve(-25.0 + 1e-9).abs() < 1e-6);
+        assert!((compressor.params.curve(-15.0) - -3.75).abs() < 1e-9);
+    }
+
+    #[test]
+    fn compressor_attack_and_release() {
+        let params = CompressorParams {
+            threshold: -20.0,
+            ratio: 10.0,
+            knee: 0.0,
+            attack: 0.01,
+            release: 0.1,
+            ..Default::default()
+        };
+        let mut compressor = Compressor::new(params, 1000.0);
+        let mut gains = [1.0];
+
+        // After one attack time constant, most of the reduction is applied.
+        for _ in 0..10 {
+            compressor.process(&[1.0], &mut gains);
+        }
+        let reduction = to_db(gains[0]);
+        assert!(
+            reduction < -18.0 * 0.6 && reduction > -18.0 * 0.7,
+            "{reduction}"
+        );
+
+        for _ in 0..1000 {
+            compressor.process(&[1.0], &mut gains);
+        }
+        assert!((to_db(gains[0]) - -18.0).abs() < 1e-9);
+
+        // Release is ten times slower.
+        for _ in 0..10 {
+            compressor.process(&[0.0], &mut gains);
+        }
+        assert!(to_db(gains[0]) < -16.0);
+    }
+
+    #[test]
+    fn expander_range() {
+        let params = ExpanderParams {
+            threshold: -20.0,
+            ratio: 4.0,
+            knee: 0.0,
+            range: -30.0,
+            ..Default::default()
+        };
+        let expander = Expander::new(params, 48000.0);
+
+        assert_eq!(expander.params.curve(0.0), 0.0);
+        assert_eq!(expander.params.curve(-25.0), -15.0);
+        assert_eq!(expander.params.curve(-60.0), -30.0);
+    }
+}
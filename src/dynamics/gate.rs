@@ -0,0 +1,227 @@
+use crate::dynamics::{Detection, Detector, DynamicsProcessor, Link, coefficient, from_db, to_db};
+
+/// The parameters of a [`Gate`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GateParams {
+    /// The level above which the gate opens, in decibels.
+    pub threshold: f64,
+
+    /// How far below the threshold the level must fall for the gate to close,
+    /// in decibels. This prevents chattering around the threshold.
+    pub hysteresis: f64,
+
+    /// The time that the gate stays open after the level falls below the
+    /// closing threshold, in seconds.
+    pub hold: f64,
+
+    /// The time taken for the gate to open, in seconds.
+    pub attack: f64,
+
+    /// The time taken for the gate to close, in seconds.
+    pub release: f64,
+
+    /// The gain when the gate is closed, in negative decibels. Use
+    /// [`f64::NEG_INFINITY`] to silence the signal completely.
+    pub range: f64,
+
+    /// How the level of the key signal is measured.
+    pub detection: Detection,
+
+    /// How the levels of multiple channels are combined.
+    pub link: Link,
+}
+
+impl Default for GateParams {
+    fn default() -> Self {
+        Self {
+            threshold: -40.0,
+            hysteresis: 6.0,
+            hold: 0.05,
+            attack: 0.001,
+            release: 0.05,
+            range: -80.0,
+            detection: Detection::Peak,
+            link: Link::Max,
+        }
+    }
+}
+
+/// The state of one channel of a [`Gate`].
+#[derive(Copy, Clone, Debug, Default)]
+struct Channel {
+    open: bool,
+    // The number of frames left before the gate may close.
+    hold: usize,
+    // The smoothed linear gain.
+    gain: f64,
+}
+
+/// A noise gate, which silences (or attenuates) the signal while the level is
+/// below a threshold.
+///
+/// ```
+/// use sampara::{signal, Signal};
+/// use sampara::dynamics::{Gate, GateParams};
+///
+/// fn main() {
+///     let params = GateParams {
+///         threshold: -20.0,
+///         hold: 0.0,
+///         attack: 0.0,
+///         release: 0.0,
+///         range: f64::NEG_INFINITY,
+///         ..Default::default()
+///     };
+///
+///     let gate = Gate::new(params, 48000.0);
+///     let input = vec![0.001f32, 0.5, 0.001];
+///     let mut signal = signal::from_samples_fixed::<_, 1>(input).dynamics(gate);
+///
+///     assert_eq!(signal.next(), Some([0.0].into()));
+///     assert_eq!(signal.next(), Some([0.5].into()));
+///     assert_eq!(signal.next(), Some([0.0].into()));
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Gate {
+    params: GateParams,
+    sample_rate: f64,
+    detector: Detector,
+    hold: usize,
+    attack: f64,
+    release: f64,
+    levels: Vec<f64>,
+    channels: Vec<Channel>,
+}
+
+impl Gate {
+    /// Creates a new [`Gate`] for a given sample rate.
+    pub fn new(params: GateParams, sample_rate: f64) -> Self {
+        Self {
+            params,
+            sample_rate,
+            detector: Detector::new(params.detection, params.link, sample_rate),
+            hold: (params.hold * sample_rate).round() as usize,
+            attack: coefficient(params.attack, sample_rate),
+            release: coefficient(params.release, sample_rate),
+            levels: Vec::new(),
+            channels: Vec::new(),
+        }
+    }
+
+    pub fn params(&self) -> &GateParams {
+        &self.params
+    }
+
+    /// Sets new parameters. The current state of the gate is kept, unless the
+    /// detection or link mode changes.
+    pub fn set_params(&mut self, params: GateParams) {
+        if (params.detection, params.link) != (self.params.detection, self.params.link) {
+            self.detector = Detector::new(params.detection, params.link, self.sample_rate);
+        }
+
+        self.hold = (params.hold * self.sample_rate).round() as usize;
+        self.attack = coefficient(params.attack, self.sample_rate);
+        self.release = coefficient(params.release, self.sample_rate);
+        self.params = params;
+    }
+
+    /// Returns `true` if the gate is open for a given channel.
+    pub fn is_open(&self, channel: usize) -> bool {
+        self.channels.get(channel).is_some_and(|c| c.open)
+    }
+}
+
+impl DynamicsProcessor for Gate {
+    fn process(&mut self, key: &[f64], gains: &mut [f64]) {
+        self.detector.levels(key, &mut self.levels);
+
+        let closed = from_db(self.params.range);
+        self.channels.resize(
+            key.len(),
+            Channel {
+                open: false,
+                hold: 0,
+                gain: closed,
+            },
+        );
+
+        let close_threshold = self.params.threshold - self.params.hysteresis;
+
+        for ((gain, channel), &level) in gains.iter_mut().zip(&mut self.channels).zip(&self.levels)
+        {
+            let level = to_db(level);
+
+            if level >= self.params.threshold {
+                channel.open = true;
+            }
+
+            if channel.open {
+                if level >= close_threshold {
+                    channel.hold = self.hold;
+                } else if channel.hold > 0 {
+                    channel.hold -= 1;
+                } else {
+                    channel.open = false;
+                }
+            }
+
+            let (target, coeff) = if channel.open {
+                (1.0, self.attack)
+            } else {
+                (closed, self.release)
+            };
+            channel.gain = coeff * channel.gain + (1.0 - coeff) * target;
+
+            *gain = channel.gain;
+        }
+    }
+
+    fn reset(&mut self) {
+        self.detector.reset();
+        self.channels.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hysteresis_and_hold() {
+        let params = GateParams {
+            threshold: -20.0,
+            hysteresis: 10.0,
+            hold: 2.0,
+            attack: 0.0,
+            release: 0.0,
+            range: f64::NEG_INFINITY,
+            ..Default::default()
+        };
+        let mut gate = Gate::new(params, 1.0);
+        let mut gains = [0.0];
+
+        let db = |db: f64| 10.0f64.powf(db / 20.0);
+
+        // Below the opening threshold, the gate stays closed.
+        gate.process(&[db(-25.0)], &mut gains);
+        assert_eq!(gains[0], 0.0);
+
+        gate.process(&[db(-10.0)], &mut gains);
+        assert_eq!(gains[0], 1.0);
+
+        // Between the two thresholds, the gate stays open.
+        gate.process(&[db(-25.0)], &mut gains);
+        assert_eq!(gains[0], 1.0);
+
+        // Below the closing threshold, the gate is held open for 2 frames.
+        for _ in 0..2 {
+            gate.process(&[db(-40.0)], &mut gains);
+            assert_eq!(gains[0], 1.0);
+        }
+
+        gate.process(&[db(-40.0)], &mut gains);
+        assert_eq!(gains[0], 0.0);
+        assert!(!gate.is_open(0));
+    }
+}
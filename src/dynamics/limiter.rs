@@ -0,0 +1,381 @@
+use std::collections::VecDeque;
+
+use crate::dynamics::{DynamicsProcessor, Link, coefficient, from_db};
+use crate::fir::{self, Response};
+use crate::spectrum::Window;
+
+/// The oversampling factor used to estimate true peaks.
+const OVERSAMPLING: usize = 4;
+
+/// The number of input samples that the true peak estimate is delayed by.
+/// The interpolation filter has `2 * TRUE_PEAK_DELAY` taps per phase.
+const TRUE_PEAK_DELAY: usize = 6;
+
+/// The parameters of a [`Limiter`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LimiterParams {
+    /// The maximum output level, in decibels.
+    pub ceiling: f64,
+
+    /// How far ahead the limiter looks for peaks, in seconds. The gain is
+    /// reduced smoothly over this time before each peak.
+    pub lookahead: f64,
+
+    /// The time taken for the gain to recover, in seconds.
+    pub release: f64,
+
+    /// Whether to limit the estimated true (inter-sample) peaks of the signal,
+    /// rather than just its sample peaks. This adds a few samples of latency.
+    pub true_peak: bool,
+
+    /// How the peaks of multiple channels are combined. [`Link::Average`]
+    /// is treated as [`Link::Max`], so that no channel goes over the
+    /// ceiling.
+    pub link: Link,
+}
+
+impl Default for LimiterParams {
+    fn default() -> Self {
+        Self {
+            ceiling: -1.0,
+            lookahead: 0.005,
+            release: 0.05,
+            true_peak: true,
+            link: Link::Max,
+        }
+    }
+}
+
+/// The gain computation state for one channel (or all linked channels) of a
+/// [`Limiter`].
+#[derive(Clone, Debug)]
+struct Channel {
+    // The frame index and required gain of candidates for the sliding window
+    // minimum, in increasing order of both.
+    minimum: VecDeque<(usize, f64)>,
+    // The gain after the release smoothing.
+    released: f64,
+    // The last `lookahead` released gains, and their sum.
+    window: Vec<f64>,
+    sum: f64,
+}
+
+impl Channel {
+    fn new(lookahead: usize) -> Self {
+        Self {
+            minimum: VecDeque::new(),
+            released: 1.0,
+            window: vec![1.0; lookahead],
+            sum: lookahead as f64,
+        }
+    }
+
+    /// Pushes the gain required at frame `index`, and returns the gain to
+    /// apply to the frame `lookahead` frames before it.
+    fn push(&mut self, index: usize, required: f64, release: f64) -> f64 {
+        let lookahead = self.window.len();
+
+        // The minimum required gain over the last `lookahead + 1` frames.
+        while self.minimum.back().is_some_and(|&(_, g)| g >= required) {
+            self.minimum.pop_back();
+        }
+        self.minimum.push_back((index, required));
+        while self
+            .minimum
+            .front()
+            .is_some_and(|&(i, _)| i + lookahead < index)
+        {
+            self.minimum.pop_front();
+        }
+        let minimum = self.minimum.front().map_or(1.0, |&(_, g)| g);
+
+        // Falling gains are followed instantly, and rising gains are smoothed.
+        self.released = if minimum < self.released {
+            minimum
+        } else {
+            release * self.released + (1.0 - release) * minimum
+        };
+
+        // Averaging the gains over the lookahead ramps smoothly into each
+        // peak, while staying below the gain required at that peak.
+        let slot = index % lookahead;
+        self.sum += self.released - self.window[slot];
+        self.window[slot] = self.released;
+        if slot == lookahead - 1 {
+            self.sum = self.window.iter().sum();
+        }
+
+        self.sum / lookahead as f64
+    }
+}
+
+/// A brickwall lookahead limiter, which keeps the level of the signal below a
+/// ceiling.
+///
+/// The signal is delayed by the lookahead time, so that the gain can be
+/// reduced smoothly before each peak arrives. With true peak detection, the
+/// key signal is oversampled to estimate the peaks between samples, which
+/// would otherwise exceed the ceiling after digital-to-analog conversion.
+///
+/// ```
+/// use sampara::{signal, Signal};
+/// use sampara::dynamics::{Limiter, LimiterParams};
+///
+/// fn main() {
+///     let params = LimiterParams {
+///         ceiling: -6.0,
+///         true_peak: false,
+///         ..Default::default()
+///     };
+///
+///     let limiter = Limiter::new(params, 48000.0);
+///     let input: Vec<f32> = (0..4800).map(|i| (i as f32 * 0.05).sin()).collect();
+///     let signal = signal::from_samples_fixed::<_, 1>(input).dynamics(limiter);
+///
+///     let ceiling = 10.0f32.powf(-6.0 / 20.0);
+///     let mut signal = signal;
+///     while let Some(frame) = signal.next() {
+///         assert!(frame.into_array()[0].abs() <= ceiling + 1e-6);
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Limiter {
+    params: LimiterParams,
+    sample_rate: f64,
+    lookahead: usize,
+    release: f64,
+    // The polyphase interpolation filter, for true peak detection.
+    phases: Vec<Vec<f64>>,
+    // The last `2 * TRUE_PEAK_DELAY` key samples of each channel, most recent
+    // first.
+    history: Vec<VecDeque<f64>>,
+    index: usize,
+    peaks: Vec<f64>,
+    channels: Vec<Channel>,
+}
+
+impl Limiter {
+    /// Creates a new [`Limiter`] for a given sample rate.
+    pub fn new(params: LimiterParams, sample_rate: f64) -> Self {
+        // A half-band (at the original rate) low-pass filter, whose center
+        // tap lines up with an input sample. Each phase is one of the
+        // interpolated positions between input samples.
+        let len = 2 * TRUE_PEAK_DELAY * OVERSAMPLING + 1;
+        let cutoff = 0.5 / OVERSAMPLING as f64;
+        let kernel: Vec<f64> = fir::windowed_sinc(
+            Response::LowPass { cutoff },
+            len,
+            Window::Kaiser { beta: 8.0 },
+        );
+
+        let phases = (1..OVERSAMPLING)
+            .map(|p| {
+                kernel
+                    .iter()
+                    .skip(p)
+                    .step_by(OVERSAMPLING)
+                    .map(|h| h * OVERSAMPLING as f64)
+                    .collect()
+            })
+            .collect();
+
+        let mut limiter = Self {
+            params,
+            sample_rate,
+            lookahead: 0,
+            release: 0.0,
+            phases,
+            history: Vec::new(),
+            index: 0,
+            peaks: Vec::new(),
+            channels: Vec::new(),
+        };
+
+        limiter.set_params(params);
+        limiter
+    }
+
+    pub fn params(&self) -> &LimiterParams {
+        &self.params
+    }
+
+    /// Sets new parameters. If the lookahead time or true peak detection
+    /// changes, this resets the limiter and changes its latency.
+    pub fn set_params(&mut self, params: LimiterParams) {
+        let lookahead = ((params.lookahead * self.sample_rate).round() as usize).max(1);
+
+        if lookahead != self.lookahead || params.true_peak != self.params.true_peak {
+            self.reset();
+        }
+
+        self.lookahead = lookahead;
+        self.release = coefficient(params.release, self.sample_rate);
+        self.params = params;
+    }
+
+    /// Estimates the peak level of a channel around the sample that is
+    /// `TRUE_PEAK_DELAY` samples old.
+    fn true_peak(phases: &[Vec<f64>], history: &VecDeque<f64>) -> f64 {
+        let sample = history[TRUE_PEAK_DELAY];
+
+        phases
+            .iter()
+            .map(|phase| {
+                phase
+                    .iter()
+                    .zip(history)
+                    .map(|(h, x)| h * x)
+                    .sum::<f64>()
+                    .abs()
+            })
+            .fold(sample.abs(), f64::max)
+    }
+}
+
+impl DynamicsProcessor for Limiter {
+    fn process(&mut self, key: &[f64], gains: &mut [f64]) {
+        let channels = key.len();
+        let linked = self.params.link != Link::None;
+        let detectors = if linked { 1 } else { channels };
+
+        if self.channels.len() != detectors || self.history.len() != channels {
+            self.channels = vec![Channel::new(self.lookahead); detectors];
+            self.history = vec![VecDeque::from(vec![0.0; 2 * TRUE_PEAK_DELAY]); channels];
+        }
+
+        self.peaks.clear();
+        if self.params.true_peak {
+            for (history, &x) in self.history.iter_mut().zip(key) {
+                history.pop_back();
+                history.push_front(x);
+                self.peaks.push(Self::true_peak(&self.phases, history));
+            }
+        } else {
+            self.peaks.extend(key.iter().map(|x| x.abs()));
+        }
+
+        if linked {
+            // Limiting by the average would let the loudest channel go over
+            // the ceiling.
+            Link::Max.apply(&mut self.peaks);
+            self.peaks.resize(1, 0.0);
+        }
+
+        let ceiling = from_db(self.params.ceiling);
+        let index = self.index;
+        self.index += 1;
+
+        for (c, channel) in self.channels.iter_mut().enumerate() {
+            let peak = self.peaks[c];
+            let required = if peak > ceiling { ceiling / peak } else { 1.0 };
+            let gain = channel.push(index, required, self.release);
+
+            if linked {
+                gains.fill(gain);
+            } else {
+                gains[c] = gain;
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        self.history.clear();
+        self.channels.clear();
+        self.index = 0;
+    }
+
+    fn latency(&self) -> usize {
+        if self.params.true_peak {
+            self.lookahead + TRUE_PEAK_DELAY
+        } else {
+            self.lookahead
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::signal::{self, Signal};
+
+    fn limit(input: &[[f64; 2]], params: LimiterParams) -> Vec<[f64; 2]> {
+        let frames = input.iter().map(|&f| crate::Fixed::from(f));
+        let mut signal = signal::from_frames(frames).dynamics(Limiter::new(params, 1000.0));
+
+        let mut output = Vec::new();
+        while let Some(frame) = signal.next() {
+            output.push(frame.into_array());
+        }
+        output
+    }
+
+    #[test]
+    fn sample_peaks_stay_below_ceiling() {
+        let input: Vec<[f64; 2]> = (0..2000)
+            .map(|i| {
+                let t = i as f64;
+                let burst = if (i / 100) % 2 == 0 { 4.0 } else { 0.5 };
+                [burst * (t * 0.3).sin(), 0.1 * (t * 0.7).cos()]
+            })
+            .collect();
+
+        for link in [Link::None, Link::Max, Link::Average] {
+            let params = LimiterParams {
+                ceiling: -3.0,
+                lookahead: 0.01,
+                true_peak: false,
+                link,
+                ..Default::default()
+            };
+
+            let output = limit(&input, params);
+            let latency = 10;
+            assert_eq!(output.len(), input.len() + latency);
+
+            let ceiling = from_db(-3.0);
+            assert!(output.iter().flatten().all(|x| x.abs() <= ceiling + 1e-12));
+
+            // Unlinked, the quiet right channel is untouched.
+            if link == Link::None {
+                for (x, y) in input.iter().zip(&output[latency..]) {
+                    assert!((x[1] - y[1]).abs() < 1e-12);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn true_peaks() {
+        // A sine at a quarter of the sample rate, sampled at 45 degrees, has
+        // true peaks 3 dB above its sample peaks.
+        let input: Vec<[f64; 2]> = (0..400)
+            .map(|i| {
+                let x =
+                    (core::f64::consts::FRAC_PI_2 * i as f64 + core::f64::consts::FRAC_PI_4).sin();
+                [x, x]
+            })
+            .collect();
+
+        let params = LimiterParams {
+            ceiling: -1.0,
+            lookahead: 0.005,
+            release: 1.0,
+            true_peak: true,
+            link: Link::Max,
+        };
+
+        let output = limit(&input, params);
+        assert_eq!(output.len(), input.len() + 5 + TRUE_PEAK_DELAY);
+
+        // Once settled, the sample peaks are limited to 3 dB below the
+        // ceiling.
+        let peak = output[200..400]
+            .iter()
+            .map(|f| f[0].abs())
+            .fold(0.0, f64::max);
+        let expected = from_db(-1.0) * core::f64::consts::FRAC_1_SQRT_2;
+        assert!((peak - expected).abs() < 0.02, "{peak} {expected}");
+    }
+}
@@ -0,0 +1,596 @@
+//! Dynamic range processors: compressors, limiters, expanders and gates.
+//!
+//! Each processor implements [`DynamicsProcessor`], which computes a gain for
+//! each channel from the level of a key signal. They are applied to a
+//! [`Signal`] with [`Signal::dynamics`], where the key is the [`Signal`]
+//! itself, or with [`Signal::sidechain`], where the key is another
+//! [`Signal`]. They can also be turned into a [`Processor`] with
+//! [`DynamicsProcessor::into_processor`].
+
+mod compressor;
+mod gate;
+mod limiter;
+
+pub use self::compressor::{Compressor, CompressorParams, Expander, ExpanderParams};
+pub use self::gate::{Gate, GateParams};
+pub use self::limiter::{Limiter, LimiterParams};
+pub use crate::envelope::Link;
+
+use std::collections::VecDeque;
+
+use crate::envelope::{Follower, Mode, to_db};
+use crate::frame::Frame;
+use crate::processor::Processor;
+use crate::sample::to_f64;
+use crate::sample::{FloatSample, FromSample, Sample};
+use crate::signal::{ExactSizeSignal, Signal, len_hint_with_tail};
+
+/// Types that compute a gain for each channel of a [`Signal`] from the
+/// levels of a key signal, one [`Frame`] at a time.
+pub trait DynamicsProcessor {
+    /// Computes the linear gain to apply to each channel, given the key
+    /// sample for each channel. Both slices have the same length, which is
+    /// the number of channels of the processed [`Signal`].
+    fn process(&mut self, key: &[f64], gains: &mut [f64]);
+
+    /// Clears all internal state.
+    fn reset(&mut self);
+
+    /// The number of [`Frame`]s that the processed [`Signal`] must be delayed
+    /// by, so that its gains line up with it. This is used for lookahead.
+    fn latency(&self) -> usize {
+        0
+    }
+
+    /// Creates a new [`Processor`] that applies [`Self`] to blocks of
+    /// [`Frame`]s, keyed by their own levels.
+    ///
+    /// ```
+    /// use sampara::Fixed;
+    /// use sampara::dynamics::{DynamicsProcessor, Limiter, LimiterParams};
+    /// use sampara::processor::Processor;
+    ///
+    /// fn main() {
+    ///     let params = LimiterParams::default();
+    ///     let mut limiter = Limiter::new(params, 48000.0).into_processor::<Fixed<f32, 2>>();
+    ///     limiter.prepare(48000.0, 64);
+    ///
+    ///     let mut block = [Fixed::from([0.5, -0.5]); 64];
+    ///     limiter.process(&mut block);
+    ///
+    ///     // The output is delayed by the lookahead of the limiter.
+    ///     assert!(limiter.latency() > 0);
+    ///     assert_eq!(block[0], Fixed::from([0.0, 0.0]));
+    /// }
+    /// ```
+    fn into_processor<F>(self) -> BlockDynamics<F, Self>
+    where
+        Self: Sized,
+        F: Frame,
+        F::Sample: FloatSample,
+    {
+        BlockDynamics::new(self)
+    }
+}
+
+impl<P> DynamicsProcessor for &mut P
+where
+    P: DynamicsProcessor + ?Sized,
+{
+    fn process(&mut self, key: &[f64], gains: &mut [f64]) {
+        (**self).process(key, gains)
+    }
+
+    fn reset(&mut self) {
+        (**self).reset()
+    }
+
+    fn latency(&self) -> usize {
+        (**self).latency()
+    }
+}
+
+/// How the level of a key signal is measured.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Detection {
+    /// The absolute value of each sample.
+    Peak,
+
+    /// The root mean square over a sliding window of `window` seconds.
+    Rms { window: f64 },
+}
+
+/// Measures the levels of the channels of a key signal, according to a
+/// [`Detection`] mode and a [`Link`] mode.
+#[derive(Clone, Debug)]
+struct Detector {
+    detection: Detection,
+    link: Link,
+    sample_rate: f64,
+    followers: Vec<Follower>,
+}
+
+impl Detector {
+    fn new(detection: Detection, link: Link, sample_rate: f64) -> Self {
+        Self {
+            detection,
+            link,
+            sample_rate,
+            followers: Vec::new(),
+        }
+    }
+
+    fn reset(&mut self) {
+        self.followers.clear();
+    }
+
+    /// Writes the linear level of each channel into `levels`.
+    fn levels(&mut self, key: &[f64], levels: &mut Vec<f64>) {
+        if self.followers.len() != key.len() {
+            // The smoothing is left to each processor, so the followers only
+            // measure the level.
+            let mode = match self.detection {
+                Detection::Peak => Mode::Peak,
+                Detection::Rms { window } => Mode::Rms {
+                    window: (window * self.sample_rate).max(1.0),
+                },
+            };
+            self.followers = vec![Follower::new(0.0, 0.0, mode); key.len()];
+        }
+
+        levels.clear();
+        levels.extend(
+            key.iter()
+                .zip(&mut self.followers)
+                .map(|(&x, f)| f.process(x)),
+        );
+        self.link.apply(levels);
+    }
+}
+
+/// Returns the coefficient of a one-pole smoothing filter with a given time
+/// constant, in seconds.
+fn coefficient(time: f64, sample_rate: f64) -> f64 {
+    if time > 0.0 {
+        (-1.0 / (time * sample_rate)).exp()
+    } else {
+        0.0
+    }
+}
+
+/// Converts decibels to a linear gain.
+fn from_db(db: f64) -> f64 {
+    10.0f64.powf(db / 20.0)
+}
+
+/// The state shared by the [`Dynamics`] and [`Sidechain`] adapters.
+struct Core<F, P> {
+    processor: P,
+    // The delayed input frames, for lookahead.
+    delay: VecDeque<F>,
+    // A frame of equilibrium, used to flush the delay once the input ends.
+    silence: Option<F>,
+    key: Vec<f64>,
+    gains: Vec<f64>,
+    // The number of frames left to output, once the input has run out.
+    tail: Option<usize>,
+}
+
+impl<F, P> Core<F, P>
+where
+    F: Frame,
+    F::Sample: FloatSample,
+    P: DynamicsProcessor,
+{
+    fn new(processor: P) -> Self {
+        Self {
+            processor,
+            delay: VecDeque::new(),
+            silence: None,
+            key: Vec::new(),
+            gains: Vec::new(),
+            tail: None,
+        }
+    }
+
+    /// Fills the delay with silence, shaped like the first input frame, so
+    /// that the output lags the input by the latency of the processor.
+    fn prime(&mut self, frame: &F) {
+        if self.silence.is_none() {
+            let mut silence = frame.clone();
+            silence.iter_mut().for_each(|s| *s = F::Sample::EQUILIBRIUM);
+
+            let latency = self.processor.latency();
+            self.delay
+                .extend(core::iter::repeat_n(silence.clone(), latency));
+            self.silence = Some(silence);
+        }
+    }
+
+    /// Returns the next input frame, or a silent frame while flushing the
+    /// delay once the input has run out.
+    fn input(&mut self, next: impl FnOnce() -> Option<F>) -> Option<F> {
+        if self.tail.is_none() {
+            match next() {
+                Some(frame) => {
+                    self.prime(&frame);
+                    return Some(frame);
+                }
+                None => self.tail = Some(self.processor.latency()),
+            }
+        }
+
+        match self.tail {
+            Some(ref mut tail) if *tail > 0 => {
+                *tail -= 1;
+
+                // Nothing is left to flush if there was no input at all.
+                self.silence.clone()
+            }
+            _ => None,
+        }
+    }
+
+    /// The length hint of the output, given that of the input.
+    fn len_hint(&self, input: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        match self.tail {
+            Some(tail) => len_hint_with_tail((0, Some(0)), tail, self.silence.is_some()),
+            None => len_hint_with_tail(input, self.processor.latency(), self.silence.is_some()),
+        }
+    }
+
+    /// Stores the samples of the key frame for the next call to
+    /// [`Self::process`].
+    fn set_key(&mut self, key: impl Iterator<Item = f64>) {
+        self.key.clear();
+        self.key.extend(key);
+    }
+
+    /// Processes one input frame in place with the samples of the stored key
+    /// frame, replacing it with the delayed output frame. A key with one
+    /// channel applies to all channels.
+    fn process(&mut self, frame: &mut F) {
+        let channels = frame.len();
+
+        if self.key.len() == 1 {
+            self.key.resize(channels, self.key[0]);
+        }
+        assert_eq!(
+            self.key.len(),
+            channels,
+            "key must have one channel or as many channels as the signal"
+        );
+
+        self.gains.clear();
+        self.gains.resize(channels, 1.0);
+        self.processor.process(&self.key, &mut self.gains);
+
+        if let Some(mut delayed) = self.delay.pop_front() {
+            core::mem::swap(frame, &mut delayed);
+            self.delay.push_back(delayed);
+        }
+
+        for (sample, &gain) in frame.iter_mut().zip(&self.gains) {
+            *sample = *sample * F::Sample::from_sample(gain);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.processor.reset();
+        self.delay.clear();
+        self.silence = None;
+        self.tail = None;
+    }
+}
+
+/// A [`Signal`] that applies a [`DynamicsProcessor`] to an underlying
+/// [`Signal`], keyed by its own levels.
+///
+/// This is created by [`Signal::dynamics`].
+pub struct Dynamics<S, P>
+where
+    S: Signal,
+{
+    signal: S,
+    core: Core<S::Frame, P>,
+}
+
+impl<S, P> Dynamics<S, P>
+where
+    S: Signal,
+    <S::Frame as Frame>::Sample: FloatSample,
+    P: DynamicsProcessor,
+{
+    pub(crate) fn new(signal: S, processor: P) -> Self {
+        Self {
+            signal,
+            core: Core::new(processor),
+        }
+    }
+
+    /// Returns a reference to the wrapped [`DynamicsProcessor`].
+    pub fn processor(&self) -> &P {
+        &self.core.processor
+    }
+
+    /// Returns a mutable reference to the wrapped [`DynamicsProcessor`].
+    pub fn processor_mut(&mut self) -> &mut P {
+        &mut self.core.processor
+    }
+
+    /// The number of [`Frame`]s that the output is delayed by, relative to
+    /// the input.
+    pub fn latency(&self) -> usize {
+        self.core.processor.latency()
+    }
+}
+
+impl<S, P> Signal for Dynamics<S, P>
+where
+    S: Signal,
+    <S::Frame as Frame>::Sample: FloatSample,
+    P: DynamicsProcessor,
+{
+    type Frame = S::Frame;
+
+    fn next(&mut self) -> Option<Self::Frame> {
+        let signal = &mut self.signal;
+        let mut frame = self.core.input(|| signal.next())?;
+
+        self.core.set_key(frame.iter().map(to_f64));
+        self.core.process(&mut frame);
+        Some(frame)
+    }
+
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        self.core.len_hint(self.signal.len_hint())
+    }
+}
+
+impl<S, P> ExactSizeSignal for Dynamics<S, P>
+where
+    S: ExactSizeSignal,
+    <S::Frame as Frame>::Sample: FloatSample,
+    P: DynamicsProcessor,
+{
+}
+
+/// A [`Signal`] that applies a [`DynamicsProcessor`] to an underlying
+/// [`Signal`], keyed by the levels of a separate sidechain [`Signal`].
+///
+/// The sidechain must have either one channel, which keys every channel, or
+/// the same number of channels as the underlying [`Signal`], otherwise
+/// [`Signal::next`] panics. If the sidechain runs out first, it is treated as
+/// silence.
+///
+/// This is created by [`Signal::sidechain`].
+pub struct Sidechain<S, K, P>
+where
+    S: Signal,
+    K: Signal,
+{
+    signal: S,
+    key: K,
+    core: Core<S::Frame, P>,
+}
+
+impl<S, K, P> Sidechain<S, K, P>
+where
+    S: Signal,
+    K: Signal,
+    <S::Frame as Frame>::Sample: FloatSample,
+    P: DynamicsProcessor,
+{
+    pub(crate) fn new(signal: S, key: K, processor: P) -> Self {
+        Self {
+            signal,
+            key,
+            core: Core::new(processor),
+        }
+    }
+
+    /// Returns a reference to the wrapped [`DynamicsProcessor`].
+    pub fn processor(&self) -> &P {
+        &self.core.processor
+    }
+
+    /// Returns a mutable reference to the wrapped [`DynamicsProcessor`].
+    pub fn processor_mut(&mut self) -> &mut P {
+        &mut self.core.processor
+    }
+
+    /// The number of [`Frame`]s that the output is delayed by, relative to
+    /// the input.
+    pub fn latency(&self) -> usize {
+        self.core.processor.latency()
+    }
+}
+
+impl<S, K, P> Signal for Sidechain<S, K, P>
+where
+    S: Signal,
+    K: Signal,
+    <S::Frame as Frame>::Sample: FloatSample,
+    P: DynamicsProcessor,
+{
+    type Frame = S::Frame;
+
+    fn next(&mut self) -> Option<Self::Frame> {
+        let signal = &mut self.signal;
+        let mut frame = self.core.input(|| signal.next())?;
+
+        match self.key.next() {
+            Some(key) => self.core.set_key(key.iter().map(to_f64)),
+            None => self.core.set_key(core::iter::once(0.0)),
+        }
+
+        self.core.process(&mut frame);
+        Some(frame)
+    }
+
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        self.core.len_hint(self.signal.len_hint())
+    }
+}
+
+impl<S, K, P> ExactSizeSignal for Sidechain<S, K, P>
+where
+    S: ExactSizeSignal,
+    K: Signal,
+    <S::Frame as Frame>::Sample: FloatSample,
+    P: DynamicsProcessor,
+{
+}
+
+/// A [`Processor`] that applies a [`DynamicsProcessor`] to blocks of
+/// [`Frame`]s, keyed by their own levels.
+///
+/// The [`DynamicsProcessor`] keeps the sample rate that it was created with,
+/// so the sample rate passed to [`Processor::prepare`] is ignored.
+///
+/// This is created by [`DynamicsProcessor::into_processor`].
+pub struct BlockDynamics<F, P>
+where
+    F: Frame,
+{
+    core: Core<F, P>,
+}
+
+impl<F, P> BlockDynamics<F, P>
+where
+    F: Frame,
+    F::Sample: FloatSample,
+    P: DynamicsProcessor,
+{
+    pub(crate) fn new(processor: P) -> Self {
+        Self {
+            core: Core::new(processor),
+        }
+    }
+
+    /// Returns a reference to the wrapped [`DynamicsProcessor`].
+    pub fn processor(&self) -> &P {
+        &self.core.processor
+    }
+
+    /// Returns a mutable reference to the wrapped [`DynamicsProcessor`].
+    pub fn processor_mut(&mut self) -> &mut P {
+        &mut self.core.processor
+    }
+}
+
+impl<F, P> Processor for BlockDynamics<F, P>
+where
+    F: Frame,
+    F::Sample: FloatSample,
+    P: DynamicsProcessor,
+{
+    type Frame = F;
+
+    fn prepare(&mut self, _sample_rate: f64, _max_block: usize) {
+        let latency = self.core.processor.latency();
+        self.core.delay.reserve(latency + 1);
+    }
+
+    fn process(&mut self, block: &mut [Self::Frame]) {
+        for frame in block.iter_mut() {
+            self.core.prime(frame);
+            self.core.set_key(frame.iter().map(to_f64));
+            self.core.process(frame);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.core.reset();
+    }
+
+    fn latency(&self) -> usize {
+        self.core.processor.latency()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detector_links() {
+        let mut levels = Vec::new();
+
+        let mut detector = Detector::new(Detection::Peak, Link::None, 48000.0);
+        detector.levels(&[0.5, -0.25], &mut levels);
+        assert_eq!(levels, vec![0.5, 0.25]);
+
+        let mut detector = Detector::new(Detection::Peak, Link::Max, 48000.0);
+        detector.levels(&[0.5, -0.75], &mut levels);
+        assert_eq!(levels, vec![0.75, 0.75]);
+
+        let mut detector = Detector::new(Detection::Peak, Link::Average, 48000.0);
+        detector.levels(&[0.5, -0.25], &mut levels);
+        assert_eq!(levels, vec![0.375, 0.375]);
+    }
+
+    #[test]
+    fn rms_window_in_seconds() {
+        // 4 ms at 1 kHz is a window of 4 frames, so an impulse stays in the
+        // window for 4 frames.
+        let mut detector = Detector::new(Detection::Rms { window: 0.004 }, Link::None, 1000.0);
+        let mut levels = Vec::new();
+
+        let mut output = Vec::new();
+        for x in [1.0, 0.0, 0.0, 0.0, 0.0] {
+            detector.levels(&[x], &mut levels);
+            output.push(levels[0]);
+        }
+
+        assert!(output[..4].iter().all(|l| (l - 0.5).abs() < 1e-12));
+        assert!(output[4].abs() < 1e-12);
+    }
+
+    #[test]
+    #[should_panic]
+    fn sidechain_channel_mismatch() {
+        use crate::signal;
+
+        let key = signal::from_samples_fixed::<_, 2>(vec![0.5f64, 0.5]);
+        let mut signal = signal::from_samples_fixed::<_, 3>(vec![0.5f64; 3])
+            .sidechain(key, Limiter::new(LimiterParams::default(), 1000.0));
+
+        signal.next();
+    }
+
+    #[test]
+    fn block_processor_matches_signal() {
+        use crate::frame::Fixed;
+        use crate::processor::{self, Processor};
+        use crate::signal;
+
+        let input: Vec<Fixed<f64, 2>> = (0..300)
+            .map(|i| {
+                let x = (i as f64 * 0.05).sin() * (i as f64 / 100.0);
+                Fixed::from([x, -0.5 * x])
+            })
+            .collect();
+
+        let limiter = || Limiter::new(LimiterParams::default(), 8000.0);
+
+        let expected: Vec<_> = signal::from_frames(input.clone())
+            .dynamics(limiter())
+            .into_frames()
+            .take(input.len())
+            .collect();
+
+        let mut chain = limiter()
+            .into_processor()
+            .chain(processor::from_fn(|_: &mut Fixed<f64, 2>| {}));
+        chain.prepare(8000.0, 64);
+        assert_eq!(chain.latency(), limiter().latency());
+
+        let mut output = input;
+        for block in output.chunks_mut(64) {
+            chain.process(block);
+        }
+
+        assert_eq!(output, expected);
+    }
+}
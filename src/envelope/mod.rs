@@ -0,0 +1,376 @@
+//! Envelope followers, which track the level of a signal over time.
+
+use crate::frame::Frame;
+use crate::sample::{FromSample, to_f64};
+use crate::signal::{ExactSizeSignal, Signal};
+
+/// How an envelope follower measures and smooths the level of a signal.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Mode {
+    /// Smooths the absolute value of each sample, using the attack time while
+    /// the level is rising and the release time while it is falling.
+    Peak,
+
+    /// Measures the root mean square over a sliding window, and then smooths
+    /// it like [`Mode::Peak`]. The `window` is in the same unit as the attack
+    /// and release times: seconds for [`Signal::envelope`], and [`Frame`]s
+    /// for [`Follower`].
+    Rms { window: f64 },
+
+    /// Tracks the peaks with the release time first, and then smooths the
+    /// result with the attack time. Unlike [`Mode::Peak`], the release is
+    /// not slowed down by a long attack time.
+    Decoupled,
+}
+
+impl Mode {
+    /// Scales the window of [`Mode::Rms`] by `factor`, such as a sample rate
+    /// to convert it from seconds to [`Frame`]s.
+    pub(crate) fn scaled(self, factor: f64) -> Self {
+        match self {
+            Mode::Rms { window } => Mode::Rms {
+                window: window * factor,
+            },
+            mode => mode,
+        }
+    }
+}
+
+/// How the levels of multiple channels are combined, so that they are
+/// processed together and keep their balance.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Link {
+    /// Each channel is processed independently.
+    None,
+
+    /// All channels are processed according to the loudest channel.
+    Max,
+
+    /// All channels are processed according to the average level.
+    Average,
+}
+
+impl Link {
+    /// Combines the levels of all channels in place, according to this
+    /// [`Link`] mode.
+    pub(crate) fn apply(&self, levels: &mut [f64]) {
+        let linked = match self {
+            Link::None => return,
+            Link::Max => levels.iter().copied().fold(0.0, f64::max),
+            Link::Average if !levels.is_empty() => levels.iter().sum::<f64>() / levels.len() as f64,
+            Link::Average => 0.0,
+        };
+
+        levels.fill(linked);
+    }
+}
+
+/// A single-channel envelope follower.
+///
+/// ```
+/// use sampara::envelope::{Follower, Mode};
+///
+/// fn main() {
+///     // An instant attack, and a release time constant of 10 frames.
+///     let mut follower = Follower::new(0.0, 10.0, Mode::Peak);
+///
+///     assert_eq!(follower.process(-0.5), 0.5);
+///
+///     let level = follower.process(0.0);
+///     assert!((level - 0.5 * (-0.1f64).exp()).abs() < 1e-12);
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Follower {
+    mode: Mode,
+    attack: f64,
+    release: f64,
+    // The intermediate peak level, for `Mode::Decoupled`.
+    peak: f64,
+    level: f64,
+    window: Option<RmsWindow>,
+}
+
+impl Follower {
+    /// Creates a new [`Follower`], with attack and release time constants in
+    /// [`Frame`]s. A time constant of 0 follows changes instantly.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the window of [`Mode::Rms`] rounds to 0 [`Frame`]s.
+    pub fn new(attack: f64, release: f64, mode: Mode) -> Self {
+        let window = match mode {
+            Mode::Rms { window } => {
+                let frames = window.round() as usize;
+                assert!(frames > 0, "RMS window must be at least one frame");
+                Some(RmsWindow::new(frames))
+            }
+            Mode::Peak | Mode::Decoupled => None,
+        };
+
+        Self {
+            mode,
+            attack: coefficient(attack),
+            release: coefficient(release),
+            peak: 0.0,
+            level: 0.0,
+            window,
+        }
+    }
+
+    /// Processes one sample, and returns the new level.
+    pub fn process(&mut self, x: f64) -> f64 {
+        let input = match &mut self.window {
+            Some(window) => window.push(x * x).sqrt(),
+            None => x.abs(),
+        };
+
+        self.level = match self.mode {
+            Mode::Peak | Mode::Rms { .. } => {
+                let coeff = if input > self.level {
+                    self.attack
+                } else {
+                    self.release
+                };
+
+                coeff * self.level + (1.0 - coeff) * input
+            }
+            Mode::Decoupled => {
+                self.peak = input.max(self.release * self.peak + (1.0 - self.release) * input);
+                self.attack * self.level + (1.0 - self.attack) * self.peak
+            }
+        };
+
+        self.level
+    }
+
+    /// The current level.
+    pub fn level(&self) -> f64 {
+        self.level
+    }
+
+    pub fn reset(&mut self) {
+        self.peak = 0.0;
+        self.level = 0.0;
+
+        if let Some(window) = &mut self.window {
+            *window = RmsWindow::new(window.values.len());
+        }
+    }
+}
+
+/// A running mean over a sliding window of values.
+#[derive(Clone, Debug)]
+struct RmsWindow {
+    values: Vec<f64>,
+    index: usize,
+    sum: f64,
+}
+
+impl RmsWindow {
+    fn new(len: usize) -> Self {
+        Self {
+            values: vec![0.0; len],
+            index: 0,
+            sum: 0.0,
+        }
+    }
+
+    /// Pushes a new value, and returns the mean of the window.
+    fn push(&mut self, value: f64) -> f64 {
+        self.sum += value - self.values[self.index];
+        self.values[self.index] = value;
+        self.index += 1;
+
+        // Recompute the sum once per cycle, so that rounding errors do not
+        // accumulate.
+        if self.index == self.values.len() {
+            self.index = 0;
+            self.sum = self.values.iter().sum();
+        }
+
+        self.sum.max(0.0) / self.values.len() as f64
+    }
+}
+
+/// Returns the coefficient of a one-pole smoothing filter with a time
+/// constant in frames.
+fn coefficient(time: f64) -> f64 {
+    if time > 0.0 {
+        (-time.recip()).exp()
+    } else {
+        0.0
+    }
+}
+
+/// Converts a linear level to decibels, with a floor of -200 dB.
+pub(crate) fn to_db(level: f64) -> f64 {
+    20.0 * level.max(1e-10).log10()
+}
+
+/// A [`Signal`] that yields the envelope of each channel of an underlying
+/// [`Signal`], as a [`Frame`] of its
+/// [`Sample::Float`](crate::Sample::Float) type.
+///
+/// This is created by [`Signal::envelope`].
+pub struct Envelope<S>
+where
+    S: Signal,
+{
+    signal: S,
+    attack: f64,
+    release: f64,
+    mode: Mode,
+    link: Link,
+    decibels: bool,
+    followers: Vec<Follower>,
+    levels: Vec<f64>,
+}
+
+impl<S> Envelope<S>
+where
+    S: Signal,
+{
+    pub(crate) fn new(signal: S, attack: f64, release: f64, mode: Mode) -> Self {
+        // Check the parameters up front, rather than on the first frame.
+        let _ = Follower::new(attack, release, mode);
+
+        Self {
+            signal,
+            attack,
+            release,
+            mode,
+            link: Link::None,
+            decibels: false,
+            followers: Vec::new(),
+            levels: Vec::new(),
+        }
+    }
+
+    /// Combines the envelopes of all channels, according to a [`Link`] mode.
+    pub fn link(mut self, link: Link) -> Self {
+        self.link = link;
+        self
+    }
+
+    /// Yields the envelopes in decibels, with a floor of -200 dB, rather than
+    /// as linear levels.
+    pub fn decibels(mut self) -> Self {
+        self.decibels = true;
+        self
+    }
+}
+
+impl<S> Signal for Envelope<S>
+where
+    S: Signal,
+{
+    type Frame = <S::Frame as Frame>::Float;
+
+    fn next(&mut self) -> Option<Self::Frame> {
+        let frame = self.signal.next()?;
+
+        if self.followers.len() != frame.len() {
+            let follower = Follower::new(self.attack, self.release, self.mode);
+            self.followers = vec![follower; frame.len()];
+        }
+
+        self.levels.clear();
+        self.levels.extend(
+            frame
+                .iter()
+                .zip(self.followers.iter_mut())
+                .map(|(x, follower)| follower.process(to_f64(x))),
+        );
+        self.link.apply(&mut self.levels);
+
+        let mut output = frame.into_float_frame();
+        for (sample, &level) in output.iter_mut().zip(&self.levels) {
+            let level = if self.decibels { to_db(level) } else { level };
+            *sample = FromSample::from_sample(level);
+        }
+
+        Some(output)
+    }
+
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        self.signal.len_hint()
+    }
+}
+
+impl<S> ExactSizeSignal for Envelope<S> where S: ExactSizeSignal {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn attack_and_release() {
+        let mut follower = Follower::new(10.0, 100.0, Mode::Peak);
+
+        for _ in 0..10 {
+            follower.process(1.0);
+        }
+        assert!((follower.level() - (1.0 - (-1.0f64).exp())).abs() < 1e-12);
+
+        let level = follower.level();
+        for _ in 0..100 {
+            follower.process(0.0);
+        }
+        assert!((follower.level() - level * (-1.0f64).exp()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn decoupled_ripple() {
+        // On a signal with a fast release and a slow attack, the branching
+        // follower falls between peaks and slowly climbs back, while the
+        // decoupled follower smooths the peak envelope with the attack time.
+        let mut peak = Follower::new(5.0, 1.0, Mode::Peak);
+        let mut decoupled = Follower::new(5.0, 1.0, Mode::Decoupled);
+
+        let mut peak_levels = Vec::new();
+        let mut decoupled_levels = Vec::new();
+        for i in 0..200 {
+            let x = if i % 2 == 0 { 1.0 } else { 0.0 };
+            peak_levels.push(peak.process(x));
+            decoupled_levels.push(decoupled.process(x));
+        }
+
+        let ripple = |levels: &[f64]| {
+            let (min, max) = levels[100..]
+                .iter()
+                .fold((f64::MAX, f64::MIN), |(lo, hi), &l| (lo.min(l), hi.max(l)));
+            max - min
+        };
+
+        assert!(ripple(&decoupled_levels) < ripple(&peak_levels) / 2.0);
+        assert!(decoupled_levels[199] > peak_levels[199]);
+    }
+
+    #[test]
+    fn rms_window() {
+        let mut follower = Follower::new(0.0, 0.0, Mode::Rms { window: 4.0 });
+
+        // A square wave has an RMS level equal to its amplitude, once the
+        // window is full.
+        for i in 0..20 {
+            follower.process(if i % 2 == 0 { 0.5 } else { -0.5 });
+        }
+
+        assert!((follower.level() - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn rms_window_in_seconds() {
+        // 4 ms at 1 kHz is a window of 4 frames.
+        let signal = crate::signal::from_samples_fixed::<_, 1>(vec![1.0f64, 0.0, 0.0, 0.0, 0.0]);
+        let mut signal = signal.envelope(0.0, 0.0, Mode::Rms { window: 0.004 }, 1000.0);
+
+        let mut levels = Vec::new();
+        while let Some(frame) = signal.next() {
+            levels.push(frame.into_array()[0]);
+        }
+
+        assert_eq!(levels, vec![0.5, 0.5, 0.5, 0.5, 0.0]);
+    }
+}
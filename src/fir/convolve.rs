@@ -0,0 +1,633 @@
+use crate::fir::Fir;
+use crate::frame::Frame;
+use crate::sample::{FloatSample, FromSample, IntoSample, Sample};
+use crate::signal::{ExactSizeSignal, FloatOf, Signal, len_hint_with_tail};
+use crate::spectrum::{Complex, RealFft};
+
+/// The longest impulse response that is convolved with [`Method::Direct`] by
+/// default.
+const DIRECT_MAX_LEN: usize = 64;
+
+/// The block size used for [`Method::Partitioned`] by default.
+const DEFAULT_BLOCK_SIZE: usize = 256;
+
+/// The algorithm used to convolve a [`Signal`] with an [`ImpulseResponse`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Method {
+    /// Direct-form convolution with a [`Fir`] filter, which has no latency but
+    /// a cost per sample proportional to the length of the impulse response.
+    Direct,
+
+    /// Uniformly partitioned FFT convolution, where the impulse response is
+    /// split into partitions of `block_size` samples that are convolved in
+    /// the frequency domain. This has a latency of `block_size` frames, and a
+    /// cost per sample that grows much more slowly with the length of the
+    /// impulse response. `block_size` must be a power of two.
+    Partitioned { block_size: usize },
+}
+
+/// How the impulse responses of an [`ImpulseResponse`] map input channels to
+/// output channels.
+#[derive(Clone, Debug)]
+enum Routing<T> {
+    Shared(Vec<T>),
+    PerChannel(Vec<Vec<T>>),
+    TrueStereo([[Vec<T>; 2]; 2]),
+}
+
+/// One or more impulse responses to convolve a [`Signal`] with, using
+/// [`Signal::convolve`].
+///
+/// The number of channels of the [`Signal`] must match the layout of the
+/// impulse responses; this is checked on its first [`Frame`].
+#[derive(Clone, Debug)]
+pub struct ImpulseResponse<T>
+where
+    T: FloatSample,
+{
+    routing: Routing<T>,
+    method: Option<Method>,
+}
+
+impl<T> ImpulseResponse<T>
+where
+    T: FloatSample,
+{
+    /// Creates a new [`ImpulseResponse`] that convolves every channel with the
+    /// same impulse response.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ir` is empty.
+    pub fn new(ir: Vec<T>) -> Self {
+        assert!(!ir.is_empty(), "impulse response must not be empty");
+
+        Self {
+            routing: Routing::Shared(ir),
+            method: None,
+        }
+    }
+
+    /// Creates a new [`ImpulseResponse`] that convolves each channel with its
+    /// own impulse response.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no impulse responses, or if any impulse response is
+    /// empty.
+    pub fn per_channel(irs: Vec<Vec<T>>) -> Self {
+        assert!(
+            !irs.is_empty(),
+            "there must be at least one impulse response"
+        );
+        assert!(
+            irs.iter().all(|ir| !ir.is_empty()),
+            "impulse responses must not be empty"
+        );
+
+        Self {
+            routing: Routing::PerChannel(irs),
+            method: None,
+        }
+    }
+
+    /// Creates a new true stereo [`ImpulseResponse`], where each of the two
+    /// input channels is convolved with a separate impulse response for each
+    /// of the two output channels. For example, `left_to_right` is the
+    /// response of the right output to the left input.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any impulse response is empty.
+    pub fn true_stereo(
+        left_to_left: Vec<T>,
+        left_to_right: Vec<T>,
+        right_to_left: Vec<T>,
+        right_to_right: Vec<T>,
+    ) -> Self {
+        let matrix = [
+            [left_to_left, left_to_right],
+            [right_to_left, right_to_right],
+        ];
+
+        assert!(
+            matrix.iter().flatten().all(|ir| !ir.is_empty()),
+            "impulse responses must not be empty"
+        );
+
+        Self {
+            routing: Routing::TrueStereo(matrix),
+            method: None,
+        }
+    }
+
+    /// Sets the convolution [`Method`] to use.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the block size of [`Method::Partitioned`] is not a power of
+    /// two.
+    pub fn with_method(mut self, method: Method) -> Self {
+        if let Method::Partitioned { block_size } = method {
+            assert!(
+                block_size.is_power_of_two(),
+                "block size must be a power of two"
+            );
+        }
+
+        self.method = Some(method);
+        self
+    }
+
+    /// Returns the convolution [`Method`] that will be used. Unless set with
+    /// [`Self::with_method`], this is [`Method::Direct`] for impulse responses
+    /// of up to 64 samples, and [`Method::Partitioned`] with a block size of
+    /// 256 otherwise.
+    pub fn method(&self) -> Method {
+        self.method.unwrap_or(if self.len() <= DIRECT_MAX_LEN {
+            Method::Direct
+        } else {
+            Method::Partitioned {
+                block_size: DEFAULT_BLOCK_SIZE,
+            }
+        })
+    }
+
+    /// The length of the longest impulse response, in samples.
+    pub fn len(&self) -> usize {
+        self.irs().map(<[T]>::len).max().unwrap_or(0)
+    }
+
+    /// Returns `true` if there are no impulse responses.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn irs(&self) -> impl Iterator<Item = &[T]> {
+        let irs: Vec<&[T]> = match &self.routing {
+            Routing::Shared(ir) => vec![ir],
+            Routing::PerChannel(irs) => irs.iter().map(Vec::as_slice).collect(),
+            Routing::TrueStereo(matrix) => matrix.iter().flatten().map(Vec::as_slice).collect(),
+        };
+
+        irs.into_iter()
+    }
+
+    /// Returns the `(input, output, ir)` convolution paths for a given number
+    /// of channels.
+    fn paths(&self, channels: usize) -> Vec<(usize, usize, &[T])> {
+        match &self.routing {
+            Routing::Shared(ir) => (0..channels).map(|c| (c, c, ir.as_slice())).collect(),
+            Routing::PerChannel(irs) => {
+                assert_eq!(
+                    irs.len(),
+                    channels,
+                    "number of impulse responses must match number of channels"
+                );
+
+                irs.iter()
+                    .enumerate()
+                    .map(|(c, ir)| (c, c, ir.as_slice()))
+                    .collect()
+            }
+            Routing::TrueStereo(matrix) => {
+                assert_eq!(channels, 2, "true stereo convolution requires 2 channels");
+
+                (0..2)
+                    .flat_map(|i| (0..2).map(move |o| (i, o, matrix[i][o].as_slice())))
+                    .collect()
+            }
+        }
+    }
+}
+
+impl<T> From<Vec<T>> for ImpulseResponse<T>
+where
+    T: FloatSample,
+{
+    fn from(ir: Vec<T>) -> Self {
+        Self::new(ir)
+    }
+}
+
+/// A multichannel convolution engine, with one convolution path for each
+/// pairing of input and output channels that has an impulse response.
+enum Engine<T>
+where
+    T: FloatSample,
+{
+    Direct(Vec<(usize, usize, Fir<T>)>),
+    Partitioned(Box<Partitioned<T>>),
+}
+
+impl<T> Engine<T>
+where
+    T: FloatSample,
+{
+    fn new(ir: &ImpulseResponse<T>, channels: usize) -> Self {
+        let paths = ir.paths(channels);
+
+        match ir.method() {
+            Method::Direct => Engine::Direct(
+                paths
+                    .into_iter()
+                    .map(|(i, o, ir)| (i, o, Fir::new(ir.to_vec())))
+                    .collect(),
+            ),
+            Method::Partitioned { block_size } => {
+                Engine::Partitioned(Box::new(Partitioned::new(&paths, channels, block_size)))
+            }
+        }
+    }
+
+    fn process(&mut self, input: &[T], output: &mut [T]) {
+        match self {
+            Engine::Direct(paths) => {
+                output.fill(T::EQUILIBRIUM);
+
+                for (i, o, fir) in paths.iter_mut() {
+                    output[*o] = output[*o] + fir.process(input[*i]);
+                }
+            }
+            Engine::Partitioned(engine) => engine.process(input, output),
+        }
+    }
+}
+
+/// One convolution path of a [`Partitioned`] engine.
+struct Path<T>
+where
+    T: FloatSample,
+{
+    input: usize,
+    output: usize,
+    // The spectrum of each partition of the impulse response.
+    partitions: Vec<Vec<Complex<T>>>,
+}
+
+/// A uniformly partitioned overlap-save convolution engine.
+struct Partitioned<T>
+where
+    T: FloatSample,
+{
+    block_size: usize,
+    fft: RealFft<T>,
+    paths: Vec<Path<T>>,
+    // For each input channel, the spectra of the most recent input blocks, as
+    // a ring buffer where `newest` is the index of the most recent one.
+    spectra: Vec<Vec<Vec<Complex<T>>>>,
+    newest: usize,
+    // For each input channel, the previous and current block of input.
+    inputs: Vec<Vec<T>>,
+    // For each output channel, the current block of output.
+    outputs: Vec<Vec<T>>,
+    // The position within the current blocks.
+    position: usize,
+    accumulators: Vec<Vec<Complex<T>>>,
+    scratch: Vec<T>,
+}
+
+impl<T> Partitioned<T>
+where
+    T: FloatSample,
+{
+    fn new(paths: &[(usize, usize, &[T])], channels: usize, block_size: usize) -> Self {
+        let size = 2 * block_size;
+        let zero = T::EQUILIBRIUM;
+
+        let mut fft = RealFft::new(size);
+        let bins = fft.bins();
+        let mut scratch = vec![zero; size];
+
+        let paths: Vec<Path<T>> = paths
+            .iter()
+            .map(|&(input, output, ir)| {
+                let partitions = ir
+                    .chunks(block_size)
+                    .map(|chunk| {
+                        scratch.fill(zero);
+                        scratch[..chunk.len()].copy_from_slice(chunk);
+
+                        let mut spectrum = vec![Complex::ZERO; bins];
+                        fft.forward(&scratch, &mut spectrum);
+                        spectrum
+                    })
+                    .collect();
+
+                Path {
+                    input,
+                    output,
+                    partitions,
+                }
+            })
+            .collect();
+
+        let count = paths.iter().map(|p| p.partitions.len()).max().unwrap_or(1);
+
+        Self {
+            block_size,
+            fft,
+            paths,
+            spectra: vec![vec![vec![Complex::ZERO; bins]; count]; channels],
+            newest: 0,
+            inputs: vec![vec![zero; size]; channels],
+            outputs: vec![vec![zero; block_size]; channels],
+            position: 0,
+            accumulators: vec![vec![Complex::ZERO; bins]; channels],
+            scratch,
+        }
+    }
+
+    fn process(&mut self, input: &[T], output: &mut [T]) {
+        let block_size = self.block_size;
+
+        for (o, buffer) in output.iter_mut().zip(&self.outputs) {
+            *o = buffer[self.position];
+        }
+
+        for (buffer, &x) in self.inputs.iter_mut().zip(input) {
+            buffer[block_size + self.position] = x;
+        }
+
+        self.position += 1;
+
+        if self.position == block_size {
+            self.position = 0;
+            self.process_block();
+        }
+    }
+
+    fn process_block(&mut self) {
+        let block_size = self.block_size;
+        let count = self.spectra.first().map_or(1, Vec::len);
+
+        self.newest = (self.newest + 1) % count;
+
+        for (buffer, spectra) in self.inputs.iter_mut().zip(self.spectra.iter_mut()) {
+            self.fft.forward(buffer, &mut spectra[self.newest]);
+            buffer.copy_within(block_size.., 0);
+        }
+
+        for accumulator in self.accumulators.iter_mut() {
+            accumulator.fill(Complex::ZERO);
+        }
+
+        for path in self.paths.iter() {
+            let spectra = &self.spectra[path.input];
+            let accumulator = &mut self.accumulators[path.output];
+
+            for (age, partition) in path.partitions.iter().enumerate() {
+                let spectrum = &spectra[(self.newest + count - age) % count];
+
+                for ((a, &x), &h) in accumulator.iter_mut().zip(spectrum).zip(partition) {
+                    *a += x * h;
+                }
+            }
+        }
+
+        for (accumulator, buffer) in self.accumulators.iter().zip(self.outputs.iter_mut()) {
+            self.fft.inverse(accumulator, &mut self.scratch);
+
+            // With overlap-save, only the second half is free of wraparound.
+            buffer.copy_from_slice(&self.scratch[block_size..]);
+        }
+    }
+}
+
+/// A [`Signal`] that convolves each channel of an underlying [`Signal`] with
+/// an [`ImpulseResponse`].
+///
+/// This is created by [`Signal::convolve`].
+pub struct Convolve<S>
+where
+    S: Signal,
+{
+    signal: S,
+    ir: ImpulseResponse<FloatOf<S>>,
+    engine: Option<Engine<FloatOf<S>>>,
+    // A frame of equilibrium, used to flush the tail of the convolution.
+    silence: Option<S::Frame>,
+    input: Vec<FloatOf<S>>,
+    output: Vec<FloatOf<S>>,
+    // The number of frames left to output, once the input has run out.
+    tail: Option<usize>,
+}
+
+impl<S> Convolve<S>
+where
+    S: Signal,
+{
+    pub(crate) fn new(signal: S, ir: ImpulseResponse<FloatOf<S>>) -> Self {
+        Self {
+            signal,
+            ir,
+            engine: None,
+            silence: None,
+            input: Vec::new(),
+            output: Vec::new(),
+            tail: None,
+        }
+    }
+
+    /// Returns the [`ImpulseResponse`] that is being convolved with.
+    pub fn impulse_response(&self) -> &ImpulseResponse<FloatOf<S>> {
+        &self.ir
+    }
+
+    /// The number of [`Frame`]s that the output is delayed by, relative to
+    /// the input.
+    pub fn latency(&self) -> usize {
+        match self.ir.method() {
+            Method::Direct => 0,
+            Method::Partitioned { block_size } => block_size,
+        }
+    }
+}
+
+impl<S> Signal for Convolve<S>
+where
+    S: Signal,
+    <S::Frame as Frame>::Sample: FromSample<FloatOf<S>>,
+{
+    type Frame = S::Frame;
+
+    fn next(&mut self) -> Option<Self::Frame> {
+        let mut frame = match self.tail {
+            Some(0) => return None,
+            Some(ref mut tail) => {
+                *tail -= 1;
+                self.silence.clone()?
+            }
+            None => match self.signal.next() {
+                Some(frame) => {
+                    if self.engine.is_none() {
+                        let channels = frame.len();
+                        let zero = FloatOf::<S>::EQUILIBRIUM;
+
+                        self.engine = Some(Engine::new(&self.ir, channels));
+                        self.input = vec![zero; channels];
+                        self.output = vec![zero; channels];
+
+                        let mut silence = frame.clone();
+                        silence
+                            .iter_mut()
+                            .for_each(|s| *s = <S::Frame as Frame>::Sample::EQUILIBRIUM);
+                        self.silence = Some(silence);
+                    }
+
+                    frame
+                }
+                None => {
+                    // Flush the latency, and all but the first sample of the
+                    // impulse response.
+                    let tail = self.latency() + self.ir.len() - 1;
+                    self.tail = Some(tail);
+                    return self.next();
+                }
+            },
+        };
+
+        let engine = self.engine.as_mut()?;
+
+        for (x, sample) in self.input.iter_mut().zip(frame.iter()) {
+            *x = sample.into_sample();
+        }
+
+        engine.process(&self.input, &mut self.output);
+
+        for (sample, &y) in frame.iter_mut().zip(&self.output) {
+            *sample = y.into_sample();
+        }
+
+        Some(frame)
+    }
+
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        let (input, tail) = match self.tail {
+            Some(tail) => ((0, Some(0)), tail),
+            None => (self.signal.len_hint(), self.latency() + self.ir.len() - 1),
+        };
+
+        len_hint_with_tail(input, tail, self.silence.is_some())
+    }
+}
+
+impl<S> ExactSizeSignal for Convolve<S>
+where
+    S: ExactSizeSignal,
+    <S::Frame as Frame>::Sample: FromSample<FloatOf<S>>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::signal;
+
+    fn naive(x: &[f64], h: &[f64]) -> Vec<f64> {
+        let mut y = vec![0.0; x.len() + h.len() - 1];
+        for (i, &a) in x.iter().enumerate() {
+            for (j, &b) in h.iter().enumerate() {
+                y[i + j] += a * b;
+            }
+        }
+        y
+    }
+
+    fn run<S>(signal: S) -> Vec<[f64; 2]>
+    where
+        S: Signal<Frame = crate::Fixed<f64, 2>>,
+    {
+        let mut signal = signal;
+        let mut output = Vec::new();
+        while let Some(frame) = signal.next() {
+            output.push(frame.into_array());
+        }
+        output
+    }
+
+    #[test]
+    fn methods_match_naive() {
+        let x: Vec<f64> = (0..300)
+            .map(|i| ((i * 7919) % 113) as f64 / 56.0 - 1.0)
+            .collect();
+        let h: Vec<f64> = (0..150)
+            .map(|i| ((i * 104729) % 97) as f64 / 97.0 - 0.5)
+            .collect();
+        let expected = naive(&x, &h);
+
+        for method in [
+            Method::Direct,
+            Method::Partitioned { block_size: 1 },
+            Method::Partitioned { block_size: 16 },
+            Method::Partitioned { block_size: 256 },
+        ] {
+            let signal = signal::from_samples_fixed::<_, 2>(x.iter().flat_map(|&s| [s, -s]));
+            let ir = ImpulseResponse::new(h.clone()).with_method(method);
+            let convolve = signal.convolve(ir);
+            let latency = convolve.latency();
+
+            let output = run(convolve);
+            assert_eq!(output.len(), expected.len() + latency, "{method:?}");
+
+            for (e, y) in expected.iter().zip(&output[latency..]) {
+                assert!((e - y[0]).abs() < 1e-9, "{method:?}");
+                assert!((e + y[1]).abs() < 1e-9, "{method:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn true_stereo_routing() {
+        let frames = [[1.0, 0.0], [0.0, 1.0], [0.0, 0.0]];
+
+        for method in [Method::Direct, Method::Partitioned { block_size: 2 }] {
+            let signal = signal::from_frames(frames.iter().map(|&f| crate::Fixed::from(f)));
+            let ir = ImpulseResponse::true_stereo(vec![1.0], vec![2.0], vec![3.0], vec![4.0])
+                .with_method(method);
+            let convolve = signal.convolve(ir);
+            let latency = convolve.latency();
+
+            let output = run(convolve);
+            let expected = [[1.0, 2.0], [3.0, 4.0], [0.0, 0.0]];
+            assert_eq!(output.len(), expected.len() + latency);
+
+            for (e, y) in expected
+                .iter()
+                .flatten()
+                .zip(output[latency..].iter().flatten())
+            {
+                assert!((e - y).abs() < 1e-12, "{method:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn per_channel_dynamic() {
+        let ir = ImpulseResponse::per_channel(vec![vec![1.0f32, 1.0], vec![0.0, 0.0, 1.0]]);
+        let mut signal = signal::from_samples_dynamic(vec![1.0f32, 2.0], 2).convolve(ir);
+
+        let mut output = Vec::new();
+        while let Some(frame) = signal.next() {
+            output.push(frame.iter().copied().collect::<Vec<_>>());
+        }
+
+        assert_eq!(output, vec![vec![1.0, 0.0], vec![1.0, 0.0], vec![0.0, 2.0]]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn channel_mismatch() {
+        let ir = ImpulseResponse::true_stereo(vec![1.0], vec![0.0], vec![0.0], vec![1.0]);
+        signal::from_samples_fixed::<_, 1>(vec![1.0f32])
+            .convolve(ir)
+            .next();
+    }
+
+    #[test]
+    #[should_panic]
+    fn no_impulse_responses() {
+        ImpulseResponse::<f32>::per_channel(vec![]);
+    }
+}
@@ -0,0 +1,738 @@
+use core::f64::consts::PI;
+
+use crate::sample::FloatSample;
+use crate::spectrum::{Complex, RealFft, Window};
+
+/// The maximum number of exchange iterations of [`remez`].
+const REMEZ_MAX_ITERATIONS: usize = 40;
+
+/// The number of grid points per coefficient used by [`remez`].
+const REMEZ_GRID_DENSITY: usize = 16;
+
+/// An ideal frequency response for [`windowed_sinc`] and [`kaiser`] designs.
+///
+/// All frequencies are normalized to the sample rate, so they must be in the
+/// interval (0.0, 0.5), where 0.5 is the Nyquist frequency. The `low`
+/// frequency of a band must be below its `high` frequency.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Response {
+    /// Passes frequencies below `cutoff`.
+    LowPass { cutoff: f64 },
+
+    /// Passes frequencies above `cutoff`.
+    HighPass { cutoff: f64 },
+
+    /// Passes frequencies between `low` and `high`.
+    BandPass { low: f64, high: f64 },
+
+    /// Passes frequencies outside of `low` and `high`.
+    BandStop { low: f64, high: f64 },
+}
+
+impl Response {
+    /// Returns the ideal (infinite, non-causal) impulse response at a given
+    /// offset from its center.
+    fn ideal(&self, t: f64) -> f64 {
+        let low_pass = |cutoff: f64| 2.0 * cutoff * sinc(2.0 * cutoff * t);
+        let impulse = if t == 0.0 { 1.0 } else { 0.0 };
+
+        match *self {
+            Response::LowPass { cutoff } => low_pass(cutoff),
+            Response::HighPass { cutoff } => impulse - low_pass(cutoff),
+            Response::BandPass { low, high } => low_pass(high) - low_pass(low),
+            Response::BandStop { low, high } => impulse - low_pass(high) + low_pass(low),
+        }
+    }
+
+    /// Returns a frequency in the middle of a passband, where the gain of the
+    /// designed filter is normalized to 1.
+    fn passband_center(&self) -> f64 {
+        match *self {
+            Response::LowPass { .. } | Response::BandStop { .. } => 0.0,
+            Response::HighPass { .. } => 0.5,
+            Response::BandPass { low, high } => (low + high) / 2.0,
+        }
+    }
+
+    /// Returns `true` if this response passes the Nyquist frequency, which
+    /// requires an odd number of coefficients.
+    fn passes_nyquist(&self) -> bool {
+        matches!(self, Response::HighPass { .. } | Response::BandStop { .. })
+    }
+
+    fn validate(&self) {
+        match *self {
+            Response::LowPass { cutoff } | Response::HighPass { cutoff } => assert!(
+                0.0 < cutoff && cutoff < 0.5,
+                "cutoff frequency must be in (0.0, 0.5)"
+            ),
+            Response::BandPass { low, high } | Response::BandStop { low, high } => assert!(
+                0.0 < low && low < high && high < 0.5,
+                "band frequencies must have 0.0 < low < high < 0.5"
+            ),
+        }
+    }
+}
+
+/// A tolerance specification for [`kaiser`] designs.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct KaiserSpec {
+    /// The maximum peak-to-peak ripple in the passband, in decibels.
+    pub passband_ripple: f64,
+
+    /// The minimum attenuation in the stopband, in positive decibels.
+    pub stopband_attenuation: f64,
+
+    /// The width of each transition band, normalized to the sample rate.
+    pub transition_width: f64,
+}
+
+impl KaiserSpec {
+    /// Returns the attenuation, in positive decibels, that the Kaiser window
+    /// must achieve to meet both the passband and the stopband tolerances.
+    /// Windowed-sinc designs have equal ripple in both bands, so this is set
+    /// by the stricter of the two.
+    pub fn attenuation(&self) -> f64 {
+        let passband = 10.0f64.powf(self.passband_ripple / 20.0);
+        let passband_delta = (passband - 1.0) / (passband + 1.0);
+        let stopband_delta = 10.0f64.powf(-self.stopband_attenuation / 20.0);
+
+        -20.0 * passband_delta.min(stopband_delta).log10()
+    }
+
+    /// Returns the odd number of coefficients needed to meet this
+    /// specification, using Kaiser's empirical formula.
+    pub fn taps(&self) -> usize {
+        let estimate = (self.attenuation() - 7.95) / (14.36 * self.transition_width);
+        let len = estimate.ceil().max(1.0) as usize + 1;
+
+        len | 1
+    }
+
+    /// Returns the [`Window::Kaiser`] window that meets this specification.
+    pub fn window(&self) -> Window {
+        Window::Kaiser {
+            beta: Window::kaiser_beta(self.attenuation()),
+        }
+    }
+}
+
+/// A band of a [`remez`] design.
+///
+/// Frequencies are normalized to the sample rate, so they must be in the
+/// interval [0.0, 0.5].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Band {
+    /// The lower edge of the band.
+    pub low: f64,
+
+    /// The upper edge of the band.
+    pub high: f64,
+
+    /// The desired gain in the band.
+    pub gain: f64,
+
+    /// The relative weight of errors in the band. A larger weight results in
+    /// less ripple in this band, at the expense of the others.
+    pub weight: f64,
+}
+
+/// Designs a linear-phase FIR kernel of a given length by windowing the ideal
+/// impulse response of a [`Response`]. The gain is normalized to 1 in the
+/// middle of the passband.
+///
+/// ```
+/// use sampara::fir::{self, Fir, Response};
+/// use sampara::spectrum::Window;
+///
+/// fn main() {
+///     let kernel: Vec<f64> = fir::windowed_sinc(Response::LowPass { cutoff: 0.1 }, 31, Window::Hamming);
+///     assert_eq!(kernel.len(), 31);
+///
+///     // A constant input is passed at unity gain, once the filter is full.
+///     let mut fir = Fir::new(kernel);
+///     let output = (0..40).map(|_| fir.process(1.0)).last().unwrap();
+///     assert!((output - 1.0).abs() < 1e-12);
+/// }
+/// ```
+///
+/// # Panics
+///
+/// Panics if `len` is 0, if the frequencies of `response` are not in the
+/// interval (0.0, 0.5), if a band does not have `low < high`, or if `len` is
+/// even and `response` passes the Nyquist frequency.
+pub fn windowed_sinc<T: FloatSample>(response: Response, len: usize, window: Window) -> Vec<T> {
+    assert!(len > 0, "kernel length must not be 0");
+    response.validate();
+    assert!(
+        len % 2 == 1 || !response.passes_nyquist(),
+        "high-pass and band-stop kernels must have an odd length"
+    );
+
+    let center = (len - 1) as f64 / 2.0;
+
+    let mut kernel: Vec<f64> = window
+        .symmetric::<f64>(len)
+        .into_iter()
+        .enumerate()
+        .map(|(n, w)| response.ideal(n as f64 - center) * w)
+        .collect();
+
+    let gain = amplitude(&kernel, response.passband_center());
+    kernel.iter_mut().for_each(|h| *h /= gain);
+
+    kernel.into_iter().map(T::from_sample).collect()
+}
+
+/// Designs a linear-phase FIR kernel that meets a [`KaiserSpec`], by
+/// windowing the ideal impulse response of a [`Response`] with a Kaiser
+/// window. The length and window shape are both derived from the
+/// specification, and the cutoff frequencies are the centers of the
+/// transition bands.
+///
+/// ```
+/// use sampara::fir::{self, KaiserSpec, Response};
+///
+/// fn main() {
+///     let spec = KaiserSpec {
+///         passband_ripple: 0.1,
+///         stopband_attenuation: 60.0,
+///         transition_width: 0.05,
+///     };
+///
+///     let kernel: Vec<f32> = fir::kaiser(Response::HighPass { cutoff: 0.25 }, spec);
+///     assert_eq!(kernel.len(), spec.taps());
+///     assert_eq!(kernel.len() % 2, 1);
+/// }
+/// ```
+///
+/// # Panics
+///
+/// Panics if the frequencies of `response` are not in the interval
+/// (0.0, 0.5), or if a band does not have `low < high`.
+pub fn kaiser<T: FloatSample>(response: Response, spec: KaiserSpec) -> Vec<T> {
+    windowed_sinc(response, spec.taps(), spec.window())
+}
+
+/// Designs a linear-phase FIR kernel of a given length that approximates an
+/// arbitrary magnitude response, by sampling it densely and windowing the
+/// resulting impulse response.
+///
+/// The magnitude response is a function of frequency, normalized to the
+/// sample rate, in the interval [0.0, 0.5]. Kernels of even length always
+/// have a gain of zero at the Nyquist frequency.
+///
+/// ```
+/// use sampara::fir;
+/// use sampara::spectrum::Window;
+///
+/// fn main() {
+///     // A gentle tilt, from unity gain at DC down to half gain at Nyquist.
+///     let kernel: Vec<f64> = fir::frequency_sampling(63, |f| 1.0 - f, Window::Hann);
+///
+///     let dc: f64 = kernel.iter().sum();
+///     assert!((dc - 1.0).abs() < 1e-2);
+/// }
+/// ```
+///
+/// # Panics
+///
+/// Panics if `len` is 0.
+pub fn frequency_sampling<T, F>(len: usize, magnitude: F, window: Window) -> Vec<T>
+where
+    T: FloatSample,
+    F: Fn(f64) -> f64,
+{
+    assert!(len > 0, "kernel length must not be 0");
+
+    let size = (2 * len).next_power_of_two().max(512);
+    let delay = (len - 1) as f64 / 2.0;
+
+    // Delay the response by half of the kernel length, so that the impulse
+    // response is centered in the first `len` points.
+    let spectrum: Vec<Complex<f64>> = (0..=size / 2)
+        .map(|k| {
+            let f = k as f64 / size as f64;
+            Complex::from_polar(magnitude(f), -2.0 * PI * f * delay)
+        })
+        .collect();
+
+    let mut impulse = vec![0.0; size];
+    RealFft::<f64>::new(size).inverse(&spectrum, &mut impulse);
+
+    impulse
+        .into_iter()
+        .zip(window.symmetric::<f64>(len))
+        .map(|(h, w)| T::from_sample(h * w))
+        .collect()
+}
+
+/// Designs an optimal equiripple linear-phase FIR kernel of a given length,
+/// using the Parks-McClellan algorithm with Remez exchange.
+///
+/// The resulting kernel minimizes the maximum weighted error against the
+/// desired gain across all of the [`Band`]s. Frequencies between the bands
+/// are transition bands, which are left unconstrained.
+///
+/// ```
+/// use sampara::fir::{self, Band};
+///
+/// fn main() {
+///     let bands = [
+///         Band { low: 0.0, high: 0.1, gain: 1.0, weight: 1.0 },
+///         Band { low: 0.15, high: 0.5, gain: 0.0, weight: 1.0 },
+///     ];
+///
+///     let kernel: Vec<f64> = fir::remez(47, &bands);
+///     assert_eq!(kernel.len(), 47);
+///
+///     // The kernel is symmetric, for linear phase.
+///     for (a, b) in kernel.iter().zip(kernel.iter().rev()) {
+///         assert!((a - b).abs() < 1e-12);
+///     }
+/// }
+/// ```
+///
+/// # Panics
+///
+/// Panics if `len` is less than 3, if `bands` is empty, or if the bands are
+/// not ordered, non-overlapping and in the interval [0.0, 0.5].
+pub fn remez<T: FloatSample>(len: usize, bands: &[Band]) -> Vec<T> {
+    assert!(len >= 3, "kernel length must be at least 3");
+    assert!(!bands.is_empty(), "at least one band is required");
+    assert!(
+        bands.iter().all(|b| b.low <= b.high && b.weight > 0.0)
+            && bands.windows(2).all(|w| w[0].high < w[1].low)
+            && bands[0].low >= 0.0
+            && bands[bands.len() - 1].high <= 0.5,
+        "bands must be ordered, non-overlapping and in [0.0, 0.5]"
+    );
+
+    // Kernels of even length (type II) have a zero at the Nyquist frequency,
+    // so their amplitude response is factored as `cos(pi * f) * P(f)`, and
+    // `P` is approximated instead.
+    let even = len.is_multiple_of(2);
+    let r = if even { len / 2 } else { len.div_ceil(2) };
+
+    let (grid, desired, weight) = remez_grid(bands, r, even);
+    assert!(
+        grid.len() > r,
+        "bands are too narrow for this kernel length"
+    );
+
+    let mut extremals: Vec<usize> = (0..=r).map(|i| i * (grid.len() - 1) / r).collect();
+    let mut interpolation = Interpolation::default();
+
+    for _ in 0..REMEZ_MAX_ITERATIONS {
+        interpolation = Interpolation::new(&grid, &desired, &weight, &extremals);
+
+        let error: Vec<f64> = grid
+            .iter()
+            .zip(&desired)
+            .zip(&weight)
+            .map(|((&f, &d), &w)| w * (d - interpolation.eval(f)))
+            .collect();
+
+        match find_extremals(&error, r + 1) {
+            Some(found) => extremals = found,
+            None => break,
+        }
+
+        let magnitudes = extremals.iter().map(|&i| error[i].abs());
+        let max = magnitudes.clone().fold(0.0, f64::max);
+        let min = magnitudes.fold(f64::MAX, f64::min);
+
+        if max == 0.0 || (max - min) / max < 1e-6 {
+            break;
+        }
+    }
+
+    // Sample the amplitude response at the DFT frequencies, and take the
+    // inverse DFT of the resulting real, linear-phase spectrum.
+    let amplitudes: Vec<f64> = (0..len.div_ceil(2))
+        .map(|k| {
+            let f = k as f64 / len as f64;
+            let a = interpolation.eval(f);
+            if even { a * (PI * f).cos() } else { a }
+        })
+        .collect();
+
+    let center = (len - 1) as f64 / 2.0;
+
+    (0..len)
+        .map(|n| {
+            let t = n as f64 - center;
+            let sum: f64 = amplitudes
+                .iter()
+                .enumerate()
+                .skip(1)
+                .map(|(k, a)| 2.0 * a * (2.0 * PI * k as f64 * t / len as f64).cos())
+                .sum();
+
+            T::from_sample((amplitudes[0] + sum) / len as f64)
+        })
+        .collect()
+}
+
+/// Returns the dense frequency grid of a [`remez`] design, along with the
+/// desired gain and weight at each grid point.
+fn remez_grid(bands: &[Band], r: usize, even: bool) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let step = 0.5 / (REMEZ_GRID_DENSITY * r) as f64;
+
+    let mut grid = Vec::new();
+    let mut desired = Vec::new();
+    let mut weight = Vec::new();
+
+    for band in bands {
+        let mut high = band.high;
+
+        // The amplitude response of even length kernels is always zero at the
+        // Nyquist frequency, so it cannot be constrained.
+        if even && high > 0.5 - step {
+            high = 0.5 - step;
+        }
+
+        let points = (((high - band.low) / step).round() as usize).max(1);
+
+        for i in 0..=points {
+            let f = band.low + (high - band.low) * i as f64 / points as f64;
+            let (d, w) = if even {
+                let c = (PI * f).cos();
+                (band.gain / c, band.weight * c)
+            } else {
+                (band.gain, band.weight)
+            };
+
+            grid.push(f);
+            desired.push(d);
+            weight.push(w);
+        }
+    }
+
+    (grid, desired, weight)
+}
+
+/// The barycentric Lagrange interpolation of the best approximation on a set
+/// of extremal frequencies.
+#[derive(Default)]
+struct Interpolation {
+    x: Vec<f64>,
+    y: Vec<f64>,
+    weights: Vec<f64>,
+}
+
+impl Interpolation {
+    fn new(grid: &[f64], desired: &[f64], weight: &[f64], extremals: &[usize]) -> Self {
+        let x: Vec<f64> = extremals
+            .iter()
+            .map(|&i| (2.0 * PI * grid[i]).cos())
+            .collect();
+        let n = x.len();
+
+        // Interleave the products, to keep intermediate values in range.
+        let stride = (n - 2) / 15 + 1;
+        let weights: Vec<f64> = (0..n)
+            .map(|k| {
+                let mut denom = 1.0;
+                for start in 0..stride {
+                    for j in (start..n).step_by(stride) {
+                        if j != k {
+                            denom *= 2.0 * (x[k] - x[j]);
+                        }
+                    }
+                }
+
+                if denom.abs() < 1e-5 {
+                    denom = 1e-5;
+                }
+
+                denom.recip()
+            })
+            .collect();
+
+        // The deviation, chosen so that the error alternates in sign with
+        // equal magnitude at each extremal frequency.
+        let mut numer = 0.0;
+        let mut denom = 0.0;
+        let mut sign = 1.0;
+        for (&i, &a) in extremals.iter().zip(&weights) {
+            numer += a * desired[i];
+            denom += sign * a / weight[i];
+            sign = -sign;
+        }
+        let delta = numer / denom;
+
+        let mut sign = 1.0;
+        let y = extremals
+            .iter()
+            .map(|&i| {
+                let y = desired[i] - sign * delta / weight[i];
+                sign = -sign;
+                y
+            })
+            .collect();
+
+        Self { x, y, weights }
+    }
+
+    /// Evaluates the interpolated amplitude response at a frequency.
+    fn eval(&self, f: f64) -> f64 {
+        let xc = (2.0 * PI * f).cos();
+
+        let mut numer = 0.0;
+        let mut denom = 0.0;
+        for ((&x, &y), &a) in self.x.iter().zip(&self.y).zip(&self.weights) {
+            let d = xc - x;
+            if d.abs() < 1e-7 {
+                return y;
+            }
+
+            let c = a / d;
+            numer += c * y;
+            denom += c;
+        }
+
+        numer / denom
+    }
+}
+
+/// Finds `count` alternating extrema of the error on the grid, or [`None`]
+/// if there are too few.
+fn find_extremals(error: &[f64], count: usize) -> Option<Vec<usize>> {
+    let last = error.len() - 1;
+    let mut found = Vec::new();
+
+    if (error[0] > 0.0 && error[0] > error[1]) || (error[0] < 0.0 && error[0] < error[1]) {
+        found.push(0);
+    }
+
+    for i in 1..last {
+        let (prev, e, next) = (error[i - 1], error[i], error[i + 1]);
+        if (e >= prev && e > next && e > 0.0) || (e <= prev && e < next && e < 0.0) {
+            found.push(i);
+        }
+    }
+
+    let (prev, e) = (error[last - 1], error[last]);
+    if (e > 0.0 && e > prev) || (e < 0.0 && e < prev) {
+        found.push(last);
+    }
+
+    if found.len() < count {
+        return None;
+    }
+
+    // Remove the smallest extrema until the right number remain, preferring
+    // to remove one of any pair that does not alternate in sign.
+    while found.len() > count {
+        let mut up = error[found[0]] > 0.0;
+        let mut smallest = 0;
+        let mut alternating = true;
+
+        for j in 1..found.len() {
+            if error[found[j]].abs() < error[found[smallest]].abs() {
+                smallest = j;
+            }
+
+            if up && error[found[j]] < 0.0 {
+                up = false;
+            } else if !up && error[found[j]] > 0.0 {
+                up = true;
+            } else {
+                alternating = false;
+                break;
+            }
+        }
+
+        if alternating && found.len() == count + 1 {
+            let end = found.len() - 1;
+            smallest = if error[found[end]].abs() < error[found[0]].abs() {
+                end
+            } else {
+                0
+            };
+        }
+
+        found.remove(smallest);
+    }
+
+    Some(found)
+}
+
+/// Returns the real amplitude response of a symmetric kernel at a frequency,
+/// normalized to the sample rate.
+fn amplitude(kernel: &[f64], f: f64) -> f64 {
+    let center = (kernel.len() - 1) as f64 / 2.0;
+
+    kernel
+        .iter()
+        .enumerate()
+        .map(|(n, h)| h * (2.0 * PI * f * (n as f64 - center)).cos())
+        .sum()
+}
+
+/// The normalized sinc function, `sin(pi * x) / (pi * x)`.
+fn sinc(x: f64) -> f64 {
+    if x == 0.0 {
+        1.0
+    } else {
+        (PI * x).sin() / (PI * x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gain_db(kernel: &[f64], f: f64) -> f64 {
+        20.0 * amplitude(kernel, f).abs().log10()
+    }
+
+    fn freqs(low: f64, high: f64) -> impl Iterator<Item = f64> {
+        (0..=100).map(move |i| low + (high - low) * i as f64 / 100.0)
+    }
+
+    #[test]
+    fn kaiser_meets_spec() {
+        let spec = KaiserSpec {
+            passband_ripple: 0.1,
+            stopband_attenuation: 60.0,
+            transition_width: 0.04,
+        };
+
+        let responses = [
+            (
+                Response::LowPass { cutoff: 0.2 },
+                vec![(0.0, 0.18)],
+                vec![(0.22, 0.5)],
+            ),
+            (
+                Response::HighPass { cutoff: 0.2 },
+                vec![(0.22, 0.5)],
+                vec![(0.0, 0.18)],
+            ),
+            (
+                Response::BandPass {
+                    low: 0.1,
+                    high: 0.3,
+                },
+                vec![(0.12, 0.28)],
+                vec![(0.0, 0.08), (0.32, 0.5)],
+            ),
+            (
+                Response::BandStop {
+                    low: 0.1,
+                    high: 0.3,
+                },
+                vec![(0.0, 0.08), (0.32, 0.5)],
+                vec![(0.12, 0.28)],
+            ),
+        ];
+
+        for (response, passbands, stopbands) in responses {
+            let kernel: Vec<f64> = kaiser(response, spec);
+
+            for &(low, high) in &passbands {
+                for f in freqs(low, high) {
+                    assert!(gain_db(&kernel, f).abs() < 0.1, "{response:?} at {f}");
+                }
+            }
+
+            for &(low, high) in &stopbands {
+                for f in freqs(low, high) {
+                    assert!(gain_db(&kernel, f) < -59.0, "{response:?} at {f}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn frequency_sampling_matches_curve() {
+        let curve = |f: f64| if f < 0.25 { 1.0 } else { 0.1 };
+        let kernel: Vec<f64> = frequency_sampling(255, curve, Window::BlackmanHarris);
+
+        for f in freqs(0.0, 0.2) {
+            assert!((amplitude(&kernel, f) - 1.0).abs() < 1e-3, "at {f}");
+        }
+        for f in freqs(0.3, 0.5) {
+            assert!((amplitude(&kernel, f) - 0.1).abs() < 1e-3, "at {f}");
+        }
+    }
+
+    #[test]
+    fn remez_is_equiripple() {
+        let bands = [
+            Band {
+                low: 0.0,
+                high: 0.2,
+                gain: 1.0,
+                weight: 1.0,
+            },
+            Band {
+                low: 0.25,
+                high: 0.5,
+                gain: 0.0,
+                weight: 10.0,
+            },
+        ];
+
+        for len in [32, 33] {
+            let kernel: Vec<f64> = remez(len, &bands);
+
+            let passband = freqs(0.0, 0.2).map(|f| (amplitude(&kernel, f) - 1.0).abs());
+            let stopband = freqs(0.25, 0.5).map(|f| amplitude(&kernel, f).abs());
+            let passband = passband.fold(0.0, f64::max);
+            let stopband = stopband.fold(0.0, f64::max);
+
+            // The stopband error is weighted 10 times more heavily.
+            assert!(
+                (passband / stopband - 10.0).abs() < 1.0,
+                "{len}: {passband} {stopband}"
+            );
+            assert!(stopband < 1e-2, "{len}: {stopband}");
+        }
+    }
+
+    #[test]
+    fn remez_band_pass() {
+        let bands = [
+            Band {
+                low: 0.0,
+                high: 0.1,
+                gain: 0.0,
+                weight: 1.0,
+            },
+            Band {
+                low: 0.15,
+                high: 0.35,
+                gain: 1.0,
+                weight: 1.0,
+            },
+            Band {
+                low: 0.4,
+                high: 0.5,
+                gain: 0.0,
+                weight: 1.0,
+            },
+        ];
+
+        let kernel: Vec<f64> = remez(65, &bands);
+
+        assert!(freqs(0.15, 0.35).all(|f| gain_db(&kernel, f).abs() < 0.1));
+        assert!(
+            freqs(0.0, 0.1)
+                .chain(freqs(0.4, 0.5))
+                .all(|f| gain_db(&kernel, f) < -40.0)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_band() {
+        let response = Response::BandPass {
+            low: 0.1,
+            high: 0.1,
+        };
+        windowed_sinc::<f64>(response, 31, Window::Hann);
+    }
+}
@@ -0,0 +1,140 @@
+//! Finite impulse response (FIR) filtering, convolution and filter design.
+
+mod convolve;
+mod design;
+
+pub use self::convolve::{Convolve, ImpulseResponse, Method};
+pub use self::design::{
+    Band, KaiserSpec, Response, frequency_sampling, kaiser, remez, windowed_sinc,
+};
+
+use crate::frame::Fixed;
+use crate::processor::Processor;
+use crate::sample::FloatSample;
+
+/// A direct-form FIR filter, which convolves its input with a kernel of
+/// coefficients one sample at a time.
+///
+/// The cost per sample grows with the length of the kernel, so this is best
+/// suited to short kernels. For long impulse responses, use
+/// [`Signal::convolve`](crate::Signal::convolve) with
+/// [`Method::Partitioned`] instead.
+#[derive(Clone, Debug)]
+pub struct Fir<S>
+where
+    S: FloatSample,
+{
+    coeffs: Vec<S>,
+
+    // The input history, stored twice in a row so that the most recent
+    // `coeffs.len()` inputs are always contiguous, starting at `pos`.
+    history: Vec<S>,
+    pos: usize,
+}
+
+impl<S> Fir<S>
+where
+    S: FloatSample,
+{
+    /// Creates a new [`Fir`] filter from its kernel of coefficients, which is
+    /// also its impulse response.
+    ///
+    /// ```
+    /// use sampara::fir::Fir;
+    ///
+    /// fn main() {
+    ///     let mut fir = Fir::new(vec![0.5f32, 0.25]);
+    ///
+    ///     assert_eq!(fir.process(1.0), 0.5);
+    ///     assert_eq!(fir.process(0.0), 0.25);
+    ///     assert_eq!(fir.process(0.0), 0.0);
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `coeffs` is empty.
+    pub fn new(coeffs: Vec<S>) -> Self {
+        assert!(!coeffs.is_empty(), "FIR kernel must not be empty");
+
+        let history = vec![S::EQUILIBRIUM; 2 * coeffs.len()];
+
+        Self {
+            coeffs,
+            history,
+            pos: 0,
+        }
+    }
+
+    pub fn coefficients(&self) -> &[S] {
+        &self.coeffs
+    }
+
+    pub fn reset(&mut self) {
+        self.history.fill(S::EQUILIBRIUM);
+        self.pos = 0;
+    }
+
+    pub fn process(&mut self, input: S) -> S {
+        let len = self.coeffs.len();
+
+        self.pos = if self.pos == 0 { len - 1 } else { self.pos - 1 };
+        self.history[self.pos] = input;
+        self.history[self.pos + len] = input;
+
+        self.coeffs
+            .iter()
+            .zip(&self.history[self.pos..self.pos + len])
+            .fold(S::EQUILIBRIUM, |acc, (&h, &x)| acc + h * x)
+    }
+}
+
+impl<S> From<Vec<S>> for Fir<S>
+where
+    S: FloatSample,
+{
+    fn from(coeffs: Vec<S>) -> Self {
+        Self::new(coeffs)
+    }
+}
+
+impl<S> Processor for Fir<S>
+where
+    S: FloatSample,
+{
+    type Frame = Fixed<S, 1>;
+
+    #[inline]
+    fn process(&mut self, block: &mut [Self::Frame]) {
+        for frame in block.iter_mut() {
+            let [x] = frame.into_array();
+            *frame = Fixed::from([Fir::process(self, x)]);
+        }
+    }
+
+    fn reset(&mut self) {
+        Fir::reset(self)
+    }
+}
+
+impl<S, const N: usize> Processor for [Fir<S>; N]
+where
+    S: FloatSample,
+{
+    type Frame = Fixed<S, N>;
+
+    #[inline]
+    fn process(&mut self, block: &mut [Self::Frame]) {
+        for frame in block.iter_mut() {
+            let mut samples = frame.into_array();
+            for (fir, sample) in self.iter_mut().zip(samples.iter_mut()) {
+                *sample = Fir::process(fir, *sample);
+            }
+            *frame = Fixed::from(samples);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.iter_mut().for_each(Fir::reset)
+    }
+}
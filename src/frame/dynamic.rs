@@ -85,11 +85,16 @@ impl<S: Sample, const N: usize> From<[S; N]> for Dynamic<S> {
 
 impl<S: Sample> Frame for Dynamic<S> {
     type Sample = S;
+    type Float = Dynamic<S::Float>;
 
     fn equil() -> Self {
         Self(Box::new([]))
     }
 
+    fn equil_with_channels(channels: usize) -> Option<Self> {
+        Some(Self(vec![S::EQUILIBRIUM; channels].into_boxed_slice()))
+    }
+
     fn get(&self, channel: usize) -> Option<&S> {
         self.0.get(channel)
     }
@@ -109,6 +114,14 @@ impl<S: Sample> Frame for Dynamic<S> {
     fn len(&self) -> usize {
         self.0.len()
     }
+
+    fn into_float_frame(self) -> Self::Float {
+        Dynamic(self.0.iter().map(|s| s.into_float()).collect())
+    }
+
+    fn into_dynamic(self) -> Dynamic<S> {
+        self
+    }
 }
 
 impl<S: Sample> IntoIterator for Dynamic<S> {
@@ -37,11 +37,16 @@ impl<S: Sample, const N: usize> From<[S; N]> for Fixed<S, N> {
 
 impl<S: Sample, const N: usize> Frame for Fixed<S, N> {
     type Sample = S;
+    type Float = Fixed<S::Float, N>;
 
     fn equil() -> Self {
         Self::EQUILIBRIUM
     }
 
+    fn equil_with_channels(channels: usize) -> Option<Self> {
+        (channels == N).then_some(Self::EQUILIBRIUM)
+    }
+
     fn get(&self, channel: usize) -> Option<&S> {
         self.0.get(channel)
     }
@@ -61,6 +66,10 @@ impl<S: Sample, const N: usize> Frame for Fixed<S, N> {
     fn len(&self) -> usize {
         N
     }
+
+    fn into_float_frame(self) -> Self::Float {
+        Fixed(self.0.map(Sample::into_float))
+    }
 }
 
 impl<S: Sample, const N: usize> IntoIterator for Fixed<S, N> {
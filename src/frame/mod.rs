@@ -11,8 +11,26 @@ use crate::sample::Sample;
 pub trait Frame: Clone + PartialEq + Debug + Default + IntoIterator<Item = Self::Sample> {
     type Sample: Sample;
 
+    /// The [`Frame`] type with the same number of channels, but with the
+    /// corresponding [`Sample::Float`] type.
+    type Float: Frame<Sample = <Self::Sample as Sample>::Float>;
+
     fn equil() -> Self;
 
+    /// Creates a [`Frame`] with `channels` channels, all at equilibrium, or
+    /// [`None`] if this type cannot have that many channels.
+    ///
+    /// ```
+    /// use sampara::{Dynamic, Fixed, Frame};
+    ///
+    /// fn main() {
+    ///     assert_eq!(Fixed::<i16, 2>::equil_with_channels(2), Some(Fixed::from([0, 0])));
+    ///     assert_eq!(Fixed::<i16, 2>::equil_with_channels(3), None);
+    ///     assert_eq!(Dynamic::<u8>::equil_with_channels(3), Some(Dynamic::from([128, 128, 128])));
+    /// }
+    /// ```
+    fn equil_with_channels(channels: usize) -> Option<Self>;
+
     fn get(&self, channel: usize) -> Option<&Self::Sample>;
 
     fn get_mut(&mut self, channel: usize) -> Option<&mut Self::Sample>;
@@ -22,6 +40,38 @@ pub trait Frame: Clone + PartialEq + Debug + Default + IntoIterator<Item = Self:
     fn iter_mut(&mut self) -> impl Iterator<Item = &mut Self::Sample>;
 
     fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Converts each [`Sample`] of this [`Frame`] into its corresponding
+    /// [`Sample::Float`] type.
+    ///
+    /// ```
+    /// use sampara::{Fixed, Frame};
+    ///
+    /// fn main() {
+    ///     let frame = Fixed::from([128u8, 0, 255]);
+    ///     assert_eq!(frame.into_float_frame(), Fixed::from([0.0, -1.0, 0.9921875]));
+    /// }
+    /// ```
+    fn into_float_frame(self) -> Self::Float;
+
+    /// Converts this [`Frame`] into a [`Dynamic`] frame with the same
+    /// [`Sample`]s. [`Dynamic`] frames are returned as they are.
+    ///
+    /// ```
+    /// use sampara::{Dynamic, Fixed, Frame};
+    ///
+    /// fn main() {
+    ///     let frame = Fixed::from([0.5, -0.5]);
+    ///     assert_eq!(frame.into_dynamic(), Dynamic::from([0.5, -0.5]));
+    /// }
+    /// ```
+    fn into_dynamic(self) -> Dynamic<Self::Sample> {
+        Dynamic::from(self.into_iter().collect::<Vec<_>>())
+    }
 }
 
 /// An iterator that yields the [`Sample`] for each channel in the frame by
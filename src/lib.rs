@@ -1,14 +1,27 @@
-#![feature(associated_type_defaults)]
-
 pub mod biquad;
+pub mod buffer;
+pub mod channel;
+pub mod delay;
+pub mod dynamics;
+pub mod envelope;
+pub mod fir;
 pub mod frame;
+pub mod mixer;
+pub mod modulation;
+pub mod processor;
+pub mod reverb;
+pub mod ring;
 pub mod sample;
 pub mod signal;
+pub mod spectrum;
+pub mod split;
 pub mod stats;
+pub mod stereo;
+pub mod waveshape;
 
 pub use frame::{Dynamic, Fixed, Frame};
 pub use sample::{FromSample, IntoSample, Sample};
-pub use signal::Signal;
+pub use signal::{CollectTarget, ExactSizeSignal, SeekableSignal, Signal};
 
 #[cfg(test)]
 mod tests {}
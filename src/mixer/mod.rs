@@ -0,0 +1,390 @@
+//! Mixing any number of [`Signal`]s into one, as on a mixing console bus.
+
+use crate::frame::Frame;
+use crate::sample::{FromSample, Sample, SignedSample, to_f64};
+use crate::signal::{Signal, min_len_hint};
+use crate::stereo::PanLaw;
+
+/// What a [`Mixer`] does when one of its inputs ends.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EndPolicy {
+    /// The mixer ends as soon as any of its inputs ends.
+    Stop,
+
+    /// Inputs that have ended are padded with equilibrium, as with
+    /// [`Signal::sig_next`], and the mixer ends once all of its inputs have
+    /// ended.
+    Pad,
+}
+
+/// Identifies an input of a [`Mixer`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct InputId(usize);
+
+/// The settings of an input of a [`Mixer`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct InputParams {
+    /// The gain of the input.
+    pub gain: f64,
+
+    /// The position of a stereo input, from -1 (left) to 1 (right), following
+    /// the pan law of the [`Mixer`]. This is ignored unless the [`Frame`]s
+    /// have two channels.
+    pub pan: f64,
+
+    /// Whether the input is silenced.
+    pub mute: bool,
+
+    /// Whether the input is soloed. While any input is soloed, only soloed
+    /// inputs are heard. A muted input stays silent even when soloed.
+    pub solo: bool,
+}
+
+impl Default for InputParams {
+    fn default() -> Self {
+        Self {
+            gain: 1.0,
+            pan: 0.0,
+            mute: false,
+            solo: false,
+        }
+    }
+}
+
+/// An input of a [`Mixer`].
+struct Input<'a, F> {
+    id: InputId,
+    signal: Box<dyn Signal<Frame = F> + 'a>,
+    params: InputParams,
+    ended: bool,
+}
+
+/// A [`Signal`] that sums a dynamic number of input [`Signal`]s with the same
+/// [`Frame`] type, each with its own gain, pan, mute and solo.
+///
+/// Each input sample is converted to the [`Sample::Signed`] type, scaled if
+/// its gain or pan is not 1, and summed with
+/// [`SignedSample::saturating_add_amp`], so that integer inputs clip instead
+/// of overflowing. Inputs at unity gain are summed exactly, even for 64-bit
+/// samples that do not fit in an `f64`.
+///
+/// Inputs can be added and removed between calls to [`Signal::next`]. Every
+/// input is advanced on each call, including muted inputs, so that they stay
+/// in sync.
+///
+/// ```
+/// use sampara::{signal, Signal};
+/// use sampara::mixer::{EndPolicy, InputParams, Mixer};
+///
+/// fn main() {
+///     let mut mixer = Mixer::new(EndPolicy::Pad);
+///
+///     let drums = mixer.add(signal::from_samples_fixed::<_, 1>(vec![100i16, 200, 300]));
+///     let bass = mixer.add_with(
+///         signal::from_samples_fixed::<_, 1>(vec![i16::MAX, i16::MAX]),
+///         InputParams { gain: 0.5, ..Default::default() },
+///     );
+///
+///     assert_eq!(mixer.next(), Some([16483].into()));
+///
+///     mixer.params_mut(bass).unwrap().mute = true;
+///     assert_eq!(mixer.next(), Some([200].into()));
+///
+///     // Only the bass is left, and it has ended.
+///     mixer.remove(drums);
+///     mixer.params_mut(bass).unwrap().mute = false;
+///     assert_eq!(mixer.next(), None);
+/// }
+/// ```
+pub struct Mixer<'a, F>
+where
+    F: Frame,
+{
+    inputs: Vec<Input<'a, F>>,
+    end: EndPolicy,
+    pan_law: PanLaw,
+    next_id: usize,
+    sum: Vec<<F::Sample as Sample>::Signed>,
+}
+
+impl<'a, F> Mixer<'a, F>
+where
+    F: Frame,
+    F::Sample: FromSample<<F::Sample as Sample>::Signed>,
+    <F::Sample as Sample>::Signed: FromSample<f64>,
+{
+    /// Creates a new [`Mixer`] without any inputs, using
+    /// [`PanLaw::Linear`] so that centered inputs are unchanged.
+    pub fn new(end: EndPolicy) -> Self {
+        Self {
+            inputs: Vec::new(),
+            end,
+            pan_law: PanLaw::Linear,
+            next_id: 0,
+            sum: Vec::new(),
+        }
+    }
+
+    /// Sets the pan law of stereo inputs.
+    pub fn pan_law(mut self, pan_law: PanLaw) -> Self {
+        self.pan_law = pan_law;
+        self
+    }
+
+    /// Adds an input with the default [`InputParams`].
+    pub fn add<S>(&mut self, signal: S) -> InputId
+    where
+        S: Signal<Frame = F> + 'a,
+    {
+        self.add_with(signal, InputParams::default())
+    }
+
+    /// Adds an input.
+    pub fn add_with<S>(&mut self, signal: S, params: InputParams) -> InputId
+    where
+        S: Signal<Frame = F> + 'a,
+    {
+        self.add_boxed(Box::new(signal), params)
+    }
+
+    /// Adds an input that is already boxed.
+    pub fn add_boxed(
+        &mut self,
+        signal: Box<dyn Signal<Frame = F> + 'a>,
+        params: InputParams,
+    ) -> InputId {
+        let id = InputId(self.next_id);
+        self.next_id += 1;
+
+        self.inputs.push(Input {
+            id,
+            signal,
+            params,
+            ended: false,
+        });
+
+        id
+    }
+
+    /// Removes an input, and returns it if it was in the mixer.
+    pub fn remove(&mut self, id: InputId) -> Option<Box<dyn Signal<Frame = F> + 'a>> {
+        let index = self.inputs.iter().position(|input| input.id == id)?;
+        Some(self.inputs.remove(index).signal)
+    }
+
+    /// The number of inputs, including any that have ended.
+    pub fn len(&self) -> usize {
+        self.inputs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inputs.is_empty()
+    }
+
+    /// The ids of the inputs, in the order that they were added.
+    pub fn ids(&self) -> impl Iterator<Item = InputId> + '_ {
+        self.inputs.iter().map(|input| input.id)
+    }
+
+    pub fn params(&self, id: InputId) -> Option<&InputParams> {
+        self.inputs
+            .iter()
+            .find(|input| input.id == id)
+            .map(|input| &input.params)
+    }
+
+    pub fn params_mut(&mut self, id: InputId) -> Option<&mut InputParams> {
+        self.inputs
+            .iter_mut()
+            .find(|input| input.id == id)
+            .map(|input| &mut input.params)
+    }
+
+    /// Whether an input has ended. Under [`EndPolicy::Pad`], ended inputs are
+    /// kept until they are removed.
+    pub fn has_ended(&self, id: InputId) -> Option<bool> {
+        self.inputs
+            .iter()
+            .find(|input| input.id == id)
+            .map(|input| input.ended)
+    }
+}
+
+impl<'a, F> Signal for Mixer<'a, F>
+where
+    F: Frame,
+    F::Sample: FromSample<<F::Sample as Sample>::Signed>,
+    <F::Sample as Sample>::Signed: FromSample<f64>,
+{
+    type Frame = F;
+
+    /// # Panics
+    ///
+    /// Panics if the inputs yield [`Frame`]s with different numbers of
+    /// channels.
+    fn next(&mut self) -> Option<Self::Frame> {
+        let any_solo = self
+            .inputs
+            .iter()
+            .any(|input| input.params.solo && !input.ended);
+        let mut template = None;
+        let mut live = false;
+
+        self.sum.clear();
+
+        for input in self.inputs.iter_mut() {
+            let frame = if input.ended {
+                None
+            } else {
+                input.signal.next()
+            };
+
+            let Some(frame) = frame else {
+                input.ended = true;
+                match self.end {
+                    EndPolicy::Stop => return None,
+                    EndPolicy::Pad => continue,
+                }
+            };
+
+            live = true;
+
+            if self.sum.is_empty() {
+                self.sum.resize(frame.len(), Sample::EQUILIBRIUM);
+            }
+            assert_eq!(
+                frame.len(),
+                self.sum.len(),
+                "mixer inputs must have the same number of channels"
+            );
+
+            let InputParams {
+                gain,
+                pan,
+                mute,
+                solo,
+            } = input.params;
+
+            if !mute && (solo || !any_solo) {
+                let pan = match frame.len() {
+                    2 => self.pan_law.gains(pan),
+                    _ => [1.0, 1.0],
+                };
+
+                for (c, (sum, x)) in self.sum.iter_mut().zip(frame.iter()).enumerate() {
+                    let scale = gain * pan[c.min(1)];
+                    let x = if scale == 1.0 {
+                        x.into_signed()
+                    } else {
+                        FromSample::from_sample(scale * to_f64(x))
+                    };
+
+                    *sum = sum.saturating_add_amp(x);
+                }
+            }
+
+            template.get_or_insert(frame);
+        }
+
+        if !live {
+            return None;
+        }
+
+        let mut output = template?;
+        for (sample, &y) in output.iter_mut().zip(&self.sum) {
+            *sample = FromSample::from_sample(y);
+        }
+
+        Some(output)
+    }
+
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        let mut live = self.inputs.iter().filter(|input| !input.ended);
+        let Some(first) = live.next() else {
+            return (0, Some(0));
+        };
+
+        match self.end {
+            EndPolicy::Stop if self.inputs.iter().any(|input| input.ended) => (0, Some(0)),
+            EndPolicy::Stop => live.fold(first.signal.len_hint(), |hint, input| {
+                min_len_hint(hint, input.signal.len_hint())
+            }),
+            EndPolicy::Pad => live.fold(first.signal.len_hint(), |(lower, upper), input| {
+                let (l, u) = input.signal.len_hint();
+                (lower.max(l), upper.zip(u).map(|(a, b)| a.max(b)))
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signal;
+
+    #[test]
+    fn solo_and_pan() {
+        let mut mixer = Mixer::new(EndPolicy::Stop).pan_law(PanLaw::ConstantAmplitude);
+
+        let a = mixer.add(signal::from_samples_fixed::<_, 2>(vec![0.5f32; 16]));
+        let b = mixer.add_with(
+            signal::from_samples_fixed::<_, 2>(vec![0.25f32; 12]),
+            InputParams {
+                pan: -1.0,
+                ..Default::default()
+            },
+        );
+        assert_eq!(mixer.next(), Some([0.5, 0.25].into()));
+
+        mixer.params_mut(b).unwrap().solo = true;
+        assert_eq!(mixer.next(), Some([0.25, 0.0].into()));
+
+        // A muted solo is silent itself, and still silences everything else.
+        mixer.params_mut(b).unwrap().mute = true;
+        assert_eq!(mixer.next(), Some([0.0, 0.0].into()));
+
+        mixer.params_mut(b).unwrap().solo = false;
+        mixer.params_mut(a).unwrap().gain = 2.0;
+        assert_eq!(mixer.next(), Some([0.5, 0.5].into()));
+
+        // The mixer stops with its shortest input.
+        assert!(mixer.next().is_some());
+        assert!(mixer.next().is_some());
+        assert_eq!(mixer.next(), None);
+    }
+
+    #[test]
+    fn unsigned_headroom() {
+        let mut mixer = Mixer::new(EndPolicy::Pad);
+        mixer.add(signal::from_samples_fixed::<_, 1>(vec![200u8, 250, 10]));
+        mixer.add(signal::from_samples_fixed::<_, 1>(vec![100u8, 250]));
+
+        // 72 + -28 around the equilibrium of 128.
+        assert_eq!(mixer.next(), Some([172].into()));
+        assert_eq!(mixer.next(), Some([u8::MAX].into()));
+        assert_eq!(mixer.next(), Some([10].into()));
+        assert_eq!(mixer.next(), None);
+        assert_eq!(mixer.len(), 2);
+    }
+
+    #[test]
+    fn unity_gain_is_exact() {
+        // These do not fit in the 53-bit mantissa of an f64.
+        let mut mixer = Mixer::new(EndPolicy::Stop);
+        mixer.add(signal::from_samples_fixed::<_, 1>(vec![
+            0x1234_5678_9abc_def1i64,
+        ]));
+        assert_eq!(mixer.next(), Some([0x1234_5678_9abc_def1].into()));
+
+        let mut mixer = Mixer::new(EndPolicy::Stop);
+        mixer.add(signal::from_samples_fixed::<_, 1>(vec![
+            0x1234_5678_9abc_def1u64,
+        ]));
+        assert_eq!(mixer.next(), Some([0x1234_5678_9abc_def1].into()));
+
+        let mut mixer = Mixer::new(EndPolicy::Stop);
+        mixer.add(signal::from_samples_fixed::<_, 1>(vec![i64::MAX - 1]));
+        mixer.add(signal::from_samples_fixed::<_, 1>(vec![2i64]));
+        assert_eq!(mixer.next(), Some([i64::MAX].into()));
+    }
+}
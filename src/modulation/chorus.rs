@@ -0,0 +1,480 @@
+use crate::delay::DelayLine;
+use crate::frame::Frame;
+use crate::modulation::{Lfo, Shape, channel_lfo};
+use crate::sample::{FloatSample, FromSample, Sample};
+use crate::signal::{ExactSizeSignal, Signal, len_hint_with_tail};
+
+/// The parameters of a [`Chorus`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ChorusParams {
+    /// The number of delayed voices.
+    pub voices: usize,
+
+    /// The frequency of the modulation, in Hz.
+    pub rate: f64,
+
+    /// The shortest delay of each voice, in seconds.
+    pub delay: f64,
+
+    /// How far the delay of each voice is swept, in seconds.
+    pub depth: f64,
+
+    /// The proportion of delayed voices in the output, from 0 (dry) to 1
+    /// (wet).
+    pub mix: f64,
+
+    /// The phase offset between the modulation of adjacent channels, in
+    /// cycles.
+    pub spread: f64,
+}
+
+impl Default for ChorusParams {
+    fn default() -> Self {
+        Self {
+            voices: 3,
+            rate: 0.5,
+            delay: 0.015,
+            depth: 0.005,
+            mix: 0.5,
+            spread: 0.25,
+        }
+    }
+}
+
+/// The state of one channel of a [`Chorus`].
+struct ChorusChannel<T> {
+    line: DelayLine<T>,
+    lfos: Vec<Lfo>,
+}
+
+/// A [`Signal`] that mixes an underlying [`Signal`] with several copies of
+/// itself, each with a slowly modulated delay.
+///
+/// The voices of each channel are spread evenly over the modulation cycle.
+/// The output is extended by the longest delay, so that the voices can
+/// finish once the input ends.
+///
+/// This is created by [`Signal::chorus`].
+pub struct Chorus<S>
+where
+    S: Signal,
+{
+    signal: S,
+    params: ChorusParams,
+    sample_rate: f64,
+    channels: Vec<ChorusChannel<<S::Frame as Frame>::Sample>>,
+    // A frame of equilibrium, used to flush the delay once the input ends.
+    silence: Option<S::Frame>,
+    // The number of frames left to output, once the input has run out.
+    tail: Option<usize>,
+}
+
+impl<S> Chorus<S>
+where
+    S: Signal,
+    <S::Frame as Frame>::Sample: FloatSample,
+{
+    pub(crate) fn new(signal: S, params: ChorusParams, sample_rate: f64) -> Self {
+        Self {
+            signal,
+            params,
+            sample_rate,
+            channels: Vec::new(),
+            silence: None,
+            tail: None,
+        }
+    }
+
+    /// The number of [`Frame`]s that the voices ring for once the input ends.
+    fn tail_len(&self) -> usize {
+        ((self.params.delay + self.params.depth) * self.sample_rate).ceil() as usize
+    }
+
+    pub fn params(&self) -> &ChorusParams {
+        &self.params
+    }
+
+    /// Sets new parameters. If the number of voices or the maximum delay
+    /// changes, the delay lines are cleared.
+    pub fn set_params(&mut self, params: ChorusParams) {
+        if params.voices != self.params.voices
+            || params.delay + params.depth != self.params.delay + self.params.depth
+        {
+            self.channels.clear();
+        }
+
+        for channel in self.channels.iter_mut() {
+            channel
+                .lfos
+                .iter_mut()
+                .for_each(|l| l.set_frequency(params.rate));
+        }
+
+        self.params = params;
+    }
+
+    fn channel(&self, channel: usize) -> ChorusChannel<<S::Frame as Frame>::Sample> {
+        let ChorusParams {
+            voices,
+            rate,
+            delay,
+            depth,
+            spread,
+            ..
+        } = self.params;
+
+        let max_delay = ((delay + depth) * self.sample_rate).ceil() as usize + 1;
+        let lfos = (0..voices)
+            .map(|v| {
+                let lfo = channel_lfo(Shape::Sine, rate, self.sample_rate, spread, channel);
+                lfo.with_phase(lfo.phase() + v as f64 / voices as f64)
+            })
+            .collect();
+
+        ChorusChannel {
+            line: DelayLine::new(max_delay),
+            lfos,
+        }
+    }
+}
+
+impl<S> Signal for Chorus<S>
+where
+    S: Signal,
+    <S::Frame as Frame>::Sample: FloatSample,
+{
+    type Frame = S::Frame;
+
+    fn next(&mut self) -> Option<Self::Frame> {
+        let mut frame = match self.tail {
+            Some(0) => return None,
+            Some(ref mut tail) => {
+                *tail -= 1;
+                self.silence.clone()?
+            }
+            None => match self.signal.next() {
+                Some(frame) => {
+                    if self.silence.is_none() {
+                        let mut silence = frame.clone();
+                        silence
+                            .iter_mut()
+                            .for_each(|s| *s = <S::Frame as Frame>::Sample::EQUILIBRIUM);
+                        self.silence = Some(silence);
+                    }
+
+                    frame
+                }
+                None => {
+                    self.tail = Some(self.tail_len());
+                    return self.next();
+                }
+            },
+        };
+
+        if self.channels.len() != frame.len() {
+            self.channels = (0..frame.len()).map(|c| self.channel(c)).collect();
+        }
+
+        let ChorusParams {
+            voices,
+            delay,
+            depth,
+            mix,
+            ..
+        } = self.params;
+        let gain = mix / voices.max(1) as f64;
+
+        for (sample, channel) in frame.iter_mut().zip(self.channels.iter_mut()) {
+            channel.line.push(*sample);
+
+            let wet = channel
+                .lfos
+                .iter_mut()
+                .map(|lfo| {
+                    let time = delay + depth * 0.5 * (lfo.tick() + 1.0);
+                    channel.line.read_lagrange(time * self.sample_rate)
+                })
+                .fold(<S::Frame as Frame>::Sample::EQUILIBRIUM, |acc, x| acc + x);
+
+            *sample =
+                *sample * FromSample::from_sample(1.0 - mix) + wet * FromSample::from_sample(gain);
+        }
+
+        Some(frame)
+    }
+
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        let (input, tail) = match self.tail {
+            Some(tail) => ((0, Some(0)), tail),
+            None => (self.signal.len_hint(), self.tail_len()),
+        };
+
+        len_hint_with_tail(input, tail, self.silence.is_some())
+    }
+}
+
+impl<S> ExactSizeSignal for Chorus<S>
+where
+    S: ExactSizeSignal,
+    <S::Frame as Frame>::Sample: FloatSample,
+{
+}
+
+/// The parameters of a [`Flanger`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FlangerParams {
+    /// The frequency of the modulation, in Hz.
+    pub rate: f64,
+
+    /// The shortest delay, in seconds.
+    pub delay: f64,
+
+    /// How far the delay is swept, in seconds.
+    pub depth: f64,
+
+    /// The proportion of the delayed signal that is fed back into the delay,
+    /// from -1 to 1. Higher values make the comb filter more resonant.
+    pub feedback: f64,
+
+    /// The proportion of the delayed signal in the output, from 0 (dry) to 1
+    /// (wet).
+    pub mix: f64,
+
+    /// The phase offset between the modulation of adjacent channels, in
+    /// cycles.
+    pub spread: f64,
+}
+
+impl Default for FlangerParams {
+    fn default() -> Self {
+        Self {
+            rate: 0.25,
+            delay: 0.001,
+            depth: 0.002,
+            feedback: 0.5,
+            mix: 0.5,
+            spread: 0.25,
+        }
+    }
+}
+
+/// The state of one channel of a [`Flanger`].
+struct FlangerChannel<T> {
+    line: DelayLine<T>,
+    lfo: Lfo,
+}
+
+/// A [`Signal`] that mixes an underlying [`Signal`] with a copy of itself,
+/// whose short delay is swept to create a moving comb filter.
+///
+/// The output is extended by the longest delay, so that the delayed signal
+/// can finish once the input ends.
+///
+/// This is created by [`Signal::flanger`].
+pub struct Flanger<S>
+where
+    S: Signal,
+{
+    signal: S,
+    params: FlangerParams,
+    sample_rate: f64,
+    channels: Vec<FlangerChannel<<S::Frame as Frame>::Sample>>,
+    // A frame of equilibrium, used to flush the delay once the input ends.
+    silence: Option<S::Frame>,
+    // The number of frames left to output, once the input has run out.
+    tail: Option<usize>,
+}
+
+impl<S> Flanger<S>
+where
+    S: Signal,
+    <S::Frame as Frame>::Sample: FloatSample,
+{
+    pub(crate) fn new(signal: S, params: FlangerParams, sample_rate: f64) -> Self {
+        Self {
+            signal,
+            params,
+            sample_rate,
+            channels: Vec::new(),
+            silence: None,
+            tail: None,
+        }
+    }
+
+    /// The number of [`Frame`]s that the delayed signal rings for once the
+    /// input ends, until the feedback has decayed by 60 dB.
+    fn tail_len(&self) -> usize {
+        let longest = ((self.params.delay + self.params.depth) * self.sample_rate).ceil();
+        let feedback = self.params.feedback.abs();
+
+        // Each pass through the line scales the delayed signal by the feedback.
+        let passes = match feedback {
+            0.0 => 0.0,
+            f if f < 1.0 => -3.0 / f.log10(),
+            _ => return usize::MAX,
+        };
+
+        ((1.0 + passes) * longest).ceil() as usize
+    }
+
+    pub fn params(&self) -> &FlangerParams {
+        &self.params
+    }
+
+    /// Sets new parameters. If the maximum delay changes, the delay lines are
+    /// cleared.
+    pub fn set_params(&mut self, params: FlangerParams) {
+        if params.delay + params.depth != self.params.delay + self.params.depth {
+            self.channels.clear();
+        }
+
+        for channel in self.channels.iter_mut() {
+            channel.lfo.set_frequency(params.rate);
+        }
+
+        self.params = params;
+    }
+}
+
+impl<S> Signal for Flanger<S>
+where
+    S: Signal,
+    <S::Frame as Frame>::Sample: FloatSample,
+{
+    type Frame = S::Frame;
+
+    fn next(&mut self) -> Option<Self::Frame> {
+        let mut frame = match self.tail {
+            Some(0) => return None,
+            Some(ref mut tail) => {
+                *tail -= 1;
+                self.silence.clone()?
+            }
+            None => match self.signal.next() {
+                Some(frame) => {
+                    if self.silence.is_none() {
+                        let mut silence = frame.clone();
+                        silence
+                            .iter_mut()
+                            .for_each(|s| *s = <S::Frame as Frame>::Sample::EQUILIBRIUM);
+                        self.silence = Some(silence);
+                    }
+
+                    frame
+                }
+                None => {
+                    self.tail = Some(self.tail_len());
+                    return self.next();
+                }
+            },
+        };
+
+        let FlangerParams {
+            rate,
+            delay,
+            depth,
+            feedback,
+            mix,
+            spread,
+        } = self.params;
+
+        if self.channels.len() != frame.len() {
+            let max_delay = ((delay + depth) * self.sample_rate).ceil() as usize + 1;
+
+            self.channels = (0..frame.len())
+                .map(|c| FlangerChannel {
+                    line: DelayLine::new(max_delay),
+                    lfo: channel_lfo(Shape::Triangle, rate, self.sample_rate, spread, c),
+                })
+                .collect();
+        }
+
+        for (sample, channel) in frame.iter_mut().zip(self.channels.iter_mut()) {
+            // The line is read before the current sample is pushed, so that it
+            // can be fed back, which adds one sample of delay.
+            let time = delay + depth * 0.5 * (channel.lfo.tick() + 1.0);
+            let delayed = channel
+                .line
+                .read_lagrange((time * self.sample_rate - 1.0).max(0.0));
+
+            channel
+                .line
+                .push(*sample + delayed * FromSample::from_sample(feedback));
+
+            *sample = *sample * FromSample::from_sample(1.0 - mix)
+                + delayed * FromSample::from_sample(mix);
+        }
+
+        Some(frame)
+    }
+
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        let (input, tail) = match self.tail {
+            Some(tail) => ((0, Some(0)), tail),
+            None => (self.signal.len_hint(), self.tail_len()),
+        };
+
+        len_hint_with_tail(input, tail, self.silence.is_some())
+    }
+}
+
+impl<S> ExactSizeSignal for Flanger<S>
+where
+    S: ExactSizeSignal,
+    <S::Frame as Frame>::Sample: FloatSample,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::signal;
+
+    #[test]
+    fn chorus_voices_are_delayed() {
+        // Without modulation, each voice is a fixed delay of 2 frames.
+        let params = ChorusParams {
+            voices: 2,
+            rate: 0.0,
+            delay: 2.0,
+            depth: 0.0,
+            mix: 1.0,
+            spread: 0.0,
+        };
+
+        let input = vec![1.0f64, 0.0, 0.0, 0.0];
+        let mut signal = signal::from_samples_fixed::<_, 1>(input).chorus(params, 1.0);
+
+        let output: Vec<f64> = std::iter::from_fn(|| signal.next())
+            .map(|f| f.into_array()[0])
+            .collect();
+        assert_eq!(output, vec![0.0, 0.0, 1.0, 0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn flanger_feedback() {
+        let params = FlangerParams {
+            rate: 0.0,
+            delay: 2.0,
+            depth: 0.0,
+            feedback: 0.5,
+            mix: 1.0,
+            spread: 0.0,
+        };
+
+        let mut input = vec![0.0f64; 7];
+        input[0] = 1.0;
+        let mut signal = signal::from_samples_fixed::<_, 1>(input).flanger(params, 1.0);
+
+        let output: Vec<f64> = std::iter::from_fn(|| signal.next())
+            .map(|f| f.into_array()[0])
+            .collect();
+        // The echoes ring until they are 60 dB down.
+        let mut expected = vec![0.0; 29];
+        for k in 0..14 {
+            expected[2 + 2 * k] = 0.5f64.powi(k as i32);
+        }
+        assert_eq!(output, expected);
+    }
+}
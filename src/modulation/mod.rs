@@ -0,0 +1,157 @@
+//! Modulation effects: chorus, flanger, phaser, tremolo and vibrato.
+//!
+//! Each effect is driven by an [`Lfo`] per channel. The phase of each
+//! channel's [`Lfo`] is offset by a `spread` parameter, in cycles, which
+//! widens the stereo image.
+
+mod chorus;
+mod phaser;
+mod tremolo;
+
+pub use self::chorus::{Chorus, ChorusParams, Flanger, FlangerParams};
+pub use self::phaser::{Phaser, PhaserParams};
+pub use self::tremolo::{Tremolo, TremoloParams, Vibrato, VibratoParams};
+
+use std::f64::consts::PI;
+
+use crate::frame::Fixed;
+use crate::signal::Signal;
+
+/// The shape of an [`Lfo`] waveform. Each shape starts at 0 and rises, like a
+/// sine.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Shape {
+    Sine,
+    Triangle,
+    Square,
+    /// A rising sawtooth.
+    Saw,
+    /// A falling sawtooth.
+    InverseSaw,
+}
+
+impl Shape {
+    /// Evaluates this shape at a phase in cycles, returning a value in the
+    /// interval [-1.0, 1.0].
+    pub fn eval(&self, phase: f64) -> f64 {
+        let phase = phase.rem_euclid(1.0);
+
+        match self {
+            Shape::Sine => (2.0 * PI * phase).sin(),
+            Shape::Triangle => 1.0 - 4.0 * ((phase + 0.25).fract() - 0.5).abs(),
+            Shape::Square if phase < 0.5 => 1.0,
+            Shape::Square => -1.0,
+            Shape::Saw => 2.0 * (phase + 0.5).fract() - 1.0,
+            Shape::InverseSaw => 1.0 - 2.0 * (phase + 0.5).fract(),
+        }
+    }
+}
+
+/// A low frequency oscillator, which yields values in the interval
+/// [-1.0, 1.0].
+///
+/// ```
+/// use sampara::{Signal, Fixed};
+/// use sampara::modulation::{Lfo, Shape};
+///
+/// fn main() {
+///     let mut lfo = Lfo::new(Shape::Triangle, 1.0, 4.0);
+///
+///     assert_eq!(lfo.next(), Some(Fixed::from([0.0])));
+///     assert_eq!(lfo.next(), Some(Fixed::from([1.0])));
+///     assert_eq!(lfo.next(), Some(Fixed::from([0.0])));
+///     assert_eq!(lfo.next(), Some(Fixed::from([-1.0])));
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Lfo {
+    shape: Shape,
+    frequency: f64,
+    sample_rate: f64,
+    // The current phase, in cycles.
+    phase: f64,
+}
+
+impl Lfo {
+    /// Creates a new [`Lfo`] at a frequency in Hz, starting at a phase of 0.
+    pub fn new(shape: Shape, frequency: f64, sample_rate: f64) -> Self {
+        Self {
+            shape,
+            frequency,
+            sample_rate,
+            phase: 0.0,
+        }
+    }
+
+    /// Starts this [`Lfo`] at a phase, in cycles.
+    pub fn with_phase(mut self, phase: f64) -> Self {
+        self.phase = phase.rem_euclid(1.0);
+        self
+    }
+
+    pub fn shape(&self) -> Shape {
+        self.shape
+    }
+
+    pub fn set_shape(&mut self, shape: Shape) {
+        self.shape = shape;
+    }
+
+    pub fn frequency(&self) -> f64 {
+        self.frequency
+    }
+
+    /// Sets a new frequency, in Hz, while keeping the current phase.
+    pub fn set_frequency(&mut self, frequency: f64) {
+        self.frequency = frequency;
+    }
+
+    /// The current phase, in cycles.
+    pub fn phase(&self) -> f64 {
+        self.phase
+    }
+
+    /// Returns the current value, and advances the phase by one sample.
+    pub fn tick(&mut self) -> f64 {
+        let value = self.shape.eval(self.phase);
+        self.phase = (self.phase + self.frequency / self.sample_rate).rem_euclid(1.0);
+        value
+    }
+}
+
+impl Signal for Lfo {
+    type Frame = Fixed<f64, 1>;
+
+    fn next(&mut self) -> Option<Self::Frame> {
+        Some(Fixed::from([self.tick()]))
+    }
+
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+/// Creates the [`Lfo`] for a channel, with its phase offset by `spread`
+/// cycles per channel.
+fn channel_lfo(shape: Shape, frequency: f64, sample_rate: f64, spread: f64, channel: usize) -> Lfo {
+    Lfo::new(shape, frequency, sample_rate).with_phase(spread * channel as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shapes() {
+        let phases = [0.0, 0.25, 0.5, 0.75];
+
+        let eval = |shape: Shape| phases.map(|p| shape.eval(p));
+        let round = |values: [f64; 4]| values.map(|x| (x * 1e9).round() / 1e9);
+
+        assert_eq!(round(eval(Shape::Sine)), [0.0, 1.0, 0.0, -1.0]);
+        assert_eq!(eval(Shape::Triangle), [0.0, 1.0, 0.0, -1.0]);
+        assert_eq!(eval(Shape::Square), [1.0, 1.0, -1.0, -1.0]);
+        assert_eq!(eval(Shape::Saw), [0.0, 0.5, -1.0, -0.5]);
+        assert_eq!(eval(Shape::InverseSaw), [0.0, -0.5, 1.0, 0.5]);
+    }
+}
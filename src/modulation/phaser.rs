@@ -0,0 +1,211 @@
+use crate::biquad::{Biquad, Coefficients};
+use crate::frame::Frame;
+use crate::modulation::{Lfo, Shape, channel_lfo};
+use crate::sample::{FloatSample, FromSample, Sample};
+use crate::signal::{ExactSizeSignal, Signal};
+
+/// The parameters of a [`Phaser`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PhaserParams {
+    /// The number of first-order allpass stages. Each pair of stages adds a
+    /// notch to the frequency response.
+    pub stages: usize,
+
+    /// The frequency of the modulation, in Hz.
+    pub rate: f64,
+
+    /// The lowest frequency of the sweep, in Hz.
+    pub min_frequency: f64,
+
+    /// The highest frequency of the sweep, in Hz.
+    pub max_frequency: f64,
+
+    /// The proportion of the output of the stages that is fed back into them,
+    /// from -1 to 1. Higher values make the notches sharper.
+    pub feedback: f64,
+
+    /// The proportion of the phase shifted signal in the output, from 0 (dry)
+    /// to 1 (wet). A mix of 0.5 gives the deepest notches.
+    pub mix: f64,
+
+    /// The phase offset between the modulation of adjacent channels, in
+    /// cycles.
+    pub spread: f64,
+}
+
+impl Default for PhaserParams {
+    fn default() -> Self {
+        Self {
+            stages: 4,
+            rate: 0.5,
+            min_frequency: 200.0,
+            max_frequency: 2000.0,
+            feedback: 0.0,
+            mix: 0.5,
+            spread: 0.25,
+        }
+    }
+}
+
+/// The state of one channel of a [`Phaser`].
+struct PhaserChannel<T>
+where
+    T: FloatSample,
+{
+    stages: Vec<Biquad<T>>,
+    lfo: Lfo,
+    // The last output of the stages, for feedback.
+    last: T,
+}
+
+/// A [`Signal`] that mixes an underlying [`Signal`] with a phase shifted copy
+/// of itself, from a chain of allpass filters whose frequency is swept. This
+/// creates notches that move through the spectrum.
+///
+/// This is created by [`Signal::phaser`].
+pub struct Phaser<S>
+where
+    S: Signal,
+    <S::Frame as Frame>::Sample: FloatSample,
+{
+    signal: S,
+    params: PhaserParams,
+    sample_rate: f64,
+    channels: Vec<PhaserChannel<<S::Frame as Frame>::Sample>>,
+}
+
+impl<S> Phaser<S>
+where
+    S: Signal,
+    <S::Frame as Frame>::Sample: FloatSample,
+{
+    pub(crate) fn new(signal: S, params: PhaserParams, sample_rate: f64) -> Self {
+        Self {
+            signal,
+            params,
+            sample_rate,
+            channels: Vec::new(),
+        }
+    }
+
+    pub fn params(&self) -> &PhaserParams {
+        &self.params
+    }
+
+    /// Sets new parameters. If the number of stages changes, the filters are
+    /// cleared.
+    pub fn set_params(&mut self, params: PhaserParams) {
+        if params.stages != self.params.stages {
+            self.channels.clear();
+        }
+
+        for channel in self.channels.iter_mut() {
+            channel.lfo.set_frequency(params.rate);
+        }
+
+        self.params = params;
+    }
+}
+
+impl<S> Signal for Phaser<S>
+where
+    S: Signal,
+    <S::Frame as Frame>::Sample: FloatSample,
+{
+    type Frame = S::Frame;
+
+    fn next(&mut self) -> Option<Self::Frame> {
+        let mut frame = self.signal.next()?;
+
+        let PhaserParams {
+            stages,
+            rate,
+            min_frequency,
+            max_frequency,
+            feedback,
+            mix,
+            spread,
+        } = self.params;
+
+        if self.channels.len() != frame.len() {
+            let coeffs = Coefficients::first_order_allpass(min_frequency, self.sample_rate);
+
+            self.channels = (0..frame.len())
+                .map(|c| PhaserChannel {
+                    stages: vec![Biquad::new(coeffs); stages],
+                    lfo: channel_lfo(Shape::Sine, rate, self.sample_rate, spread, c),
+                    last: Sample::EQUILIBRIUM,
+                })
+                .collect();
+        }
+
+        for (sample, channel) in frame.iter_mut().zip(self.channels.iter_mut()) {
+            // The sweep is exponential, so that it spends as long in each
+            // octave.
+            let position = 0.5 * (channel.lfo.tick() + 1.0);
+            let frequency = min_frequency * (max_frequency / min_frequency).powf(position);
+            let coeffs = Coefficients::first_order_allpass(frequency, self.sample_rate);
+
+            let input = *sample + channel.last * FromSample::from_sample(feedback);
+            let wet = channel.stages.iter_mut().fold(input, |x, stage| {
+                stage.set_coefficients(coeffs);
+                stage.process(x)
+            });
+            channel.last = wet;
+
+            *sample =
+                *sample * FromSample::from_sample(1.0 - mix) + wet * FromSample::from_sample(mix);
+        }
+
+        Some(frame)
+    }
+
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        self.signal.len_hint()
+    }
+}
+
+impl<S> ExactSizeSignal for Phaser<S>
+where
+    S: ExactSizeSignal,
+    <S::Frame as Frame>::Sample: FloatSample,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::signal;
+
+    #[test]
+    fn notch() {
+        // With a fixed sweep, two stages shift the phase by 180 degrees at
+        // their frequency, which an even mix cancels.
+        let sample_rate = 48000.0;
+        let params = PhaserParams {
+            stages: 2,
+            rate: 0.0,
+            min_frequency: 1000.0,
+            max_frequency: 1000.0,
+            feedback: 0.0,
+            mix: 0.5,
+            spread: 0.0,
+        };
+
+        let tone = |freq: f64| {
+            let input: Vec<f64> = (0..4800)
+                .map(|i| (2.0 * core::f64::consts::PI * freq * i as f64 / sample_rate).sin())
+                .collect();
+            let mut signal = signal::from_samples_fixed::<_, 1>(input).phaser(params, sample_rate);
+
+            std::iter::from_fn(|| signal.next())
+                .skip(2400)
+                .map(|f| f.into_array()[0].abs())
+                .fold(0.0, f64::max)
+        };
+
+        assert!(tone(1000.0) < 1e-3);
+        assert!(tone(50.0) > 0.95);
+    }
+}
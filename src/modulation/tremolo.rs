@@ -0,0 +1,323 @@
+use crate::delay::DelayLine;
+use crate::frame::Frame;
+use crate::modulation::{Lfo, Shape, channel_lfo};
+use crate::sample::{FloatSample, FromSample, Sample};
+use crate::signal::{ExactSizeSignal, Signal, len_hint_with_tail};
+
+/// The parameters of a [`Tremolo`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TremoloParams {
+    /// The shape of the modulation.
+    pub shape: Shape,
+
+    /// The frequency of the modulation, in Hz.
+    pub rate: f64,
+
+    /// How far the gain dips, from 0 (no change) to 1 (down to silence).
+    pub depth: f64,
+
+    /// The phase offset between the modulation of adjacent channels, in
+    /// cycles. A spread of 0.5 on a stereo signal is an auto-panner.
+    pub spread: f64,
+}
+
+impl Default for TremoloParams {
+    fn default() -> Self {
+        Self {
+            shape: Shape::Sine,
+            rate: 5.0,
+            depth: 0.5,
+            spread: 0.0,
+        }
+    }
+}
+
+/// A [`Signal`] that modulates the gain of an underlying [`Signal`].
+///
+/// This is created by [`Signal::tremolo`].
+pub struct Tremolo<S>
+where
+    S: Signal,
+{
+    signal: S,
+    params: TremoloParams,
+    sample_rate: f64,
+    lfos: Vec<Lfo>,
+}
+
+impl<S> Tremolo<S>
+where
+    S: Signal,
+    <S::Frame as Frame>::Sample: FloatSample,
+{
+    pub(crate) fn new(signal: S, params: TremoloParams, sample_rate: f64) -> Self {
+        Self {
+            signal,
+            params,
+            sample_rate,
+            lfos: Vec::new(),
+        }
+    }
+
+    pub fn params(&self) -> &TremoloParams {
+        &self.params
+    }
+
+    pub fn set_params(&mut self, params: TremoloParams) {
+        for lfo in self.lfos.iter_mut() {
+            lfo.set_shape(params.shape);
+            lfo.set_frequency(params.rate);
+        }
+
+        self.params = params;
+    }
+}
+
+impl<S> Signal for Tremolo<S>
+where
+    S: Signal,
+    <S::Frame as Frame>::Sample: FloatSample,
+{
+    type Frame = S::Frame;
+
+    fn next(&mut self) -> Option<Self::Frame> {
+        let mut frame = self.signal.next()?;
+
+        let TremoloParams {
+            shape,
+            rate,
+            depth,
+            spread,
+        } = self.params;
+
+        if self.lfos.len() != frame.len() {
+            self.lfos = (0..frame.len())
+                .map(|c| channel_lfo(shape, rate, self.sample_rate, spread, c))
+                .collect();
+        }
+
+        for (sample, lfo) in frame.iter_mut().zip(self.lfos.iter_mut()) {
+            // The gain is 1 at the top of the waveform, and `1 - depth` at the
+            // bottom.
+            let gain = 1.0 - depth * 0.5 * (1.0 - lfo.tick());
+            *sample = *sample * FromSample::from_sample(gain);
+        }
+
+        Some(frame)
+    }
+
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        self.signal.len_hint()
+    }
+}
+
+impl<S> ExactSizeSignal for Tremolo<S>
+where
+    S: ExactSizeSignal,
+    <S::Frame as Frame>::Sample: FloatSample,
+{
+}
+
+/// The parameters of a [`Vibrato`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct VibratoParams {
+    /// The shape of the modulation.
+    pub shape: Shape,
+
+    /// The frequency of the modulation, in Hz.
+    pub rate: f64,
+
+    /// How far the delay is swept, in seconds. The pitch deviation grows with
+    /// both the depth and the rate.
+    pub depth: f64,
+
+    /// The phase offset between the modulation of adjacent channels, in
+    /// cycles.
+    pub spread: f64,
+}
+
+impl Default for VibratoParams {
+    fn default() -> Self {
+        Self {
+            shape: Shape::Sine,
+            rate: 5.0,
+            depth: 0.002,
+            spread: 0.0,
+        }
+    }
+}
+
+/// The state of one channel of a [`Vibrato`].
+struct VibratoChannel<T> {
+    line: DelayLine<T>,
+    lfo: Lfo,
+}
+
+/// A [`Signal`] that modulates the pitch of an underlying [`Signal`], by
+/// sweeping its delay.
+///
+/// The signal is delayed by `depth / 2` seconds on average, and the output is
+/// extended by `depth` seconds, so that the delayed signal is flushed once the
+/// input ends.
+///
+/// This is created by [`Signal::vibrato`].
+pub struct Vibrato<S>
+where
+    S: Signal,
+{
+    signal: S,
+    params: VibratoParams,
+    sample_rate: f64,
+    channels: Vec<VibratoChannel<<S::Frame as Frame>::Sample>>,
+    // A frame of equilibrium, used to flush the delay once the input ends.
+    silence: Option<S::Frame>,
+    // The number of frames left to output, once the input has run out.
+    tail: Option<usize>,
+}
+
+impl<S> Vibrato<S>
+where
+    S: Signal,
+    <S::Frame as Frame>::Sample: FloatSample,
+{
+    pub(crate) fn new(signal: S, params: VibratoParams, sample_rate: f64) -> Self {
+        Self {
+            signal,
+            params,
+            sample_rate,
+            channels: Vec::new(),
+            silence: None,
+            tail: None,
+        }
+    }
+
+    /// The number of [`Frame`]s that the delayed signal rings for once the
+    /// input ends.
+    fn tail_len(&self) -> usize {
+        (self.params.depth * self.sample_rate).ceil() as usize
+    }
+
+    pub fn params(&self) -> &VibratoParams {
+        &self.params
+    }
+
+    /// Sets new parameters. If the depth changes, the delay lines are
+    /// cleared.
+    pub fn set_params(&mut self, params: VibratoParams) {
+        if params.depth != self.params.depth {
+            self.channels.clear();
+        }
+
+        for channel in self.channels.iter_mut() {
+            channel.lfo.set_shape(params.shape);
+            channel.lfo.set_frequency(params.rate);
+        }
+
+        self.params = params;
+    }
+}
+
+impl<S> Signal for Vibrato<S>
+where
+    S: Signal,
+    <S::Frame as Frame>::Sample: FloatSample,
+{
+    type Frame = S::Frame;
+
+    fn next(&mut self) -> Option<Self::Frame> {
+        let mut frame = match self.tail {
+            Some(0) => return None,
+            Some(ref mut tail) => {
+                *tail -= 1;
+                self.silence.clone()?
+            }
+            None => match self.signal.next() {
+                Some(frame) => {
+                    if self.silence.is_none() {
+                        let mut silence = frame.clone();
+                        silence
+                            .iter_mut()
+                            .for_each(|s| *s = <S::Frame as Frame>::Sample::EQUILIBRIUM);
+                        self.silence = Some(silence);
+                    }
+
+                    frame
+                }
+                None => {
+                    self.tail = Some(self.tail_len());
+                    return self.next();
+                }
+            },
+        };
+
+        let VibratoParams {
+            shape,
+            rate,
+            depth,
+            spread,
+        } = self.params;
+
+        if self.channels.len() != frame.len() {
+            let max_delay = (depth * self.sample_rate).ceil() as usize + 1;
+
+            self.channels = (0..frame.len())
+                .map(|c| VibratoChannel {
+                    line: DelayLine::new(max_delay),
+                    lfo: channel_lfo(shape, rate, self.sample_rate, spread, c),
+                })
+                .collect();
+        }
+
+        for (sample, channel) in frame.iter_mut().zip(self.channels.iter_mut()) {
+            channel.line.push(*sample);
+
+            let time = depth * 0.5 * (channel.lfo.tick() + 1.0);
+            *sample = channel.line.read_lagrange(time * self.sample_rate);
+        }
+
+        Some(frame)
+    }
+
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        let (input, tail) = match self.tail {
+            Some(tail) => ((0, Some(0)), tail),
+            None => (self.signal.len_hint(), self.tail_len()),
+        };
+
+        len_hint_with_tail(input, tail, self.silence.is_some())
+    }
+}
+
+impl<S> ExactSizeSignal for Vibrato<S>
+where
+    S: ExactSizeSignal,
+    <S::Frame as Frame>::Sample: FloatSample,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::signal;
+
+    #[test]
+    fn tremolo_spread() {
+        // A square wave at a quarter of the sample rate, with the channels in
+        // opposite phase.
+        let params = TremoloParams {
+            shape: Shape::Square,
+            rate: 1.0,
+            depth: 1.0,
+            spread: 0.5,
+        };
+
+        let input = vec![1.0f32; 8];
+        let mut signal = signal::from_samples_fixed::<_, 2>(input).tremolo(params, 4.0);
+
+        assert_eq!(signal.next(), Some([1.0, 0.0].into()));
+        assert_eq!(signal.next(), Some([1.0, 0.0].into()));
+        assert_eq!(signal.next(), Some([0.0, 1.0].into()));
+        assert_eq!(signal.next(), Some([0.0, 1.0].into()));
+    }
+}
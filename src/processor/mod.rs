@@ -0,0 +1,250 @@
+//! Block-based, in-place processing of [`Frame`]s.
+//!
+//! Where a [`Signal`] pulls one [`Frame`] at a time, a [`Processor`] is handed
+//! a whole block of [`Frame`]s by its caller, and overwrites them in place.
+//! This matches the shape of real-time audio callbacks. None of the
+//! processing paths in this module allocate.
+
+use core::marker::PhantomData;
+
+use crate::frame::Frame;
+use crate::signal::Signal;
+
+/// Types that process blocks of [`Frame`]s in place.
+///
+/// ```
+/// use sampara::processor::{self, Processor};
+///
+/// fn main() {
+///     let mut gain = processor::from_fn(|frame: &mut sampara::Fixed<f32, 2>| {
+///         for s in sampara::Frame::iter_mut(frame) {
+///             *s *= 0.5;
+///         }
+///     });
+///
+///     gain.prepare(48000.0, 2);
+///
+///     let mut block = [[1.0, -1.0].into(), [0.5, -0.5].into()];
+///     gain.process(&mut block);
+///
+///     assert_eq!(block, [[0.5, -0.5].into(), [0.25, -0.25].into()]);
+///     assert_eq!(gain.latency(), 0);
+/// }
+/// ```
+pub trait Processor {
+    /// The [`Frame`] type processed by this [`Processor`].
+    type Frame: Frame;
+
+    /// Prepares [`Self`] for processing at a given sample rate, with blocks
+    /// of at most `max_block` [`Frame`]s. This is called before processing
+    /// begins, outside of any real-time context, and is the only place where
+    /// implementors may allocate.
+    ///
+    /// The default implementation does nothing.
+    fn prepare(&mut self, sample_rate: f64, max_block: usize) {
+        let _ = (sample_rate, max_block);
+    }
+
+    /// Processes a block of [`Frame`]s in place.
+    fn process(&mut self, block: &mut [Self::Frame]);
+
+    /// Clears any internal state, as if no [`Frame`]s had been processed.
+    fn reset(&mut self);
+
+    /// Returns the delay, in [`Frame`]s, that this [`Processor`] introduces
+    /// between its input and output.
+    ///
+    /// The default implementation returns 0.
+    fn latency(&self) -> usize {
+        0
+    }
+
+    /// Returns the number of [`Frame`]s that the output of this [`Processor`]
+    /// keeps ringing for after its input falls silent, such as the decay of
+    /// a reverb.
+    ///
+    /// The default implementation returns 0.
+    fn tail(&self) -> usize {
+        0
+    }
+
+    /// Creates a new [`Processor`] that processes each block with [`Self`],
+    /// and then with another [`Processor`]. The latency and tail of the
+    /// resulting [`Processor`] are the sums of those of both.
+    ///
+    /// ```
+    /// use sampara::{Fixed, Frame};
+    /// use sampara::processor::{self, Processor};
+    ///
+    /// fn main() {
+    ///     let double = processor::from_fn(|f: &mut Fixed<i32, 1>| *f.get_mut(0).unwrap() *= 2);
+    ///     let add_one = processor::from_fn(|f: &mut Fixed<i32, 1>| *f.get_mut(0).unwrap() += 1);
+    ///
+    ///     let mut chain = double.chain(add_one);
+    ///
+    ///     let mut block = [[1].into(), [2].into()];
+    ///     chain.process(&mut block);
+    ///     assert_eq!(block, [[3].into(), [5].into()]);
+    /// }
+    /// ```
+    fn chain<P>(self, other: P) -> Chain<Self, P>
+    where
+        Self: Sized,
+        P: Processor<Frame = Self::Frame>,
+    {
+        Chain(self, other)
+    }
+}
+
+impl<P> Processor for &mut P
+where
+    P: Processor + ?Sized,
+{
+    type Frame = P::Frame;
+
+    #[inline]
+    fn prepare(&mut self, sample_rate: f64, max_block: usize) {
+        (**self).prepare(sample_rate, max_block)
+    }
+
+    #[inline]
+    fn process(&mut self, block: &mut [Self::Frame]) {
+        (**self).process(block)
+    }
+
+    #[inline]
+    fn reset(&mut self) {
+        (**self).reset()
+    }
+
+    #[inline]
+    fn latency(&self) -> usize {
+        (**self).latency()
+    }
+
+    #[inline]
+    fn tail(&self) -> usize {
+        (**self).tail()
+    }
+}
+
+/// A [`Processor`] that runs two [`Processor`]s in series.
+#[derive(Clone, Debug)]
+pub struct Chain<A, B>(A, B);
+
+impl<A, B> Processor for Chain<A, B>
+where
+    A: Processor,
+    B: Processor<Frame = A::Frame>,
+{
+    type Frame = A::Frame;
+
+    fn prepare(&mut self, sample_rate: f64, max_block: usize) {
+        self.0.prepare(sample_rate, max_block);
+        self.1.prepare(sample_rate, max_block);
+    }
+
+    #[inline]
+    fn process(&mut self, block: &mut [Self::Frame]) {
+        self.0.process(block);
+        self.1.process(block);
+    }
+
+    fn reset(&mut self) {
+        self.0.reset();
+        self.1.reset();
+    }
+
+    fn latency(&self) -> usize {
+        self.0.latency() + self.1.latency()
+    }
+
+    fn tail(&self) -> usize {
+        self.0.tail() + self.1.tail()
+    }
+}
+
+/// A stateless [`Processor`] that calls a closure on each [`Frame`].
+#[derive(Clone)]
+pub struct FromFn<F, G>(G, PhantomData<F>)
+where
+    F: Frame,
+    G: FnMut(&mut F);
+
+impl<F, G> Processor for FromFn<F, G>
+where
+    F: Frame,
+    G: FnMut(&mut F),
+{
+    type Frame = F;
+
+    #[inline]
+    fn process(&mut self, block: &mut [Self::Frame]) {
+        block.iter_mut().for_each(&mut self.0);
+    }
+
+    fn reset(&mut self) {}
+}
+
+/// Creates a new stateless [`Processor`] that calls a given closure on each
+/// [`Frame`] of each block.
+pub fn from_fn<F, G>(func: G) -> FromFn<F, G>
+where
+    F: Frame,
+    G: FnMut(&mut F),
+{
+    FromFn(func, PhantomData)
+}
+
+/// A [`Processor`] that overwrites each block with [`Frame`]s pulled from a
+/// [`Signal`].
+pub struct FromSignal<S>(S)
+where
+    S: Signal;
+
+impl<S> Processor for FromSignal<S>
+where
+    S: Signal,
+{
+    type Frame = S::Frame;
+
+    #[inline]
+    fn process(&mut self, block: &mut [Self::Frame]) {
+        for frame in block.iter_mut() {
+            *frame = self.0.sig_next();
+        }
+    }
+
+    /// A [`Signal`] cannot be rewound, so this does nothing.
+    fn reset(&mut self) {}
+}
+
+/// Creates a new [`Processor`] that ignores the contents of each block, and
+/// overwrites them with [`Frame`]s pulled from a [`Signal`]. Once the
+/// [`Signal`] is exhausted, the block is filled with equilibrium [`Frame`]s,
+/// as with [`Signal::sig_next`].
+///
+/// This lets a [`Signal`] be used as a generator inside a block-based audio
+/// callback.
+///
+/// ```
+/// use sampara::signal;
+/// use sampara::processor::{self, Processor};
+///
+/// fn main() {
+///     let mut source = processor::from_signal(signal::from_samples_fixed::<_, 1>(1..=3));
+///
+///     let mut block = [[0].into(); 2];
+///     source.process(&mut block);
+///     assert_eq!(block, [[1].into(), [2].into()]);
+///
+///     source.process(&mut block);
+///     assert_eq!(block, [[3].into(), [0].into()]);
+/// }
+/// ```
+pub fn from_signal<S>(signal: S) -> FromSignal<S>
+where
+    S: Signal,
+{
+    FromSignal(signal)
+}
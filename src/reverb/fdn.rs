@@ -0,0 +1,377 @@
+use crate::biquad::{Biquad, Coefficients};
+use crate::delay::DelayLine;
+use crate::reverb::{ReverbProcessor, next_prime};
+
+/// The feedback matrix of an [`Fdn`], which mixes the outputs of the delay
+/// lines back into their inputs. Both matrices are orthogonal, so that no
+/// energy is lost or gained by mixing.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Mixing {
+    /// The normalized Hadamard matrix, which mixes every line into every
+    /// other line equally. The number of lines must be a power of two.
+    Hadamard,
+
+    /// The Householder reflection `I - 2/N`, which is cheaper to compute but
+    /// mixes less densely.
+    Householder,
+}
+
+impl Mixing {
+    /// Mixes the values in place.
+    fn apply(&self, values: &mut [f64]) {
+        let n = values.len();
+
+        match self {
+            Mixing::Hadamard => {
+                // The fast Walsh-Hadamard transform.
+                let mut half = 1;
+                while half < n {
+                    for i in (0..n).step_by(2 * half) {
+                        for j in i..i + half {
+                            let (a, b) = (values[j], values[j + half]);
+                            values[j] = a + b;
+                            values[j + half] = a - b;
+                        }
+                    }
+                    half *= 2;
+                }
+
+                let scale = (n as f64).sqrt().recip();
+                values.iter_mut().for_each(|x| *x *= scale);
+            }
+            Mixing::Householder => {
+                let sum = 2.0 * values.iter().sum::<f64>() / n as f64;
+                values.iter_mut().for_each(|x| *x -= sum);
+            }
+        }
+    }
+}
+
+/// The parameters of an [`Fdn`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FdnParams {
+    /// The number of delay lines.
+    pub lines: usize,
+
+    /// The feedback matrix.
+    pub mixing: Mixing,
+
+    /// The shortest delay line, in seconds.
+    pub min_delay: f64,
+
+    /// The longest delay line, in seconds.
+    pub max_delay: f64,
+
+    /// The time taken for the reverb to decay by 60 dB at low frequencies,
+    /// in seconds.
+    pub rt60: f64,
+
+    /// The ratio of the decay time at high frequencies to [`Self::rt60`].
+    /// Values below 1 make the reverb darker as it decays.
+    pub damping: f64,
+
+    /// The frequency above which [`Self::damping`] applies, in Hz.
+    pub crossover: f64,
+
+    /// The gain of the reverb.
+    pub wet: f64,
+
+    /// The gain of the input signal.
+    pub dry: f64,
+}
+
+impl Default for FdnParams {
+    fn default() -> Self {
+        Self {
+            lines: 8,
+            mixing: Mixing::Hadamard,
+            min_delay: 0.02,
+            max_delay: 0.06,
+            rt60: 2.0,
+            damping: 0.5,
+            crossover: 4000.0,
+            wet: 0.3,
+            dry: 1.0,
+        }
+    }
+}
+
+/// A delay line of an [`Fdn`], with its absorption filter.
+#[derive(Clone, Debug)]
+struct Line {
+    line: DelayLine<f64>,
+    len: usize,
+    absorption: Biquad<f64>,
+}
+
+/// A feedback delay network reverb.
+///
+/// The input channels are mixed to mono and fed into each delay line, with
+/// alternating signs. The outputs of the lines are filtered, so that they
+/// decay at the decay time, and then mixed back into the lines by an
+/// orthogonal matrix. Each output channel takes a different combination of
+/// the lines, which decorrelates the channels.
+///
+/// ```
+/// use sampara::{signal, Signal};
+/// use sampara::reverb::{Fdn, FdnParams, Mixing};
+///
+/// fn main() {
+///     let params = FdnParams {
+///         lines: 6,
+///         mixing: Mixing::Householder,
+///         rt60: 0.5,
+///         ..Default::default()
+///     };
+///     let fdn = Fdn::new(params, 48000.0);
+///     assert_eq!(fdn.delays().len(), 6);
+///
+///     let mut input = vec![0.0f64; 2];
+///     input[0] = 1.0;
+///     let mut signal = signal::from_samples_fixed::<_, 2>(input).reverb(fdn);
+///
+///     // The output is extended by the decay time.
+///     assert_eq!(std::iter::from_fn(|| signal.next()).count(), 1 + 24000);
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Fdn {
+    params: FdnParams,
+    sample_rate: f64,
+    lines: Vec<Line>,
+    values: Vec<f64>,
+}
+
+impl Fdn {
+    /// Creates a new [`Fdn`] for a given sample rate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no lines, or if the mixing is
+    /// [`Mixing::Hadamard`] and the number of lines is not a power of two.
+    pub fn new(params: FdnParams, sample_rate: f64) -> Self {
+        let mut fdn = Self {
+            params,
+            sample_rate,
+            lines: Vec::new(),
+            values: Vec::new(),
+        };
+
+        fdn.set_params(params);
+        fdn
+    }
+
+    pub fn params(&self) -> &FdnParams {
+        &self.params
+    }
+
+    /// Sets new parameters. If the number or lengths of the delay lines
+    /// change, the reverb is reset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no lines, or if the mixing is
+    /// [`Mixing::Hadamard`] and the number of lines is not a power of two.
+    pub fn set_params(&mut self, params: FdnParams) {
+        assert!(params.lines > 0, "an FDN must have at least one line");
+        assert!(
+            params.mixing != Mixing::Hadamard || params.lines.is_power_of_two(),
+            "Hadamard mixing requires a power of two number of lines, not {}",
+            params.lines
+        );
+
+        let delays = self.compute_delays(&params);
+        if delays != self.delays() {
+            self.lines = delays
+                .iter()
+                .map(|&len| Line {
+                    line: DelayLine::new(len),
+                    len,
+                    absorption: Biquad::new(Coefficients {
+                        b0: 1.0,
+                        b1: 0.0,
+                        b2: 0.0,
+                        a1: 0.0,
+                        a2: 0.0,
+                    }),
+                })
+                .collect();
+        }
+
+        self.params = params;
+
+        for line in self.lines.iter_mut() {
+            line.absorption
+                .set_coefficients(Self::absorption(&params, line.len, self.sample_rate));
+        }
+    }
+
+    /// The lengths of the delay lines, in samples.
+    pub fn delays(&self) -> Vec<usize> {
+        self.lines.iter().map(|line| line.len).collect()
+    }
+
+    /// Spaces the delays exponentially between the shortest and longest, and
+    /// rounds them to distinct primes.
+    fn compute_delays(&self, params: &FdnParams) -> Vec<usize> {
+        let min = (params.min_delay * self.sample_rate).max(1.0);
+        let max = (params.max_delay * self.sample_rate).max(min);
+        let n = params.lines;
+
+        let mut delays: Vec<usize> = Vec::with_capacity(n);
+        for i in 0..n {
+            let t = if n > 1 {
+                i as f64 / (n - 1) as f64
+            } else {
+                0.0
+            };
+            let delay = (min * (max / min).powf(t)).round() as usize;
+            let floor = delays.last().map_or(0, |&d| d + 1);
+            delays.push(next_prime(delay.max(floor)));
+        }
+
+        delays
+    }
+
+    /// Returns the absorption filter of a line, which attenuates one pass
+    /// through the line so that it decays at the decay time.
+    fn absorption(params: &FdnParams, len: usize, sample_rate: f64) -> Coefficients<f64> {
+        // The attenuation in decibels of one pass, at low and high
+        // frequencies.
+        let seconds = len as f64 / sample_rate;
+        let low = -60.0 * seconds / params.rt60;
+        let high = -60.0 * seconds / (params.rt60 * params.damping);
+
+        let mut coeffs = Coefficients::high_shelf(params.crossover, high - low, sample_rate);
+        let gain = 10.0f64.powf(low / 20.0);
+        coeffs.b0 *= gain;
+        coeffs.b1 *= gain;
+        coeffs.b2 *= gain;
+
+        coeffs
+    }
+
+    /// The sign of a line in the input, or in an output channel, so that each
+    /// channel takes a different combination of the lines.
+    fn sign(line: usize, channel: usize) -> f64 {
+        // The rows of a Hadamard matrix, skipping the first.
+        if (line & (channel + 1)).count_ones().is_multiple_of(2) {
+            1.0
+        } else {
+            -1.0
+        }
+    }
+}
+
+impl ReverbProcessor for Fdn {
+    fn process(&mut self, input: &[f64], output: &mut [f64]) {
+        let n = self.lines.len();
+        let mono = input.iter().sum::<f64>();
+
+        self.values.clear();
+        self.values
+            .extend(self.lines.iter().map(|l| l.line.read(l.len - 1)));
+
+        let scale = (n as f64).sqrt().recip();
+        let FdnParams { wet, dry, .. } = self.params;
+
+        for (c, (y, &x)) in output.iter_mut().zip(input).enumerate() {
+            let reverb: f64 = self
+                .values
+                .iter()
+                .enumerate()
+                .map(|(i, v)| Self::sign(i, c) * v)
+                .sum();
+
+            *y = dry * x + wet * scale * reverb;
+        }
+
+        for (line, value) in self.lines.iter_mut().zip(self.values.iter_mut()) {
+            *value = line.absorption.process(*value);
+        }
+
+        self.params.mixing.apply(&mut self.values);
+
+        for (i, (line, &value)) in self.lines.iter_mut().zip(&self.values).enumerate() {
+            let sign = if i % 2 == 0 { 1.0 } else { -1.0 };
+            line.line.push(value + sign * scale * mono);
+        }
+    }
+
+    fn reset(&mut self) {
+        for line in self.lines.iter_mut() {
+            line.line.reset();
+            line.absorption.reset();
+        }
+    }
+
+    fn tail(&self) -> usize {
+        (self.params.rt60 * self.sample_rate).ceil() as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mixing_is_orthogonal() {
+        for mixing in [Mixing::Hadamard, Mixing::Householder] {
+            let mut values = [1.0, -2.0, 0.5, 3.0];
+            let energy: f64 = values.iter().map(|x| x * x).sum();
+
+            mixing.apply(&mut values);
+            let mixed: f64 = values.iter().map(|x| x * x).sum();
+
+            assert!((energy - mixed).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn decay_time() {
+        let params = FdnParams {
+            rt60: 0.5,
+            damping: 1.0,
+            dry: 0.0,
+            wet: 1.0,
+            ..Default::default()
+        };
+        let sample_rate = 8000.0;
+        let mut fdn = Fdn::new(params, sample_rate);
+
+        // The energy over a window, after an impulse.
+        let mut output = [0.0];
+        let mut energy = [0.0; 10];
+        for i in 0..8000 {
+            let x = if i == 0 { 1.0 } else { 0.0 };
+            fdn.process(&[x], &mut output);
+            energy[i / 800] += output[0] * output[0];
+        }
+
+        // Every 0.1 seconds, the energy falls by 12 dB.
+        for pair in energy[2..].windows(2) {
+            let db = 10.0 * (pair[1] / pair[0]).log10();
+            assert!((db - -12.0).abs() < 1.5, "{db}");
+        }
+    }
+
+    #[test]
+    fn deterministic() {
+        let run = || {
+            let mut fdn = Fdn::new(FdnParams::default(), 44100.0);
+            let mut output = [0.0, 0.0];
+            (0..1000)
+                .map(|i| {
+                    fdn.process(&[(i as f64 * 0.1).sin(), 0.0], &mut output);
+                    output
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let (a, b) = (run(), run());
+        assert_eq!(a, b);
+
+        // The stereo outputs are decorrelated.
+        assert!(a.iter().any(|[l, r]| (l - r).abs() > 1e-3));
+    }
+}
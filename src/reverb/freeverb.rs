@@ -0,0 +1,246 @@
+use crate::delay::DelayLine;
+use crate::reverb::ReverbProcessor;
+
+/// The comb filter delays of the left channel, in samples at 44.1 kHz.
+const COMBS: [usize; 8] = [1116, 1188, 1277, 1356, 1422, 1491, 1557, 1617];
+
+/// The allpass filter delays of the left channel, in samples at 44.1 kHz.
+const ALLPASSES: [usize; 4] = [556, 441, 341, 225];
+
+/// The extra delay of each channel after the first, in samples at 44.1 kHz.
+const STEREO_SPREAD: usize = 23;
+
+/// The gain applied to the input before the comb filters.
+const INPUT_GAIN: f64 = 0.015;
+
+/// The feedback of the allpass filters.
+const ALLPASS_FEEDBACK: f64 = 0.5;
+
+/// The parameters of a [`Freeverb`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FreeverbParams {
+    /// The size of the room, from 0 to 1, which sets the decay time.
+    pub room_size: f64,
+
+    /// How quickly high frequencies decay, from 0 to 1.
+    pub damping: f64,
+
+    /// The stereo width of the reverb, from 0 (mono) to 1.
+    pub width: f64,
+
+    /// The gain of the reverb.
+    pub wet: f64,
+
+    /// The gain of the input signal.
+    pub dry: f64,
+}
+
+impl Default for FreeverbParams {
+    fn default() -> Self {
+        Self {
+            room_size: 0.5,
+            damping: 0.5,
+            width: 1.0,
+            wet: 1.0,
+            dry: 1.0,
+        }
+    }
+}
+
+impl FreeverbParams {
+    /// The feedback of the comb filters.
+    fn feedback(&self) -> f64 {
+        self.room_size * 0.28 + 0.7
+    }
+}
+
+/// A lowpass feedback comb filter.
+#[derive(Clone, Debug)]
+struct Comb {
+    line: DelayLine<f64>,
+    len: usize,
+    // The state of the lowpass filter in the feedback path.
+    filtered: f64,
+}
+
+impl Comb {
+    fn new(len: usize) -> Self {
+        Self {
+            line: DelayLine::new(len),
+            len,
+            filtered: 0.0,
+        }
+    }
+
+    fn process(&mut self, input: f64, feedback: f64, damping: f64) -> f64 {
+        let output = self.line.read(self.len - 1);
+        self.filtered = output * (1.0 - damping) + self.filtered * damping;
+        self.line.push(input + self.filtered * feedback);
+        output
+    }
+}
+
+/// A Schroeder allpass filter, as approximated by Freeverb.
+#[derive(Clone, Debug)]
+struct Allpass {
+    line: DelayLine<f64>,
+    len: usize,
+}
+
+impl Allpass {
+    fn new(len: usize) -> Self {
+        Self {
+            line: DelayLine::new(len),
+            len,
+        }
+    }
+
+    fn process(&mut self, input: f64) -> f64 {
+        let delayed = self.line.read(self.len - 1);
+        self.line.push(input + delayed * ALLPASS_FEEDBACK);
+        delayed - input
+    }
+}
+
+/// The filters of one output channel of a [`Freeverb`].
+#[derive(Clone, Debug)]
+struct Channel {
+    combs: Vec<Comb>,
+    allpasses: Vec<Allpass>,
+}
+
+/// A Schroeder reverb, with the tuning of Jezar's Freeverb: eight parallel
+/// lowpass feedback comb filters, followed by four allpass filters in series.
+///
+/// The input channels are mixed to mono, and each output channel has its own
+/// filters with slightly longer delays, which decorrelates the channels. The
+/// [`tail`](ReverbProcessor::tail) is sized for a stereo output, so a third
+/// channel and beyond ring for slightly longer than it.
+///
+/// ```
+/// use sampara::{signal, Signal};
+/// use sampara::reverb::{Freeverb, FreeverbParams};
+///
+/// fn main() {
+///     let params = FreeverbParams { dry: 0.0, ..Default::default() };
+///     let reverb = Freeverb::new(params, 48000.0);
+///
+///     let mut input = vec![0.0f32; 4800];
+///     input[0] = 1.0;
+///
+///     let mut signal = signal::from_samples_fixed::<_, 1>(input).reverb(reverb);
+///     let output: Vec<f32> = std::iter::from_fn(|| signal.next())
+///         .map(|f| f.into_array()[0])
+///         .collect();
+///
+///     // The first echo arrives after the shortest comb delay, and then the
+///     // reverb rings on past the end of the input.
+///     assert!(output[..1200].iter().all(|&x| x == 0.0));
+///     assert!(output[4800..].iter().any(|&x| x != 0.0));
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Freeverb {
+    params: FreeverbParams,
+    sample_rate: f64,
+    channels: Vec<Channel>,
+    wet: Vec<f64>,
+}
+
+impl Freeverb {
+    /// Creates a new [`Freeverb`] for a given sample rate.
+    pub fn new(params: FreeverbParams, sample_rate: f64) -> Self {
+        Self {
+            params,
+            sample_rate,
+            channels: Vec::new(),
+            wet: Vec::new(),
+        }
+    }
+
+    pub fn params(&self) -> &FreeverbParams {
+        &self.params
+    }
+
+    /// Sets new parameters, while keeping the reverb ringing.
+    pub fn set_params(&mut self, params: FreeverbParams) {
+        self.params = params;
+    }
+
+    /// Scales a delay in samples at 44.1 kHz to the sample rate.
+    fn scale(&self, len: usize) -> usize {
+        ((len as f64 * self.sample_rate / 44100.0).round() as usize).max(1)
+    }
+
+    fn channel(&self, channel: usize) -> Channel {
+        let spread = channel * STEREO_SPREAD;
+
+        Channel {
+            combs: COMBS
+                .iter()
+                .map(|&len| Comb::new(self.scale(len + spread)))
+                .collect(),
+            allpasses: ALLPASSES
+                .iter()
+                .map(|&len| Allpass::new(self.scale(len + spread)))
+                .collect(),
+        }
+    }
+}
+
+impl ReverbProcessor for Freeverb {
+    fn process(&mut self, input: &[f64], output: &mut [f64]) {
+        if self.channels.len() != output.len() {
+            self.channels = (0..output.len()).map(|c| self.channel(c)).collect();
+        }
+
+        let mono = INPUT_GAIN * input.iter().sum::<f64>();
+        let feedback = self.params.feedback();
+        let damping = self.params.damping * 0.4;
+
+        self.wet.clear();
+        self.wet.extend(self.channels.iter_mut().map(|channel| {
+            let combs: f64 = channel
+                .combs
+                .iter_mut()
+                .map(|c| c.process(mono, feedback, damping))
+                .sum();
+
+            channel
+                .allpasses
+                .iter_mut()
+                .fold(combs, |x, allpass| allpass.process(x))
+        }));
+
+        // Each channel is mixed with the next one, according to the width.
+        let FreeverbParams {
+            width, wet, dry, ..
+        } = self.params;
+        let direct = wet * (0.5 + width / 2.0);
+        let cross = wet * (1.0 - width) / 2.0;
+        let channels = self.wet.len();
+
+        for (c, (y, &x)) in output.iter_mut().zip(input).enumerate() {
+            let other = self.wet[(c + 1) % channels];
+            *y = dry * x + direct * self.wet[c] + cross * other;
+        }
+    }
+
+    fn reset(&mut self) {
+        self.channels.clear();
+    }
+
+    fn tail(&self) -> usize {
+        // The longest comb filter decays by 20 * log10(feedback) dB on each
+        // pass, ignoring the damping. The channels in use are not known
+        // before the first frame, so this assumes a stereo output.
+        let passes = -3.0 / self.params.feedback().log10();
+        let longest = self.scale(COMBS[7] + STEREO_SPREAD);
+        let allpasses: usize = ALLPASSES
+            .iter()
+            .map(|&len| self.scale(len + STEREO_SPREAD))
+            .sum();
+
+        (passes * longest as f64).ceil() as usize + allpasses
+    }
+}
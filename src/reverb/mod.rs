@@ -0,0 +1,320 @@
+//! Algorithmic reverbs: a Freeverb-style Schroeder reverb and a feedback
+//! delay network.
+//!
+//! Each reverb implements [`ReverbProcessor`], and is applied to a [`Signal`]
+//! with [`Signal::reverb`], or turned into a [`Processor`] with
+//! [`ReverbProcessor::into_processor`]. Reverbs are deterministic, and their delays scale
+//! with the sample rate.
+
+mod fdn;
+mod freeverb;
+
+pub use self::fdn::{Fdn, FdnParams, Mixing};
+pub use self::freeverb::{Freeverb, FreeverbParams};
+
+use std::marker::PhantomData;
+
+use crate::frame::Frame;
+use crate::processor::Processor;
+use crate::sample::to_f64;
+use crate::sample::{FloatSample, FromSample, Sample};
+use crate::signal::{ExactSizeSignal, Signal, len_hint_with_tail};
+
+/// Types that compute the output of a reverb for each channel of a [`Signal`],
+/// one [`Frame`] at a time.
+pub trait ReverbProcessor {
+    /// Computes the output samples, which include both the dry and the wet
+    /// signal, from the input samples. Both slices have the same length,
+    /// which is the number of channels of the processed [`Signal`].
+    fn process(&mut self, input: &[f64], output: &mut [f64]);
+
+    /// Clears all internal state.
+    fn reset(&mut self);
+
+    /// The number of [`Frame`]s that the reverb keeps ringing for after its
+    /// input ends, until it has decayed by 60 dB.
+    fn tail(&self) -> usize {
+        0
+    }
+
+    /// Creates a new [`Processor`] that applies [`Self`] to blocks of
+    /// [`Frame`]s.
+    ///
+    /// ```
+    /// use sampara::Fixed;
+    /// use sampara::processor::Processor;
+    /// use sampara::reverb::{Freeverb, FreeverbParams, ReverbProcessor};
+    ///
+    /// fn main() {
+    ///     let freeverb = Freeverb::new(FreeverbParams::default(), 48000.0);
+    ///     let tail = freeverb.tail();
+    ///
+    ///     let mut reverb = freeverb.into_processor::<Fixed<f32, 2>>();
+    ///     reverb.prepare(48000.0, 64);
+    ///
+    ///     let mut block = [Fixed::from([0.0, 0.0]); 64];
+    ///     block[0] = Fixed::from([1.0, 1.0]);
+    ///     reverb.process(&mut block);
+    ///
+    ///     assert_eq!(reverb.tail(), tail);
+    /// }
+    /// ```
+    fn into_processor<F>(self) -> BlockReverb<F, Self>
+    where
+        Self: Sized,
+        F: Frame,
+        F::Sample: FloatSample,
+    {
+        BlockReverb::new(self)
+    }
+}
+
+impl<R> ReverbProcessor for &mut R
+where
+    R: ReverbProcessor + ?Sized,
+{
+    fn process(&mut self, input: &[f64], output: &mut [f64]) {
+        (**self).process(input, output)
+    }
+
+    fn reset(&mut self) {
+        (**self).reset()
+    }
+
+    fn tail(&self) -> usize {
+        (**self).tail()
+    }
+}
+
+/// Returns the smallest prime that is greater than or equal to `n`. Delay
+/// lengths are rounded to primes, so that their echoes do not line up.
+fn next_prime(n: usize) -> usize {
+    let is_prime = |n: usize| {
+        n >= 2
+            && (2..)
+                .take_while(|d| d * d <= n)
+                .all(|d| !n.is_multiple_of(d))
+    };
+    (n..).find(|&n| is_prime(n)).unwrap_or(n)
+}
+
+/// A [`Signal`] that applies a [`ReverbProcessor`] to an underlying
+/// [`Signal`].
+///
+/// The output is extended by the tail of the reverb, so that it can decay
+/// once the input ends.
+///
+/// This is created by [`Signal::reverb`].
+pub struct Reverb<S, R>
+where
+    S: Signal,
+{
+    signal: S,
+    reverb: R,
+    // A frame of equilibrium, used to flush the tail once the input ends.
+    silence: Option<S::Frame>,
+    input: Vec<f64>,
+    output: Vec<f64>,
+    // The number of frames left to output, once the input has run out.
+    tail: Option<usize>,
+}
+
+impl<S, R> Reverb<S, R>
+where
+    S: Signal,
+    <S::Frame as Frame>::Sample: FloatSample,
+    R: ReverbProcessor,
+{
+    pub(crate) fn new(signal: S, reverb: R) -> Self {
+        Self {
+            signal,
+            reverb,
+            silence: None,
+            input: Vec::new(),
+            output: Vec::new(),
+            tail: None,
+        }
+    }
+
+    pub fn reverb(&self) -> &R {
+        &self.reverb
+    }
+
+    pub fn reverb_mut(&mut self) -> &mut R {
+        &mut self.reverb
+    }
+}
+
+impl<S, R> Signal for Reverb<S, R>
+where
+    S: Signal,
+    <S::Frame as Frame>::Sample: FloatSample,
+    R: ReverbProcessor,
+{
+    type Frame = S::Frame;
+
+    fn next(&mut self) -> Option<Self::Frame> {
+        let mut frame = match self.tail {
+            Some(0) => return None,
+            Some(ref mut tail) => {
+                *tail -= 1;
+                self.silence.clone()?
+            }
+            None => match self.signal.next() {
+                Some(frame) => {
+                    if self.silence.is_none() {
+                        let mut silence = frame.clone();
+                        silence
+                            .iter_mut()
+                            .for_each(|s| *s = <S::Frame as Frame>::Sample::EQUILIBRIUM);
+                        self.silence = Some(silence);
+                    }
+
+                    frame
+                }
+                None => {
+                    self.tail = Some(self.reverb.tail());
+                    return self.next();
+                }
+            },
+        };
+
+        self.input.clear();
+        self.input.extend(frame.iter().map(to_f64));
+        self.output.resize(self.input.len(), 0.0);
+
+        self.reverb.process(&self.input, &mut self.output);
+
+        for (sample, &y) in frame.iter_mut().zip(&self.output) {
+            *sample = FromSample::from_sample(y);
+        }
+
+        Some(frame)
+    }
+
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        let (input, tail) = match self.tail {
+            Some(tail) => ((0, Some(0)), tail),
+            None => (self.signal.len_hint(), self.reverb.tail()),
+        };
+
+        len_hint_with_tail(input, tail, self.silence.is_some())
+    }
+}
+
+impl<S, R> ExactSizeSignal for Reverb<S, R>
+where
+    S: ExactSizeSignal,
+    <S::Frame as Frame>::Sample: FloatSample,
+    R: ReverbProcessor,
+{
+}
+
+/// A [`Processor`] that applies a [`ReverbProcessor`] to blocks of
+/// [`Frame`]s.
+///
+/// The [`ReverbProcessor`] keeps the sample rate that it was created with,
+/// so the sample rate passed to [`Processor::prepare`] is ignored.
+///
+/// This is created by [`ReverbProcessor::into_processor`].
+pub struct BlockReverb<F, R> {
+    reverb: R,
+    input: Vec<f64>,
+    output: Vec<f64>,
+    _marker: PhantomData<F>,
+}
+
+impl<F, R> BlockReverb<F, R>
+where
+    F: Frame,
+    F::Sample: FloatSample,
+    R: ReverbProcessor,
+{
+    pub(crate) fn new(reverb: R) -> Self {
+        Self {
+            reverb,
+            input: Vec::new(),
+            output: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn reverb(&self) -> &R {
+        &self.reverb
+    }
+
+    pub fn reverb_mut(&mut self) -> &mut R {
+        &mut self.reverb
+    }
+}
+
+impl<F, R> Processor for BlockReverb<F, R>
+where
+    F: Frame,
+    F::Sample: FloatSample,
+    R: ReverbProcessor,
+{
+    type Frame = F;
+
+    fn process(&mut self, block: &mut [Self::Frame]) {
+        for frame in block.iter_mut() {
+            self.input.clear();
+            self.input.extend(frame.iter().map(to_f64));
+            self.output.resize(self.input.len(), 0.0);
+
+            self.reverb.process(&self.input, &mut self.output);
+
+            for (sample, &y) in frame.iter_mut().zip(&self.output) {
+                *sample = FromSample::from_sample(y);
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        self.reverb.reset();
+    }
+
+    fn tail(&self) -> usize {
+        self.reverb.tail()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn primes() {
+        assert_eq!(next_prime(0), 2);
+        assert_eq!(next_prime(13), 13);
+        assert_eq!(next_prime(1116), 1117);
+    }
+
+    #[test]
+    fn block_processor_matches_signal() {
+        use crate::frame::Fixed;
+        use crate::signal;
+
+        let mut input = vec![Fixed::from([0.0f32, 0.0]); 500];
+        input[0] = Fixed::from([1.0, -0.5]);
+
+        let fdn = || Fdn::new(FdnParams::default(), 8000.0);
+
+        let expected: Vec<_> = signal::from_frames(input.clone())
+            .reverb(fdn())
+            .into_frames()
+            .take(input.len())
+            .collect();
+
+        let mut processor = fdn().into_processor();
+        processor.prepare(8000.0, 64);
+        assert_eq!(processor.tail(), fdn().tail());
+
+        let mut output = input;
+        for block in output.chunks_mut(64) {
+            processor.process(block);
+        }
+
+        assert_eq!(output, expected);
+    }
+}
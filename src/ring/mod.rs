@@ -0,0 +1,465 @@
+//! A wait-free single-producer, single-consumer ring buffer of [`Frame`]s,
+//! for handing audio between a real-time thread and other threads.
+//!
+//! Neither side ever blocks or allocates after creation. A push that does
+//! not fit is counted as an overrun, and a pop from an empty buffer is
+//! counted as an underrun, so that dropouts can be reported from outside of
+//! the real-time thread.
+//!
+//! The buffer only uses atomics from [`std`], and its tests are small enough
+//! to run under Miri.
+
+use std::cell::UnsafeCell;
+use std::mem::MaybeUninit;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use crate::frame::Frame;
+use crate::sample::Sample;
+use crate::signal::Signal;
+
+/// The state shared by a [`Producer`] and a [`Consumer`].
+///
+/// The read and write positions run from 0 to twice the capacity, so that a
+/// full buffer can be told apart from an empty one.
+struct Shared<F> {
+    slots: Box<[UnsafeCell<MaybeUninit<F>>]>,
+    // The position of the next frame to pop, only written by the consumer.
+    read: AtomicUsize,
+    // The position of the next frame to push, only written by the producer.
+    write: AtomicUsize,
+    overruns: AtomicUsize,
+    underruns: AtomicUsize,
+    producer_alive: AtomicBool,
+}
+
+// SAFETY: Each slot is only accessed by the producer while it is free, and by
+// the consumer while it is filled. The release stores of the positions hand
+// slots over between the two, along with the frames in them.
+unsafe impl<F: Send> Sync for Shared<F> {}
+
+impl<F> Shared<F> {
+    fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// The number of filled slots between two positions.
+    fn distance(&self, read: usize, write: usize) -> usize {
+        (write + 2 * self.capacity() - read) % (2 * self.capacity())
+    }
+
+    /// Advances a position by a number of slots.
+    fn advance(&self, position: usize, n: usize) -> usize {
+        (position + n) % (2 * self.capacity())
+    }
+
+    fn slot(&self, position: usize) -> *mut MaybeUninit<F> {
+        self.slots[position % self.capacity()].get()
+    }
+}
+
+impl<F> Drop for Shared<F> {
+    fn drop(&mut self) {
+        let read = *self.read.get_mut();
+        let write = *self.write.get_mut();
+
+        for i in 0..self.distance(read, write) {
+            let position = self.advance(read, i);
+            // SAFETY: The slots between the positions are filled, and both
+            // sides are gone.
+            unsafe { (*self.slot(position)).assume_init_drop() };
+        }
+    }
+}
+
+/// Creates a ring buffer that holds up to `capacity` [`Frame`]s, returning
+/// its producer and consumer sides. Each side can be sent to a different
+/// thread.
+///
+/// # Panics
+///
+/// Panics if `capacity` is 0.
+///
+/// ```
+/// use std::thread;
+///
+/// use sampara::{Fixed, Signal};
+/// use sampara::ring;
+///
+/// fn main() {
+///     let (mut producer, mut consumer) = ring::ring_buffer(64);
+///
+///     let worker = thread::spawn(move || {
+///         let frames = vec![Fixed::from([0.25f32, -0.25]); 256];
+///         let mut sent = 0;
+///         while sent < frames.len() {
+///             let n = producer.slots().min(frames.len() - sent);
+///             sent += producer.push_slice(&frames[sent..sent + n]);
+///         }
+///
+///         assert_eq!(producer.overruns(), 0);
+///     });
+///
+///     // The consumer yields silence until the frames arrive, and ends once
+///     // the producer is gone.
+///     let received = std::iter::from_fn(|| consumer.next())
+///         .filter(|&frame| frame != Fixed::from([0.0, 0.0]))
+///         .count();
+///
+///     worker.join().unwrap();
+///     assert_eq!(received, 256);
+/// }
+/// ```
+pub fn ring_buffer<F>(capacity: usize) -> (Producer<F>, Consumer<F>)
+where
+    F: Frame,
+{
+    assert!(capacity > 0, "the capacity must be at least one frame");
+
+    let shared = Arc::new(Shared {
+        slots: (0..capacity)
+            .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+            .collect(),
+        read: AtomicUsize::new(0),
+        write: AtomicUsize::new(0),
+        overruns: AtomicUsize::new(0),
+        underruns: AtomicUsize::new(0),
+        producer_alive: AtomicBool::new(true),
+    });
+
+    (
+        Producer {
+            shared: Arc::clone(&shared),
+        },
+        Consumer {
+            shared,
+            silence: None,
+        },
+    )
+}
+
+/// The side of a ring buffer that pushes [`Frame`]s.
+///
+/// This is created by [`ring_buffer`].
+pub struct Producer<F> {
+    shared: Arc<Shared<F>>,
+}
+
+impl<F> Producer<F>
+where
+    F: Frame,
+{
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity()
+    }
+
+    /// The number of free slots. The consumer can only increase this.
+    pub fn slots(&self) -> usize {
+        let read = self.shared.read.load(Ordering::Acquire);
+        let write = self.shared.write.load(Ordering::Relaxed);
+        self.capacity() - self.shared.distance(read, write)
+    }
+
+    /// The number of [`Frame`]s that did not fit in the buffer.
+    pub fn overruns(&self) -> usize {
+        self.shared.overruns.load(Ordering::Relaxed)
+    }
+
+    /// Pushes up to `n` [`Frame`]s produced by a closure, stopping early if
+    /// the closure returns [`None`]. Returns the number pushed.
+    fn push_with(&mut self, n: usize, mut f: impl FnMut() -> Option<F>) -> usize {
+        let write = self.shared.write.load(Ordering::Relaxed);
+        let n = n.min(self.slots());
+
+        let mut pushed = 0;
+        while pushed < n {
+            let Some(frame) = f() else { break };
+            let position = self.shared.advance(write, pushed);
+
+            // SAFETY: The slot is free, as it is before the read position, and
+            // the consumer does not access it until the write position moves
+            // past it.
+            unsafe { (*self.shared.slot(position)).write(frame) };
+            pushed += 1;
+        }
+
+        self.shared
+            .write
+            .store(self.shared.advance(write, pushed), Ordering::Release);
+
+        pushed
+    }
+
+    /// Pushes a [`Frame`], or returns it if the buffer is full, counting an
+    /// overrun.
+    pub fn push(&mut self, frame: F) -> Result<(), F> {
+        let mut frame = Some(frame);
+        self.push_with(1, || frame.take());
+
+        match frame {
+            None => Ok(()),
+            Some(frame) => {
+                self.shared.overruns.fetch_add(1, Ordering::Relaxed);
+                Err(frame)
+            }
+        }
+    }
+
+    /// Pushes as many [`Frame`]s from a slice as fit, and returns the number
+    /// pushed. Each [`Frame`] that does not fit counts as an overrun.
+    pub fn push_slice(&mut self, frames: &[F]) -> usize {
+        let mut iter = frames.iter().cloned();
+        let pushed = self.push_with(frames.len(), || iter.next());
+
+        let dropped = frames.len() - pushed;
+        if dropped > 0 {
+            self.shared.overruns.fetch_add(dropped, Ordering::Relaxed);
+        }
+
+        pushed
+    }
+
+    /// Pulls [`Frame`]s from a [`Signal`] into the free slots, and returns the
+    /// number pushed. This never overruns: the [`Signal`] is only advanced
+    /// for [`Frame`]s that fit.
+    pub fn push_signal<S>(&mut self, signal: &mut S) -> usize
+    where
+        S: Signal<Frame = F>,
+    {
+        self.push_with(usize::MAX, || signal.next())
+    }
+}
+
+impl<F> Drop for Producer<F> {
+    fn drop(&mut self) {
+        self.shared.producer_alive.store(false, Ordering::Release);
+    }
+}
+
+/// The side of a ring buffer that pops [`Frame`]s.
+///
+/// As a [`Signal`], it yields silence while the buffer is empty, counting an
+/// underrun for each silent [`Frame`], so that a real-time callback can keep
+/// running. It ends once the [`Producer`] has been dropped and the buffer is
+/// empty.
+///
+/// This is created by [`ring_buffer`].
+pub struct Consumer<F> {
+    shared: Arc<Shared<F>>,
+    // A frame of equilibrium, the shape of the last popped frame.
+    silence: Option<F>,
+}
+
+impl<F> Consumer<F>
+where
+    F: Frame,
+{
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity()
+    }
+
+    /// The number of [`Frame`]s in the buffer. The producer can only
+    /// increase this.
+    pub fn len(&self) -> usize {
+        let read = self.shared.read.load(Ordering::Relaxed);
+        let write = self.shared.write.load(Ordering::Acquire);
+        self.shared.distance(read, write)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The number of [`Frame`]s that were requested from an empty buffer.
+    pub fn underruns(&self) -> usize {
+        self.shared.underruns.load(Ordering::Relaxed)
+    }
+
+    /// Whether the [`Producer`] has been dropped. No more [`Frame`]s will
+    /// arrive once this returns `true` and the buffer is empty.
+    pub fn is_abandoned(&self) -> bool {
+        !self.shared.producer_alive.load(Ordering::Acquire)
+    }
+
+    /// Pops up to `n` [`Frame`]s, passing each to a closure. Returns the
+    /// number popped.
+    fn pop_with(&mut self, n: usize, mut f: impl FnMut(F)) -> usize {
+        let read = self.shared.read.load(Ordering::Relaxed);
+        let n = n.min(self.len());
+
+        for i in 0..n {
+            let position = self.shared.advance(read, i);
+
+            // SAFETY: The slot is filled, as it is before the write position,
+            // and the producer does not access it until the read position
+            // moves past it.
+            let frame = unsafe { (*self.shared.slot(position)).assume_init_read() };
+            f(frame);
+        }
+
+        self.shared
+            .read
+            .store(self.shared.advance(read, n), Ordering::Release);
+
+        n
+    }
+
+    /// Pops a [`Frame`], or returns [`None`] if the buffer is empty, counting
+    /// an underrun.
+    pub fn pop(&mut self) -> Option<F> {
+        let mut frame = None;
+        if self.pop_with(1, |f| frame = Some(f)) == 0 {
+            self.shared.underruns.fetch_add(1, Ordering::Relaxed);
+        }
+
+        frame
+    }
+
+    /// Pops [`Frame`]s into a slice, and returns the number popped. Each
+    /// [`Frame`] of the slice that is not filled counts as an underrun, and
+    /// is left unchanged.
+    pub fn pop_slice(&mut self, frames: &mut [F]) -> usize {
+        let mut slots = frames.iter_mut();
+        let popped = self.pop_with(slots.len(), |f| {
+            if let Some(slot) = slots.next() {
+                *slot = f;
+            }
+        });
+
+        let missing = frames.len() - popped;
+        if missing > 0 {
+            self.shared.underruns.fetch_add(missing, Ordering::Relaxed);
+        }
+
+        popped
+    }
+}
+
+impl<F> Signal for Consumer<F>
+where
+    F: Frame,
+{
+    type Frame = F;
+
+    fn next(&mut self) -> Option<Self::Frame> {
+        // The producer is checked before the buffer, so that frames pushed
+        // just before it was dropped are not missed.
+        let abandoned = self.is_abandoned();
+
+        let mut frame = None;
+        if self.pop_with(1, |f| frame = Some(f)) == 1 {
+            if self.silence.is_none() {
+                let mut silence = frame.clone()?;
+                silence
+                    .iter_mut()
+                    .for_each(|s| *s = <F::Sample as Sample>::EQUILIBRIUM);
+                self.silence = Some(silence);
+            }
+
+            return frame;
+        }
+
+        if abandoned {
+            return None;
+        }
+
+        self.shared.underruns.fetch_add(1, Ordering::Relaxed);
+        Some(self.silence.clone().unwrap_or_else(F::equil))
+    }
+
+    /// While the [`Producer`] is alive, silence is yielded whenever the buffer
+    /// is empty, so only the buffered [`Frame`]s are certain and there is no
+    /// upper bound.
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        let abandoned = self.is_abandoned();
+        let len = self.len();
+
+        (len, abandoned.then_some(len))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame::{Dynamic, Fixed};
+    use crate::signal;
+
+    use std::thread;
+
+    #[test]
+    fn counters() {
+        let (mut producer, mut consumer) = ring_buffer::<Fixed<i16, 1>>(3);
+
+        assert_eq!(producer.push_slice(&[[1].into(), [2].into()]), 2);
+        assert_eq!(producer.push_slice(&[[3].into(), [4].into()]), 1);
+        assert_eq!(producer.push([5].into()), Err([5].into()));
+        assert_eq!(producer.overruns(), 2);
+        assert_eq!(producer.slots(), 0);
+
+        let mut frames = [Fixed::from([0]); 4];
+        assert_eq!(consumer.pop_slice(&mut frames), 3);
+        assert_eq!(frames, [[1].into(), [2].into(), [3].into(), [0].into()]);
+        assert_eq!(consumer.underruns(), 1);
+
+        // The positions wrap around.
+        let mut source = signal::from_samples_fixed::<_, 1>(10..20);
+        assert_eq!(producer.push_signal(&mut source), 3);
+        assert_eq!(source.next(), Some([13].into()));
+        assert_eq!(consumer.pop(), Some([10].into()));
+        assert_eq!(consumer.len(), 2);
+    }
+
+    #[test]
+    fn consumer_signal() {
+        let (mut producer, mut consumer) = ring_buffer::<Dynamic<f32>>(4);
+
+        producer.push(Dynamic::from([0.5, -0.5])).unwrap();
+        assert_eq!(consumer.next(), Some(Dynamic::from([0.5, -0.5])));
+
+        // An underrun yields silence, until the producer is gone.
+        assert_eq!(consumer.next(), Some(Dynamic::from([0.0, 0.0])));
+        assert_eq!(consumer.underruns(), 1);
+
+        producer.push(Dynamic::from([1.0, 1.0])).unwrap();
+        drop(producer);
+        assert_eq!(consumer.next(), Some(Dynamic::from([1.0, 1.0])));
+        assert_eq!(consumer.next(), None);
+    }
+
+    #[test]
+    fn drops_unread_frames() {
+        let (mut producer, consumer) = ring_buffer::<Dynamic<f64>>(4);
+        producer.push(Dynamic::from([1.0; 16])).unwrap();
+        producer.push(Dynamic::from([2.0; 16])).unwrap();
+
+        drop(consumer);
+        drop(producer);
+    }
+
+    #[test]
+    fn threads() {
+        let count: u32 = if cfg!(miri) { 200 } else { 10_000 };
+        let (mut producer, mut consumer) = ring_buffer::<Fixed<u32, 1>>(16);
+
+        let writer = thread::spawn(move || {
+            let mut source = signal::from_samples_fixed::<_, 1>(0..count);
+            let mut pushed = 0;
+            while pushed < count as usize {
+                pushed += producer.push_signal(&mut source);
+                thread::yield_now();
+            }
+        });
+
+        let mut expected = 0;
+        while expected < count {
+            if let Some(frame) = consumer.pop() {
+                assert_eq!(frame, [expected].into());
+                expected += 1;
+            }
+        }
+
+        writer.join().unwrap();
+        assert!(consumer.is_empty());
+        assert!(consumer.is_abandoned());
+    }
+}
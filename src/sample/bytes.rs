@@ -0,0 +1,458 @@
+//! Conversions between [`Sample`]s and their raw byte representations.
+//!
+//! Every [`Sample`] type has a packed byte width, given by
+//! [`ByteSample::WIDTH`]. Most types use their in-memory size, while [`Q23`]
+//! is packed into 3 bytes, as is conventional for 24-bit PCM.
+
+use std::io::{ErrorKind, Read, Seek, SeekFrom};
+use std::marker::PhantomData;
+
+use crate::sample::{ALaw, MuLaw, Q15, Q23, Q31, Sample};
+
+/// [`Sample`] types that can be converted to and from a fixed number of bytes.
+///
+/// ```
+/// use sampara::sample::{ByteSample, Q23};
+///
+/// fn main() {
+///     assert_eq!(i16::from_le_bytes([0x34, 0x12]), 0x1234);
+///     assert_eq!(<i16 as ByteSample>::to_be_bytes(0x1234), [0x12, 0x34]);
+///
+///     assert_eq!(Q23::WIDTH, 3);
+///     assert_eq!(Q23::from_le_bytes([0x00, 0x00, 0x80]), Q23::MIN);
+///     assert_eq!(Q23::MAX.to_be_bytes(), [0x7F, 0xFF, 0xFF]);
+/// }
+/// ```
+pub trait ByteSample: Sample {
+    /// The number of bytes used to represent this [`Sample`].
+    const WIDTH: usize;
+
+    /// A byte array of length [`Self::WIDTH`].
+    type Bytes: AsRef<[u8]> + AsMut<[u8]> + Copy + Default;
+
+    /// Creates a [`Sample`] from its representation as bytes in little endian
+    /// order.
+    fn from_le_bytes(bytes: Self::Bytes) -> Self;
+
+    /// Creates a [`Sample`] from its representation as bytes in big endian
+    /// order.
+    fn from_be_bytes(bytes: Self::Bytes) -> Self;
+
+    /// Returns the representation of this [`Sample`] as bytes in little endian
+    /// order.
+    fn to_le_bytes(self) -> Self::Bytes;
+
+    /// Returns the representation of this [`Sample`] as bytes in big endian
+    /// order.
+    fn to_be_bytes(self) -> Self::Bytes;
+}
+
+/// A macro used to simplify the implementation of [`ByteSample`] for
+/// primitive types, which already provide equivalent inherent methods.
+macro_rules! impl_byte_sample_primitive {
+    ($($T:ty),* $(,)?) => {
+        $(
+            impl ByteSample for $T {
+                const WIDTH: usize = core::mem::size_of::<$T>();
+
+                type Bytes = [u8; core::mem::size_of::<$T>()];
+
+                #[inline]
+                fn from_le_bytes(bytes: Self::Bytes) -> Self {
+                    <$T>::from_le_bytes(bytes)
+                }
+
+                #[inline]
+                fn from_be_bytes(bytes: Self::Bytes) -> Self {
+                    <$T>::from_be_bytes(bytes)
+                }
+
+                #[inline]
+                fn to_le_bytes(self) -> Self::Bytes {
+                    <$T>::to_le_bytes(self)
+                }
+
+                #[inline]
+                fn to_be_bytes(self) -> Self::Bytes {
+                    <$T>::to_be_bytes(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_byte_sample_primitive!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, f32, f64);
+
+/// A macro used to simplify the implementation of [`ByteSample`] for newtypes
+/// whose bits are stored in a primitive type of the same width.
+macro_rules! impl_byte_sample_bits {
+    ($($T:ty: $Bits:ty),* $(,)?) => {
+        $(
+            impl ByteSample for $T {
+                const WIDTH: usize = core::mem::size_of::<$Bits>();
+
+                type Bytes = [u8; core::mem::size_of::<$Bits>()];
+
+                #[inline]
+                fn from_le_bytes(bytes: Self::Bytes) -> Self {
+                    <$T>::from_bits(<$Bits>::from_le_bytes(bytes))
+                }
+
+                #[inline]
+                fn from_be_bytes(bytes: Self::Bytes) -> Self {
+                    <$T>::from_bits(<$Bits>::from_be_bytes(bytes))
+                }
+
+                #[inline]
+                fn to_le_bytes(self) -> Self::Bytes {
+                    self.to_bits().to_le_bytes()
+                }
+
+                #[inline]
+                fn to_be_bytes(self) -> Self::Bytes {
+                    self.to_bits().to_be_bytes()
+                }
+            }
+        )*
+    };
+}
+
+impl_byte_sample_bits!(MuLaw: u8, ALaw: u8, Q15: i16, Q31: i32);
+
+impl ByteSample for Q23 {
+    const WIDTH: usize = 3;
+
+    type Bytes = [u8; 3];
+
+    #[inline]
+    fn from_le_bytes([b0, b1, b2]: [u8; 3]) -> Self {
+        // Place the bytes in the top of an `i32`, and shift back down in order
+        // to sign-extend.
+        Q23::from_bits(i32::from_le_bytes([0, b0, b1, b2]) >> 8)
+    }
+
+    #[inline]
+    fn from_be_bytes([b0, b1, b2]: [u8; 3]) -> Self {
+        Q23::from_bits(i32::from_be_bytes([b0, b1, b2, 0]) >> 8)
+    }
+
+    #[inline]
+    fn to_le_bytes(self) -> [u8; 3] {
+        let [b0, b1, b2, _] = self.to_bits().to_le_bytes();
+        [b0, b1, b2]
+    }
+
+    #[inline]
+    fn to_be_bytes(self) -> [u8; 3] {
+        let [_, b0, b1, b2] = self.to_bits().to_be_bytes();
+        [b0, b1, b2]
+    }
+}
+
+/// A byte order used to read and write [`ByteSample`]s.
+pub trait ByteOrder {
+    /// Creates a [`Sample`] from its byte representation in this order.
+    fn from_bytes<S: ByteSample>(bytes: S::Bytes) -> S;
+
+    /// Returns the byte representation of a [`Sample`] in this order.
+    fn to_bytes<S: ByteSample>(sample: S) -> S::Bytes;
+}
+
+/// Little endian byte order, where the least significant byte comes first.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum LittleEndian {}
+
+/// Big endian byte order, where the most significant byte comes first.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum BigEndian {}
+
+/// The byte order of the target platform.
+#[cfg(target_endian = "little")]
+pub type NativeEndian = LittleEndian;
+
+/// The byte order of the target platform.
+#[cfg(target_endian = "big")]
+pub type NativeEndian = BigEndian;
+
+impl ByteOrder for LittleEndian {
+    #[inline]
+    fn from_bytes<S: ByteSample>(bytes: S::Bytes) -> S {
+        S::from_le_bytes(bytes)
+    }
+
+    #[inline]
+    fn to_bytes<S: ByteSample>(sample: S) -> S::Bytes {
+        sample.to_le_bytes()
+    }
+}
+
+impl ByteOrder for BigEndian {
+    #[inline]
+    fn from_bytes<S: ByteSample>(bytes: S::Bytes) -> S {
+        S::from_be_bytes(bytes)
+    }
+
+    #[inline]
+    fn to_bytes<S: ByteSample>(sample: S) -> S::Bytes {
+        sample.to_be_bytes()
+    }
+}
+
+/// An [`Iterator`] that reads [`Sample`]s of a given byte order from an
+/// underlying [`Read`].
+///
+/// Iteration ends at the end of the underlying reader, or at the first I/O
+/// error, which can then be retrieved with [`Self::take_error`]. Any trailing
+/// bytes that do not complete a [`Sample`] are discarded.
+///
+/// Each [`Sample`] is read with a separate call to [`Read::read_exact`], so
+/// unbuffered readers should be wrapped in a [`std::io::BufReader`].
+///
+/// ```
+/// use sampara::sample::{BigEndian, ReadSamples};
+///
+/// fn main() {
+///     let bytes: &[u8] = &[0x12, 0x34, 0xFF, 0xFE, 0x00];
+///     let samples = ReadSamples::<_, i16, BigEndian>::new(bytes);
+///
+///     assert_eq!(samples.collect::<Vec<_>>(), vec![0x1234, -2]);
+/// }
+/// ```
+pub struct ReadSamples<R, S, E>
+where
+    R: Read,
+    S: ByteSample,
+    E: ByteOrder,
+{
+    reader: R,
+    error: Option<std::io::Error>,
+    done: bool,
+    // The number of samples read, or sought to.
+    position: usize,
+    // The number of bytes read since the first sample, including those of an
+    // incomplete sample, so that the first seek can find where it started.
+    offset: u64,
+    // The offset of the first sample in the reader, found on the first seek.
+    start: Option<u64>,
+    _marker: PhantomData<(S, E)>,
+}
+
+impl<R, S, E> ReadSamples<R, S, E>
+where
+    R: Read,
+    S: ByteSample,
+    E: ByteOrder,
+{
+    /// Creates a new [`ReadSamples`] that reads from a given [`Read`].
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            error: None,
+            done: false,
+            position: 0,
+            offset: 0,
+            start: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The index of the next [`Sample`] to be read.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Takes the I/O error that ended iteration, if any. Reaching the end of
+    /// the underlying reader is not considered to be an error.
+    pub fn take_error(&mut self) -> Option<std::io::Error> {
+        self.error.take()
+    }
+
+    /// Consumes this [`ReadSamples`], returning the underlying [`Read`].
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Reads into `buf` until it is full or the reader ends, returning the
+    /// number of bytes read. Every byte read is counted, even if an error
+    /// follows.
+    fn fill(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let mut filled = 0;
+        while filled < buf.len() {
+            match self.reader.read(&mut buf[filled..]) {
+                Ok(0) => break,
+                Ok(n) => {
+                    filled += n;
+                    self.offset += n as u64;
+                }
+                Err(err) if err.kind() == ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(filled)
+    }
+}
+
+impl<R, S, E> ReadSamples<R, S, E>
+where
+    R: Read + Seek,
+    S: ByteSample,
+    E: ByteOrder,
+{
+    /// Moves the underlying reader so that the next [`Sample`] read is the
+    /// one at a given index, counted from where the reader was when this
+    /// [`ReadSamples`] was created. This restarts iteration if it had ended.
+    ///
+    /// If seeking fails, iteration ends, and the error can be retrieved with
+    /// [`Self::take_error`].
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    ///
+    /// use sampara::sample::{LittleEndian, ReadSamples};
+    ///
+    /// fn main() {
+    ///     let bytes = Cursor::new(vec![1u8, 0, 2, 0, 3, 0]);
+    ///     let mut samples = ReadSamples::<_, i16, LittleEndian>::new(bytes);
+    ///
+    ///     assert_eq!(samples.by_ref().count(), 3);
+    ///
+    ///     samples.seek(1);
+    ///     assert_eq!(samples.position(), 1);
+    ///     assert_eq!(samples.collect::<Vec<_>>(), vec![2, 3]);
+    /// }
+    /// ```
+    pub fn seek(&mut self, sample: usize) {
+        let width = S::WIDTH as u64;
+
+        let result = match self.start {
+            Some(start) => Ok(start),
+            None => self
+                .reader
+                .stream_position()
+                .map(|p| p.saturating_sub(self.offset)),
+        }
+        .and_then(|start| {
+            self.start = Some(start);
+            self.reader
+                .seek(SeekFrom::Start(start + sample as u64 * width))
+        });
+
+        self.position = sample;
+        self.offset = sample as u64 * width;
+        match result {
+            Ok(_) => self.done = false,
+            Err(err) => {
+                self.done = true;
+                self.error = Some(err);
+            }
+        }
+    }
+}
+
+impl<R, S, E> Iterator for ReadSamples<R, S, E>
+where
+    R: Read,
+    S: ByteSample,
+    E: ByteOrder,
+{
+    type Item = S;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let mut bytes = S::Bytes::default();
+        match self.fill(bytes.as_mut()) {
+            Ok(n) if n == bytes.as_mut().len() => {
+                self.position += 1;
+                Some(E::from_bytes(bytes))
+            }
+            // The reader ended, possibly partway through a sample.
+            Ok(_) => {
+                self.done = true;
+                None
+            }
+            Err(err) => {
+                self.done = true;
+                self.error = Some(err);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip<S: ByteSample>(s: S) {
+        assert_eq!(S::from_le_bytes(s.to_le_bytes()), s);
+        assert_eq!(S::from_be_bytes(s.to_be_bytes()), s);
+
+        let mut le = s.to_le_bytes();
+        le.as_mut().reverse();
+        assert_eq!(le.as_ref(), s.to_be_bytes().as_ref());
+    }
+
+    #[test]
+    fn round_trips() {
+        round_trip(-12345i16);
+        round_trip(0xDEAD_BEEFu32);
+        round_trip(-0.25f32);
+        round_trip(1.0e-300f64);
+        round_trip(i128::MIN);
+        round_trip(MuLaw::from_bits(0x12));
+        round_trip(ALaw::from_bits(0xD5));
+        round_trip(Q15::MIN);
+        round_trip(Q31::MAX);
+        round_trip(Q23::MIN);
+        round_trip(Q23::MAX);
+        round_trip(Q23::from_bits(-1));
+        round_trip(Q23::from_bits(0x12_3456));
+    }
+
+    #[test]
+    fn packed_q23() {
+        assert_eq!(Q23::from_bits(0x12_3456).to_le_bytes(), [0x56, 0x34, 0x12]);
+        assert_eq!(Q23::from_be_bytes([0xFF, 0xFF, 0xFF]), Q23::from_bits(-1));
+    }
+
+    #[test]
+    fn read_samples_error() {
+        struct Failing;
+
+        impl Read for Failing {
+            fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+                Err(ErrorKind::BrokenPipe.into())
+            }
+        }
+
+        let mut samples = ReadSamples::<_, u8, LittleEndian>::new(Failing);
+        assert_eq!(samples.next(), None);
+        assert_eq!(samples.next(), None);
+        assert_eq!(
+            samples.take_error().map(|e| e.kind()),
+            Some(ErrorKind::BrokenPipe)
+        );
+    }
+
+    #[test]
+    fn seek_after_trailing_byte() {
+        use std::io::Cursor;
+
+        // The odd trailing byte is read as part of an incomplete sample, which
+        // must not shift where seeking starts from.
+        let bytes = Cursor::new(vec![1u8, 0, 2, 0, 9]);
+        let mut samples = ReadSamples::<_, i16, LittleEndian>::new(bytes);
+
+        assert_eq!(samples.by_ref().collect::<Vec<_>>(), vec![1, 2]);
+
+        samples.seek(0);
+        assert_eq!(samples.by_ref().collect::<Vec<_>>(), vec![1, 2]);
+
+        samples.seek(1);
+        assert_eq!(samples.collect::<Vec<_>>(), vec![2]);
+    }
+}
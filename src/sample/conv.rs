@@ -12,6 +12,8 @@
 //! Note that floating point samples span the range [-1.0, 1.0). This means that
 //! `1.0f32.convert_into::<i16>()` will overflow, as an example.
 
+use num_traits::ToPrimitive;
+
 use crate::Sample;
 
 /// [`Sample`] types that can be converted from another [`Sample`] type.
@@ -295,5 +297,10 @@ where
     }
 }
 
+/// Converts a [`Sample`] to `f64`, via its [`Sample::Float`] type.
+pub(crate) fn to_f64<S: Sample>(sample: &S) -> f64 {
+    sample.into_float().to_f64().unwrap_or(0.0)
+}
+
 #[cfg(test)]
 mod tests {}
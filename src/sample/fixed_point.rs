@@ -0,0 +1,341 @@
+//! Fixed-point fractional sample types, in the Q formats commonly used by DSP
+//! hardware.
+//!
+//! Each type represents a value in the interval [-1.0, 1.0), with a fixed
+//! number of fractional bits. Addition, subtraction and negation saturate at
+//! the bounds of the interval instead of wrapping, and multiplication is a
+//! fractional multiply that is rounded to the nearest representable value.
+//!
+//! Conversions to and from other [`Sample`] types go through the integer type
+//! that spans the same amplitude range: [`Q15`] is equivalent to [`i16`], and
+//! both [`Q23`] and [`Q31`] are equivalent to [`i32`].
+//!
+//! [`Sample`]: crate::Sample
+
+use core::ops::{Add, Mul, Neg, Sub};
+
+use crate::sample::{ALaw, FromSample, MuLaw};
+
+/// A Q15 (Q0.15) fixed-point [`Sample`](crate::Sample), with 15 fractional
+/// bits stored in an [`i16`].
+///
+/// ```
+/// use sampara::sample::Q15;
+/// use sampara::IntoSample;
+///
+/// fn main() {
+///     let a = Q15::from_bits(0x4000); // 0.5
+///     let b = Q15::from_bits(0x2000); // 0.25
+///
+///     assert_eq!(a * b, Q15::from_bits(0x1000));
+///     assert_eq!(a + a, Q15::MAX);
+///     assert_eq!(-Q15::MIN, Q15::MAX);
+///
+///     let f: f32 = a.into_sample();
+///     assert_eq!(f, 0.5);
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Q15(i16);
+
+/// A Q1.23 fixed-point [`Sample`](crate::Sample), with 23 fractional bits
+/// stored in the low 24 bits of an [`i32`]. This is the native format of many
+/// 24-bit audio DSPs.
+///
+/// ```
+/// use sampara::sample::Q23;
+/// use sampara::IntoSample;
+///
+/// fn main() {
+///     let a = Q23::from_bits(0x40_0000); // 0.5
+///     let b = Q23::from_bits(-0x40_0000); // -0.5
+///
+///     assert_eq!(a * b, Q23::from_bits(-0x20_0000));
+///     assert_eq!(b + b, Q23::MIN);
+///     assert_eq!(b + b + b, Q23::MIN);
+///
+///     let s: i32 = a.into_sample();
+///     assert_eq!(s, 0x4000_0000);
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Q23(i32);
+
+/// A Q31 (Q0.31) fixed-point [`Sample`](crate::Sample), with 31 fractional
+/// bits stored in an [`i32`].
+///
+/// ```
+/// use sampara::sample::Q31;
+/// use sampara::IntoSample;
+///
+/// fn main() {
+///     let a = Q31::from_bits(0x4000_0000); // 0.5
+///
+///     assert_eq!(a * a, Q31::from_bits(0x2000_0000));
+///     assert_eq!(Q31::MIN * Q31::MIN, Q31::MAX);
+///     assert_eq!(Q31::MIN - a, Q31::MIN);
+///
+///     let f: f64 = a.into_sample();
+///     assert_eq!(f, 0.5);
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Q31(i32);
+
+macro_rules! impl_q_format {
+    ($($Q:ident: {
+        Bits: $Bits:ty,
+        Wide: $Wide:ty,
+        FRAC_BITS: $FRAC_BITS:expr,
+        MIN: $MIN:expr,
+        MAX: $MAX:expr }),* $(,)?) =>
+    {
+        $(
+            impl $Q {
+                /// The number of fractional bits in this format.
+                pub const FRAC_BITS: u32 = $FRAC_BITS;
+
+                /// The smallest representable value, equal to -1.0.
+                pub const MIN: Self = $Q($MIN);
+
+                /// The largest representable value, one step below 1.0.
+                pub const MAX: Self = $Q($MAX);
+
+                /// The value representing 0.0.
+                pub const ZERO: Self = $Q(0);
+
+                /// Creates a new value from its raw bit representation. Values
+                /// outside of the range of this format are saturated.
+                #[inline]
+                pub const fn from_bits(bits: $Bits) -> Self {
+                    if bits < $MIN {
+                        Self::MIN
+                    } else if bits > $MAX {
+                        Self::MAX
+                    } else {
+                        $Q(bits)
+                    }
+                }
+
+                /// Returns the raw bit representation of this value.
+                #[inline]
+                pub const fn to_bits(self) -> $Bits {
+                    self.0
+                }
+
+                #[inline]
+                const fn saturate(wide: $Wide) -> Self {
+                    if wide < $MIN as $Wide {
+                        Self::MIN
+                    } else if wide > $MAX as $Wide {
+                        Self::MAX
+                    } else {
+                        $Q(wide as $Bits)
+                    }
+                }
+            }
+
+            impl Add for $Q {
+                type Output = Self;
+
+                /// Saturating addition.
+                #[inline]
+                fn add(self, rhs: Self) -> Self {
+                    Self::saturate(self.0 as $Wide + rhs.0 as $Wide)
+                }
+            }
+
+            impl Sub for $Q {
+                type Output = Self;
+
+                /// Saturating subtraction.
+                #[inline]
+                fn sub(self, rhs: Self) -> Self {
+                    Self::saturate(self.0 as $Wide - rhs.0 as $Wide)
+                }
+            }
+
+            impl Neg for $Q {
+                type Output = Self;
+
+                /// Saturating negation, where the negation of [`Self::MIN`] is
+                /// [`Self::MAX`].
+                #[inline]
+                fn neg(self) -> Self {
+                    Self::saturate(-(self.0 as $Wide))
+                }
+            }
+
+            impl Mul for $Q {
+                type Output = Self;
+
+                /// Saturating fractional multiplication, rounded to nearest.
+                #[inline]
+                fn mul(self, rhs: Self) -> Self {
+                    const ROUND: $Wide = 1 << ($FRAC_BITS - 1);
+                    let product = self.0 as $Wide * rhs.0 as $Wide;
+                    Self::saturate((product + ROUND) >> $FRAC_BITS)
+                }
+            }
+        )*
+    };
+}
+
+impl_q_format! {
+    Q15: { Bits: i16, Wide: i32, FRAC_BITS: 15, MIN: i16::MIN, MAX: i16::MAX },
+    Q23: { Bits: i32, Wide: i64, FRAC_BITS: 23, MIN: -(1 << 23), MAX: (1 << 23) - 1 },
+    Q31: { Bits: i32, Wide: i64, FRAC_BITS: 31, MIN: i32::MIN, MAX: i32::MAX },
+}
+
+// Each Q type is converted through the integer type that spans the same
+// amplitude range, so that conversions are as lossless as the integer ones.
+impl Q15 {
+    #[inline]
+    const fn from_carrier(s: i16) -> Self {
+        Q15(s)
+    }
+
+    #[inline]
+    const fn to_carrier(self) -> i16 {
+        self.0
+    }
+}
+
+impl Q23 {
+    #[inline]
+    const fn from_carrier(s: i32) -> Self {
+        Q23(s >> 8)
+    }
+
+    #[inline]
+    const fn to_carrier(self) -> i32 {
+        self.0 << 8
+    }
+}
+
+impl Q31 {
+    #[inline]
+    const fn from_carrier(s: i32) -> Self {
+        Q31(s)
+    }
+
+    #[inline]
+    const fn to_carrier(self) -> i32 {
+        self.0
+    }
+}
+
+/// Implements [`FromSample`] to and from a fixed-point type for each of the
+/// given [`Sample`](crate::Sample) types, by way of its carrier integer type.
+macro_rules! conv_q {
+    ($Q:ty as $Carrier:ty => [$($Tx:ty),+ $(,)?]) => {
+        $(
+            impl FromSample<$Tx> for $Q {
+                #[inline]
+                fn from_sample(s: $Tx) -> Self {
+                    <$Q>::from_carrier(<$Carrier>::from_sample(s))
+                }
+            }
+
+            impl FromSample<$Q> for $Tx {
+                #[inline]
+                fn from_sample(s: $Q) -> Self {
+                    <$Tx>::from_sample(s.to_carrier())
+                }
+            }
+        )+
+    };
+}
+
+conv_q!(Q15 as i16 => [i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, f32, f64, MuLaw, ALaw]);
+conv_q!(Q23 as i32 => [i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, f32, f64, MuLaw, ALaw]);
+conv_q!(Q31 as i32 => [i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, f32, f64, MuLaw, ALaw]);
+
+/// Implements [`FromSample`] between fixed-point types.
+macro_rules! conv_q_to_q {
+    ($($Source:ty as $SourceCarrier:ty => $Target:ty as $TargetCarrier:ty),* $(,)?) => {
+        $(
+            impl FromSample<$Source> for $Target {
+                #[inline]
+                fn from_sample(s: $Source) -> Self {
+                    <$Target>::from_carrier(<$TargetCarrier>::from_sample(s.to_carrier()))
+                }
+            }
+        )*
+    };
+}
+
+conv_q_to_q!(
+    Q15 as i16 => Q15 as i16,
+    Q15 as i16 => Q23 as i32,
+    Q15 as i16 => Q31 as i32,
+    Q23 as i32 => Q15 as i16,
+    Q23 as i32 => Q23 as i32,
+    Q23 as i32 => Q31 as i32,
+    Q31 as i32 => Q15 as i16,
+    Q31 as i32 => Q23 as i32,
+    Q31 as i32 => Q31 as i32,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::sample::{IntoSample, Sample};
+
+    #[test]
+    fn saturating_arithmetic() {
+        assert_eq!(Q15::MAX + Q15::MAX, Q15::MAX);
+        assert_eq!(Q15::MIN + Q15::MIN, Q15::MIN);
+        assert_eq!(Q15::MIN - Q15::MAX, Q15::MIN);
+        assert_eq!(-Q15::MIN, Q15::MAX);
+        assert_eq!(Q15::MIN * Q15::MIN, Q15::MAX);
+        assert_eq!(Q15::MIN * Q15::MAX, -Q15::MAX);
+
+        assert_eq!(Q23::MAX + Q23::MAX, Q23::MAX);
+        assert_eq!(Q23::MIN - Q23::MAX, Q23::MIN);
+        assert_eq!(-Q23::MIN, Q23::MAX);
+        assert_eq!(Q23::MIN * Q23::MIN, Q23::MAX);
+
+        assert_eq!(Q31::MAX + Q31::MAX, Q31::MAX);
+        assert_eq!(Q31::MIN - Q31::MAX, Q31::MIN);
+        assert_eq!(-Q31::MIN, Q31::MAX);
+        assert_eq!(Q31::MIN * Q31::MIN, Q31::MAX);
+    }
+
+    #[test]
+    fn from_bits_saturates() {
+        assert_eq!(Q23::from_bits(i32::MAX), Q23::MAX);
+        assert_eq!(Q23::from_bits(i32::MIN), Q23::MIN);
+        assert_eq!(Q23::MAX.to_bits(), 0x7F_FFFF);
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Q15::EQUILIBRIUM, Q15::ZERO);
+
+        let q: Q15 = 0.5f32.into_sample();
+        assert_eq!(q.to_bits(), 0x4000);
+
+        let q: Q23 = 0.5f64.into_sample();
+        assert_eq!(q.to_bits(), 0x40_0000);
+
+        let q: Q31 = (-1.0f64).into_sample();
+        assert_eq!(q, Q31::MIN);
+
+        let q: Q15 = 0u8.into_sample();
+        assert_eq!(q, Q15::MIN);
+
+        let q: Q31 = Q15::from_bits(0x4000).into_sample();
+        assert_eq!(q.to_bits(), 0x4000_0000);
+
+        let q: Q15 = Q23::from_bits(0x12_3456).into_sample();
+        assert_eq!(q.to_bits(), 0x1234);
+
+        let s: u8 = Q31::ZERO.into_sample();
+        assert_eq!(s, 128);
+
+        let s: f32 = Q23::MIN.into_float();
+        assert_eq!(s, -1.0);
+    }
+}
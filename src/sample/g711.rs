@@ -0,0 +1,384 @@
+//! Companded 8-bit sample types, as specified by ITU-T G.711.
+//!
+//! Both [`MuLaw`] and [`ALaw`] store a single encoded byte, and are converted
+//! to and from other [`Sample`] types by way of 16-bit linear PCM. Encoding
+//! follows the segment tables of the G.711 reference implementation exactly:
+//! mu-law operates on the top 14 bits of the linear value, and A-law on the
+//! top 13 bits.
+//!
+//! [`Sample`]: crate::Sample
+
+use core::cmp::Ordering;
+
+use crate::sample::FromSample;
+
+/// Bias that is added to the magnitude of a linear value before mu-law
+/// encoding, in order to shift the segment boundaries.
+const MU_LAW_BIAS: i16 = 0x84;
+
+/// The largest 14-bit magnitude that can be mu-law encoded without clipping.
+const MU_LAW_CLIP: i16 = 8159;
+
+/// Upper bounds (inclusive) of each of the eight mu-law segments.
+const MU_LAW_SEG_END: [i16; 8] = [0x3F, 0x7F, 0xFF, 0x1FF, 0x3FF, 0x7FF, 0xFFF, 0x1FFF];
+
+/// Upper bounds (inclusive) of each of the eight A-law segments.
+const A_LAW_SEG_END: [i16; 8] = [0x1F, 0x3F, 0x7F, 0xFF, 0x1FF, 0x3FF, 0x7FF, 0xFFF];
+
+/// A G.711 mu-law encoded [`Sample`](crate::Sample), as used in North
+/// American and Japanese telephony.
+///
+/// ```
+/// use sampara::sample::MuLaw;
+/// use sampara::{signal, IntoSample, Sample, Signal};
+///
+/// fn main() {
+///     assert_eq!(MuLaw::EQUILIBRIUM.to_bits(), 0xFF);
+///
+///     let s: MuLaw = 0i16.into_sample();
+///     assert_eq!(s.to_bits(), 0xFF);
+///
+///     let s: i16 = MuLaw::from_bits(0x80).into_sample();
+///     assert_eq!(s, 32124);
+///
+///     let s: f32 = MuLaw::from_bits(0x00).into_sample();
+///     assert_eq!(s, -0.9803467);
+///
+///     // Raw telephony payloads can be read directly as a signal.
+///     let payload = [0xFF, 0x80, 0x00, 0x7F];
+///     let mut signal =
+///         signal::from_samples_fixed::<_, 2>(payload.into_iter().map(MuLaw::from_bits));
+///
+///     assert_eq!(signal.next(), Some([MuLaw::EQUILIBRIUM, MuLaw::from_bits(0x80)].into()));
+///     assert_eq!(signal.next(), Some([MuLaw::from_bits(0x00), MuLaw::EQUILIBRIUM].into()));
+///     assert_eq!(signal.next(), None);
+/// }
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct MuLaw(u8);
+
+impl MuLaw {
+    /// The encoded value of a linear zero.
+    pub const EQUILIBRIUM: Self = MuLaw(0xFF);
+
+    /// Creates a [`MuLaw`] from its raw encoded byte.
+    #[inline]
+    pub const fn from_bits(bits: u8) -> Self {
+        MuLaw(bits)
+    }
+
+    /// Returns the raw encoded byte of this [`MuLaw`].
+    #[inline]
+    pub const fn to_bits(self) -> u8 {
+        self.0
+    }
+
+    /// Encodes a 16-bit linear PCM value. Only the top 14 bits are
+    /// significant, and magnitudes beyond the mu-law range are clipped.
+    ///
+    /// ```
+    /// use sampara::sample::MuLaw;
+    ///
+    /// fn main() {
+    ///     assert_eq!(MuLaw::from_linear(0).to_bits(), 0xFF);
+    ///     assert_eq!(MuLaw::from_linear(-1).to_bits(), 0x7E);
+    ///     assert_eq!(MuLaw::from_linear(i16::MAX).to_bits(), 0x80);
+    ///     assert_eq!(MuLaw::from_linear(i16::MIN).to_bits(), 0x00);
+    /// }
+    /// ```
+    pub const fn from_linear(pcm: i16) -> Self {
+        let mut val = pcm >> 2;
+
+        let mask = if val < 0 {
+            val = -val;
+            0x7F
+        } else {
+            0xFF
+        };
+
+        if val > MU_LAW_CLIP {
+            val = MU_LAW_CLIP;
+        }
+        val += MU_LAW_BIAS >> 2;
+
+        let mut seg = 0;
+        while seg < 8 && val > MU_LAW_SEG_END[seg] {
+            seg += 1;
+        }
+
+        if seg >= 8 {
+            return MuLaw(0x7F ^ mask);
+        }
+
+        let uval = ((seg as u8) << 4) | ((val >> (seg + 1)) & 0xF) as u8;
+        MuLaw(uval ^ mask)
+    }
+
+    /// Decodes this [`MuLaw`] into a 16-bit linear PCM value.
+    ///
+    /// ```
+    /// use sampara::sample::MuLaw;
+    ///
+    /// fn main() {
+    ///     assert_eq!(MuLaw::from_bits(0xFF).to_linear(), 0);
+    ///     assert_eq!(MuLaw::from_bits(0x7F).to_linear(), 0);
+    ///     assert_eq!(MuLaw::from_bits(0x80).to_linear(), 32124);
+    ///     assert_eq!(MuLaw::from_bits(0x00).to_linear(), -32124);
+    /// }
+    /// ```
+    pub const fn to_linear(self) -> i16 {
+        let u = !self.0;
+
+        let mut t = (((u & 0x0F) as i16) << 3) + MU_LAW_BIAS;
+        t <<= (u & 0x70) >> 4;
+
+        if u & 0x80 != 0 {
+            MU_LAW_BIAS - t
+        } else {
+            t - MU_LAW_BIAS
+        }
+    }
+}
+
+/// A G.711 A-law encoded [`Sample`](crate::Sample), as used in European and
+/// international telephony.
+///
+/// ```
+/// use sampara::sample::ALaw;
+/// use sampara::{Sample, IntoSample};
+///
+/// fn main() {
+///     assert_eq!(ALaw::EQUILIBRIUM.to_bits(), 0xD5);
+///
+///     let s: ALaw = 0i16.into_sample();
+///     assert_eq!(s.to_bits(), 0xD5);
+///
+///     let s: i16 = ALaw::from_bits(0xAA).into_sample();
+///     assert_eq!(s, 32256);
+///
+///     let s: f32 = ALaw::from_bits(0x2A).into_sample();
+///     assert_eq!(s, -0.984375);
+/// }
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct ALaw(u8);
+
+impl ALaw {
+    /// The encoded value of a linear zero.
+    pub const EQUILIBRIUM: Self = ALaw(0xD5);
+
+    /// Creates an [`ALaw`] from its raw encoded byte.
+    #[inline]
+    pub const fn from_bits(bits: u8) -> Self {
+        ALaw(bits)
+    }
+
+    /// Returns the raw encoded byte of this [`ALaw`].
+    #[inline]
+    pub const fn to_bits(self) -> u8 {
+        self.0
+    }
+
+    /// Encodes a 16-bit linear PCM value. Only the top 13 bits are
+    /// significant, and magnitudes beyond the A-law range are clipped.
+    ///
+    /// ```
+    /// use sampara::sample::ALaw;
+    ///
+    /// fn main() {
+    ///     assert_eq!(ALaw::from_linear(0).to_bits(), 0xD5);
+    ///     assert_eq!(ALaw::from_linear(-1).to_bits(), 0x55);
+    ///     assert_eq!(ALaw::from_linear(i16::MAX).to_bits(), 0xAA);
+    ///     assert_eq!(ALaw::from_linear(i16::MIN).to_bits(), 0x2A);
+    /// }
+    /// ```
+    pub const fn from_linear(pcm: i16) -> Self {
+        let mut val = pcm >> 3;
+
+        let mask = if val >= 0 {
+            0xD5
+        } else {
+            val = -val - 1;
+            0x55
+        };
+
+        let mut seg = 0;
+        while seg < 8 && val > A_LAW_SEG_END[seg] {
+            seg += 1;
+        }
+
+        if seg >= 8 {
+            return ALaw(0x7F ^ mask);
+        }
+
+        let mantissa = if seg < 2 { val >> 1 } else { val >> seg };
+        let aval = ((seg as u8) << 4) | (mantissa & 0xF) as u8;
+        ALaw(aval ^ mask)
+    }
+
+    /// Decodes this [`ALaw`] into a 16-bit linear PCM value.
+    ///
+    /// ```
+    /// use sampara::sample::ALaw;
+    ///
+    /// fn main() {
+    ///     assert_eq!(ALaw::from_bits(0xD5).to_linear(), 8);
+    ///     assert_eq!(ALaw::from_bits(0x55).to_linear(), -8);
+    ///     assert_eq!(ALaw::from_bits(0xAA).to_linear(), 32256);
+    ///     assert_eq!(ALaw::from_bits(0x2A).to_linear(), -32256);
+    /// }
+    /// ```
+    pub const fn to_linear(self) -> i16 {
+        let a = self.0 ^ 0x55;
+
+        let mut t = ((a & 0x0F) as i16) << 4;
+        let seg = (a & 0x70) >> 4;
+
+        match seg {
+            0 => t += 8,
+            1 => t += 0x108,
+            _ => {
+                t += 0x108;
+                t <<= seg - 1;
+            }
+        }
+
+        if a & 0x80 != 0 { t } else { -t }
+    }
+}
+
+// Equality and ordering are defined in terms of the decoded linear values, so
+// that both encodings of zero in mu-law compare equal, and so that the ordering
+// of samples follows their amplitudes rather than their encoded bytes.
+macro_rules! impl_linear_cmp {
+    ($($T:ty),* $(,)?) => {
+        $(
+            impl PartialEq for $T {
+                #[inline]
+                fn eq(&self, other: &Self) -> bool {
+                    self.to_linear() == other.to_linear()
+                }
+            }
+
+            impl PartialOrd for $T {
+                #[inline]
+                fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                    self.to_linear().partial_cmp(&other.to_linear())
+                }
+            }
+        )*
+    };
+}
+
+impl_linear_cmp!(MuLaw, ALaw);
+
+/// Implements [`FromSample`] to and from a companded type for each of the
+/// given [`Sample`](crate::Sample) types, by way of 16-bit linear PCM.
+macro_rules! conv_companded {
+    ($C:ty => [$($Tx:ty),+ $(,)?]) => {
+        $(
+            impl FromSample<$Tx> for $C {
+                #[inline]
+                fn from_sample(s: $Tx) -> Self {
+                    <$C>::from_linear(i16::from_sample(s))
+                }
+            }
+
+            impl FromSample<$C> for $Tx {
+                #[inline]
+                fn from_sample(s: $C) -> Self {
+                    <$Tx>::from_sample(s.to_linear())
+                }
+            }
+        )+
+    };
+}
+
+conv_companded!(MuLaw => [i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, f32, f64]);
+conv_companded!(ALaw => [i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, f32, f64]);
+
+impl FromSample<MuLaw> for MuLaw {
+    #[inline]
+    fn from_sample(s: MuLaw) -> Self {
+        s
+    }
+}
+
+impl FromSample<ALaw> for ALaw {
+    #[inline]
+    fn from_sample(s: ALaw) -> Self {
+        s
+    }
+}
+
+impl FromSample<ALaw> for MuLaw {
+    #[inline]
+    fn from_sample(s: ALaw) -> Self {
+        MuLaw::from_linear(s.to_linear())
+    }
+}
+
+impl FromSample<MuLaw> for ALaw {
+    #[inline]
+    fn from_sample(s: MuLaw) -> Self {
+        ALaw::from_linear(s.to_linear())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mu_law_round_trip() {
+        for bits in 0..=u8::MAX {
+            let s = MuLaw::from_bits(bits);
+            let reencoded = MuLaw::from_linear(s.to_linear());
+
+            // Negative zero is the only code that does not survive a round
+            // trip, as it decodes to the same linear value as positive zero.
+            if bits == 0x7F {
+                assert_eq!(reencoded.to_bits(), 0xFF);
+            } else {
+                assert_eq!(reencoded.to_bits(), bits);
+            }
+        }
+    }
+
+    #[test]
+    fn a_law_round_trip() {
+        for bits in 0..=u8::MAX {
+            let s = ALaw::from_bits(bits);
+            assert_eq!(ALaw::from_linear(s.to_linear()).to_bits(), bits);
+        }
+    }
+
+    #[test]
+    fn monotonic_encoding() {
+        let mut prev_mu = MuLaw::from_linear(i16::MIN);
+        let mut prev_a = ALaw::from_linear(i16::MIN);
+
+        for pcm in i16::MIN..=i16::MAX {
+            let mu = MuLaw::from_linear(pcm);
+            let a = ALaw::from_linear(pcm);
+
+            assert!(mu >= prev_mu);
+            assert!(a >= prev_a);
+
+            prev_mu = mu;
+            prev_a = a;
+        }
+    }
+
+    #[test]
+    fn transcoding() {
+        for bits in 0..=u8::MAX {
+            let a = ALaw::from_bits(bits);
+            let mu: MuLaw = MuLaw::from_sample(a);
+            let linear_diff = (a.to_linear() as i32 - mu.to_linear() as i32).abs();
+
+            // Mu-law has coarser steps than A-law at high amplitudes.
+            assert!(linear_diff <= 1024, "{bits:#04X}: {linear_diff}");
+        }
+    }
+}
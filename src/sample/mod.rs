@@ -1,10 +1,18 @@
+mod bytes;
 mod conv;
+mod fixed_point;
+mod g711;
 
+pub use self::bytes::{BigEndian, ByteOrder, ByteSample, LittleEndian, NativeEndian, ReadSamples};
+pub(crate) use self::conv::to_f64;
 pub use self::conv::{FromSample, IntoSample};
+pub use self::fixed_point::{Q15, Q23, Q31};
+pub use self::g711::{ALaw, MuLaw};
 
 use core::fmt::Debug;
+use core::ops::{Add, Neg, Sub};
 
-use num_traits::{Float, FloatConst, Signed};
+use num_traits::{Float, FloatConst};
 
 /// A trait for working generically across different sample format types, both
 /// in terms of representation (integral versus floating-point) and bitsize.
@@ -172,14 +180,71 @@ impl_sample! {
     f64:  { Signed: f64,  Float: f64, EQUILIBRIUM: 0.0 },
 }
 
-/// Integral and floating-point [`Sample`] types whose equilibrium is at 0.
+// Implements [`Sample`] for the G.711 companded types.
+impl_sample! {
+    MuLaw: { Signed: i16, Float: f32, EQUILIBRIUM: MuLaw::EQUILIBRIUM },
+    ALaw:  { Signed: i16, Float: f32, EQUILIBRIUM: ALaw::EQUILIBRIUM },
+}
+
+// Implements [`Sample`] for the fixed-point Q format types.
+impl_sample! {
+    Q15: { Signed: Q15, Float: f32, EQUILIBRIUM: Q15::ZERO },
+    Q23: { Signed: Q23, Float: f32, EQUILIBRIUM: Q23::ZERO },
+    Q31: { Signed: Q31, Float: f64, EQUILIBRIUM: Q31::ZERO },
+}
+
+/// Integral, fixed-point and floating-point [`Sample`] types whose equilibrium
+/// is at 0.
 ///
 /// [`Sample`]s often need to be converted to some mutual [`SignedSample`] type
 /// for addition.
-pub trait SignedSample: Sample<Signed = Self> + Signed {}
+///
+/// Only the arithmetic needed to offset amplitudes is required, rather than
+/// the full [`num_traits::Signed`] numeric tower, so that fractional formats
+/// such as [`Q15`] (which cannot represent 1.0) can be used.
+pub trait SignedSample:
+    Sample<Signed = Self> + Add<Output = Self> + Sub<Output = Self> + Neg<Output = Self>
+{
+    /// Adds a signed amplitude, saturating at the bounds of the type instead
+    /// of overflowing.
+    ///
+    /// ```
+    /// use sampara::sample::SignedSample;
+    ///
+    /// fn main() {
+    ///     assert_eq!(i16::MAX.saturating_add_amp(1), i16::MAX);
+    ///     assert_eq!(0.25f32.saturating_add_amp(0.5), 0.75);
+    /// }
+    /// ```
+    fn saturating_add_amp(self, amp: Self) -> Self;
+}
+
+macro_rules! impl_signed_sample {
+    ($($T:ty => $saturating_add:path),* $(,)?) => {
+        $(
+            impl SignedSample for $T {
+                #[inline]
+                fn saturating_add_amp(self, amp: Self) -> Self {
+                    $saturating_add(self, amp)
+                }
+            }
+        )*
+    };
+}
 
-macro_rules! impl_signed_sample { ($($T:ty)*) => { $( impl SignedSample for $T {} )* } }
-impl_signed_sample!(i8 i16 i32 i64 i128 f32 f64);
+// Floats do not overflow, and the Q formats already saturate on addition.
+impl_signed_sample! {
+    i8 => i8::saturating_add,
+    i16 => i16::saturating_add,
+    i32 => i32::saturating_add,
+    i64 => i64::saturating_add,
+    i128 => i128::saturating_add,
+    f32 => Add::add,
+    f64 => Add::add,
+    Q15 => Add::add,
+    Q23 => Add::add,
+    Q31 => Add::add,
+}
 
 /// Floating-point [`Sample`] types, represented as values in the interval
 /// [-1.0, 1.0).
@@ -187,7 +252,12 @@ impl_signed_sample!(i8 i16 i32 i64 i128 f32 f64);
 /// [`Sample`]s often need to be converted to some mutual [`FloatSample`] type
 /// for scaling.
 pub trait FloatSample:
-    Sample<Signed = Self, Float = Self> + SignedSample /*+ Duplex<f32> + Duplex<f64>*/ + Float + FloatConst
+    Sample<Signed = Self, Float = Self>
+    + SignedSample
+    + FromSample<f32>
+    + FromSample<f64>
+    + Float
+    + FloatConst
 {
 }
 
@@ -0,0 +1,482 @@
+use crate::frame::{Dynamic, Frame};
+use crate::processor::Processor;
+use crate::sample::{FromSample, Sample};
+use crate::signal::{ExactSizeSignal, FloatOf, SeekableSignal, Signal, min_len_hint};
+
+/// A [`Signal`] that passes each [`Frame`] of an underlying [`Signal`] through
+/// a [`Processor`], one [`Frame`] at a time.
+pub struct Through<S, P>
+where
+    S: Signal,
+    P: Processor<Frame = S::Frame>,
+{
+    pub(super) signal: S,
+    pub(super) processor: P,
+}
+
+impl<S, P> Through<S, P>
+where
+    S: Signal,
+    P: Processor<Frame = S::Frame>,
+{
+    /// Returns a reference to the wrapped [`Processor`].
+    pub fn processor(&self) -> &P {
+        &self.processor
+    }
+
+    /// Returns a mutable reference to the wrapped [`Processor`].
+    pub fn processor_mut(&mut self) -> &mut P {
+        &mut self.processor
+    }
+}
+
+impl<S, P> Signal for Through<S, P>
+where
+    S: Signal,
+    P: Processor<Frame = S::Frame>,
+{
+    type Frame = S::Frame;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Frame> {
+        let mut frame = self.signal.next()?;
+        self.processor.process(core::slice::from_mut(&mut frame));
+        Some(frame)
+    }
+
+    #[inline]
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        self.signal.len_hint()
+    }
+}
+
+impl<S, P> ExactSizeSignal for Through<S, P>
+where
+    S: ExactSizeSignal,
+    P: Processor<Frame = S::Frame>,
+{
+}
+
+/// A [`Signal`] with a [`Self::peek`] method, which returns the next
+/// [`Frame`] without advancing.
+///
+/// This is created by [`Signal::peekable`].
+pub struct Peekable<S>
+where
+    S: Signal,
+{
+    pub(super) signal: S,
+    // The next frame, if it has been peeked. `Some(None)` if the signal has
+    // run out.
+    pub(super) peeked: Option<Option<S::Frame>>,
+}
+
+impl<S> Peekable<S>
+where
+    S: Signal,
+{
+    /// Returns a reference to the next [`Frame`], without advancing.
+    pub fn peek(&mut self) -> Option<&S::Frame> {
+        let signal = &mut self.signal;
+        self.peeked.get_or_insert_with(|| signal.next()).as_ref()
+    }
+
+    /// Returns a mutable reference to the next [`Frame`], without advancing.
+    pub fn peek_mut(&mut self) -> Option<&mut S::Frame> {
+        let signal = &mut self.signal;
+        self.peeked.get_or_insert_with(|| signal.next()).as_mut()
+    }
+}
+
+impl<S> Signal for Peekable<S>
+where
+    S: Signal,
+{
+    type Frame = S::Frame;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Frame> {
+        match self.peeked.take() {
+            Some(peeked) => peeked,
+            None => self.signal.next(),
+        }
+    }
+
+    #[inline]
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        match self.peeked {
+            Some(None) => (0, Some(0)),
+            Some(Some(_)) => {
+                let (lower, upper) = self.signal.len_hint();
+                (
+                    lower.saturating_add(1),
+                    upper.and_then(|n| n.checked_add(1)),
+                )
+            }
+            None => self.signal.len_hint(),
+        }
+    }
+}
+
+impl<S> ExactSizeSignal for Peekable<S> where S: ExactSizeSignal {}
+
+impl<S> SeekableSignal for Peekable<S>
+where
+    S: SeekableSignal,
+{
+    fn seek(&mut self, position: usize) {
+        self.peeked = None;
+        self.signal.seek(position);
+    }
+
+    fn position(&self) -> usize {
+        match self.peeked {
+            Some(Some(_)) => self.signal.position() - 1,
+            _ => self.signal.position(),
+        }
+    }
+}
+
+/// A [`Signal`] that maps each [`Frame`] of an underlying [`Signal`] through a
+/// closure.
+///
+/// This is created by [`Signal::map`].
+pub struct Map<S, M> {
+    pub(super) signal: S,
+    pub(super) map: M,
+}
+
+impl<S, M, F> Signal for Map<S, M>
+where
+    S: Signal,
+    M: FnMut(S::Frame) -> F,
+    F: Frame,
+{
+    type Frame = F;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Frame> {
+        self.signal.next().map(&mut self.map)
+    }
+
+    #[inline]
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        self.signal.len_hint()
+    }
+}
+
+impl<S, M, F> ExactSizeSignal for Map<S, M>
+where
+    S: ExactSizeSignal,
+    M: FnMut(S::Frame) -> F,
+    F: Frame,
+{
+}
+
+impl<S, M, F> SeekableSignal for Map<S, M>
+where
+    S: SeekableSignal,
+    M: FnMut(S::Frame) -> F,
+    F: Frame,
+{
+    fn seek(&mut self, position: usize) {
+        self.signal.seek(position);
+    }
+
+    fn position(&self) -> usize {
+        self.signal.position()
+    }
+}
+
+/// A [`Signal`] that multiplies the amplitude of each [`Sample`] of an
+/// underlying [`Signal`].
+///
+/// This is created by [`Signal::mul_amp`].
+pub struct MulAmp<S>
+where
+    S: Signal,
+{
+    pub(super) signal: S,
+    pub(super) amp: FloatOf<S>,
+}
+
+impl<S> MulAmp<S>
+where
+    S: Signal,
+{
+    pub fn amp(&self) -> FloatOf<S> {
+        self.amp
+    }
+
+    pub fn set_amp(&mut self, amp: FloatOf<S>) {
+        self.amp = amp;
+    }
+}
+
+impl<S> Signal for MulAmp<S>
+where
+    S: Signal,
+    <S::Frame as Frame>::Sample: FromSample<FloatOf<S>>,
+{
+    type Frame = S::Frame;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Frame> {
+        let mut frame = self.signal.next()?;
+        for sample in frame.iter_mut() {
+            *sample = FromSample::from_sample(sample.into_float().mul_amp(self.amp));
+        }
+
+        Some(frame)
+    }
+
+    #[inline]
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        self.signal.len_hint()
+    }
+}
+
+impl<S> ExactSizeSignal for MulAmp<S>
+where
+    S: ExactSizeSignal,
+    <S::Frame as Frame>::Sample: FromSample<FloatOf<S>>,
+{
+}
+
+impl<S> SeekableSignal for MulAmp<S>
+where
+    S: SeekableSignal,
+    <S::Frame as Frame>::Sample: FromSample<FloatOf<S>>,
+{
+    fn seek(&mut self, position: usize) {
+        self.signal.seek(position);
+    }
+
+    fn position(&self) -> usize {
+        self.signal.position()
+    }
+}
+
+/// A [`Signal`] that yields at most a given number of [`Frame`]s from an
+/// underlying [`Signal`].
+///
+/// When seeking, positions are counted from the first [`Frame`] of this
+/// [`Signal`], and are limited to its length.
+///
+/// This is created by [`Signal::take`].
+pub struct Take<S> {
+    pub(super) signal: S,
+    pub(super) len: usize,
+    pub(super) taken: usize,
+}
+
+impl<S> Signal for Take<S>
+where
+    S: Signal,
+{
+    type Frame = S::Frame;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Frame> {
+        if self.taken >= self.len {
+            return None;
+        }
+
+        let frame = self.signal.next()?;
+        self.taken += 1;
+        Some(frame)
+    }
+
+    #[inline]
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        let left = self.len - self.taken;
+        min_len_hint(self.signal.len_hint(), (left, Some(left)))
+    }
+}
+
+impl<S> ExactSizeSignal for Take<S> where S: ExactSizeSignal {}
+
+impl<S> SeekableSignal for Take<S>
+where
+    S: SeekableSignal,
+{
+    fn seek(&mut self, position: usize) {
+        let start = self.signal.position() - self.taken;
+        let position = position.min(self.len);
+
+        // The underlying signal may be shorter than the take, and stop short
+        // of the position.
+        self.signal.seek(start + position);
+        self.taken = self.signal.position() - start;
+    }
+
+    fn position(&self) -> usize {
+        self.taken
+    }
+}
+
+/// An [`Iterator`] that yields each [`Frame`] of an underlying [`Signal`].
+///
+/// This is created by [`Signal::into_frames`].
+pub struct IntoFrames<S> {
+    pub(super) signal: S,
+}
+
+impl<S> IntoFrames<S> {
+    /// Returns the underlying [`Signal`].
+    pub fn into_signal(self) -> S {
+        self.signal
+    }
+}
+
+impl<S> Iterator for IntoFrames<S>
+where
+    S: Signal,
+{
+    type Item = S::Frame;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.signal.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.signal.len_hint()
+    }
+}
+
+impl<S> ExactSizeIterator for IntoFrames<S> where S: ExactSizeSignal {}
+
+/// An [`Iterator`] that yields each [`Sample`] of an underlying [`Signal`],
+/// interleaved.
+///
+/// This is created by [`Signal::into_samples`].
+pub struct IntoSamples<S>
+where
+    S: Signal,
+{
+    pub(super) signal: S,
+    pub(super) frame: Option<<S::Frame as IntoIterator>::IntoIter>,
+}
+
+impl<S> Iterator for IntoSamples<S>
+where
+    S: Signal,
+{
+    type Item = <S::Frame as Frame>::Sample;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(sample) = self.frame.as_mut().and_then(Iterator::next) {
+                return Some(sample);
+            }
+
+            self.frame = Some(self.signal.next()?.into_iter());
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self
+            .frame
+            .as_ref()
+            .map_or((0, Some(0)), Iterator::size_hint);
+
+        // The number of channels of the frames to come is not known.
+        match self.signal.len_hint() {
+            (_, Some(0)) => (lower, upper),
+            _ => (lower, None),
+        }
+    }
+}
+
+/// A boxed [`Signal`] that yields [`Dynamic`] frames, with both its type and
+/// the number of channels of its [`Frame`]s erased.
+///
+/// Unlike `Box<dyn Signal<Frame = F>>`, which still fixes the [`Frame`] type,
+/// this only fixes the [`Sample`] type, so a [`DynSignal`] can hold mono,
+/// stereo and surround [`Signal`]s alike. [`Frame`]s that are not already
+/// [`Dynamic`] are converted with [`Frame::into_dynamic`].
+///
+/// This is created by [`Signal::into_dyn`].
+///
+/// ```
+/// use sampara::{signal, Signal};
+/// use sampara::signal::DynSignal;
+///
+/// fn main() {
+///     let input = |channels: usize| -> DynSignal<'static, f32> {
+///         let samples = vec![0.5; channels * 2];
+///         match channels {
+///             1 => signal::from_samples_fixed::<_, 1>(samples).into_dyn(),
+///             2 => signal::from_samples_fixed::<_, 2>(samples).into_dyn(),
+///             n => signal::from_samples_dynamic(samples, n).into_dyn(),
+///         }
+///     };
+///
+///     let mut chains: Vec<_> = [1, 2, 6]
+///         .into_iter()
+///         .map(|channels| input(channels).mul_amp(0.5).into_dyn())
+///         .collect();
+///
+///     assert_eq!(chains[0].next(), Some(vec![0.25].into()));
+///     assert_eq!(chains[1].next(), Some(vec![0.25; 2].into()));
+///     assert_eq!(chains[2].next(), Some(vec![0.25; 6].into()));
+/// }
+/// ```
+pub struct DynSignal<'a, S>
+where
+    S: Sample,
+{
+    signal: Box<dyn Signal<Frame = Dynamic<S>> + 'a>,
+}
+
+impl<'a, S> DynSignal<'a, S>
+where
+    S: Sample,
+{
+    /// Creates a new [`DynSignal`] from any [`Signal`] with [`Sample`]s of
+    /// type `S`.
+    pub fn new<G>(signal: G) -> Self
+    where
+        G: Signal + 'a,
+        G::Frame: Frame<Sample = S>,
+    {
+        Self {
+            signal: Box::new(signal.map(Frame::into_dynamic)),
+        }
+    }
+
+    /// Returns the underlying boxed [`Signal`].
+    pub fn into_inner(self) -> Box<dyn Signal<Frame = Dynamic<S>> + 'a> {
+        self.signal
+    }
+}
+
+impl<'a, S> Signal for DynSignal<'a, S>
+where
+    S: Sample,
+{
+    type Frame = Dynamic<S>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Frame> {
+        self.signal.next()
+    }
+
+    #[inline]
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        self.signal.len_hint()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Frame> {
+        self.signal.nth(n)
+    }
+
+    #[inline]
+    fn advance_by(&mut self, n: usize) -> Result<(), usize> {
+        self.signal.advance_by(n)
+    }
+}
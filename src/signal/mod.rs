@@ -1,103 +1,1419 @@
 mod adapters;
 mod sources;
 
-use crate::{Sample, frame::Frame, signal::sources::{FromFn, FromFrames, FromSamplesDynamic, FromSamplesFixed}};
+pub use self::adapters::{
+    DynSignal, IntoFrames, IntoSamples, Map, MulAmp, Peekable, Take, Through,
+};
+pub use self::sources::{
+    Constant, Empty, FromFn, FromFrames, FromSamplesDynamic, FromSamplesFixed, FromSlice,
+};
+
+use std::io::{Read, Write};
+use std::marker::PhantomData;
+
+use crate::{
+    Sample,
+    channel::{ChannelMatrix, Layout, Remix, WithLayout},
+    delay::Delay,
+    dynamics::{Dynamics, DynamicsProcessor, Sidechain},
+    envelope::{Envelope, Mode},
+    fir::{Convolve, ImpulseResponse},
+    frame::{Fixed, Frame},
+    modulation::{
+        Chorus, ChorusParams, Flanger, FlangerParams, Phaser, PhaserParams, Tremolo, TremoloParams,
+        Vibrato, VibratoParams,
+    },
+    processor::Processor,
+    reverb::{Reverb, ReverbProcessor},
+    sample::{ByteOrder, ByteSample, FloatSample, FromSample, ReadSamples},
+    split::{self as splitting, Split, SyncSplit},
+    stereo::{AutoPan, AutoPanParams, Balance, LeftRight, MidSide, Pan, PanLaw, Width},
+    waveshape::{Curve, Waveshape},
+};
+
+/// The [`FloatSample`](crate::sample::FloatSample) type that the [`Sample`]s
+/// of a [`Signal`] are converted to for processing.
+pub(crate) type FloatOf<S> = <<<S as Signal>::Frame as Frame>::Sample as Sample>::Float;
 
 /// Types that yield a sequence of [`Frame`]s, representing an audio signal.
 ///
-/// This trait is inspired by the [`Iterator`] trait and has similar methods
-/// and adaptors, but with a DSP-related focus.
-pub trait Signal {
-    /// The [`Frame`] type returned by this [`Signal`].
-    type Frame: Frame;
+/// This trait is inspired by the [`Iterator`] trait and has similar methods
+/// and adaptors, but with a DSP-related focus.
+pub trait Signal {
+    /// The [`Frame`] type returned by this [`Signal`].
+    type Frame: Frame;
+
+    /// Advances [`Self`] and returns the next [`Frame`], or [`None`] if there
+    /// are no more to yield.
+    fn next(&mut self) -> Option<Self::Frame>;
+
+    /// Returns the bounds on the number of [`Frame`]s left to yield, like
+    /// [`Iterator::size_hint`].
+    ///
+    /// The lower bound is a number of [`Frame`]s that will certainly be
+    /// yielded, and the upper bound is [`None`] if it is unknown or larger
+    /// than [`usize::MAX`], as for infinite [`Signal`]s. The default returns
+    /// `(0, None)`, which is correct for any [`Signal`].
+    ///
+    /// ```
+    /// use sampara::{signal, Fixed, Signal};
+    ///
+    /// fn main() {
+    ///     let mut signal = signal::from_samples_fixed::<_, 2>(vec![0; 7]);
+    ///     assert_eq!(signal.len_hint(), (3, Some(3)));
+    ///
+    ///     signal.next();
+    ///     assert_eq!(signal.len_hint(), (2, Some(2)));
+    ///
+    ///     let signal = signal::from_fn(|| Some(Fixed::from([0])));
+    ///     assert_eq!(signal.len_hint(), (0, None));
+    /// }
+    /// ```
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        (0, None)
+    }
+
+    /// Similar to [`Self::next`], but will always yield a [`Frame`]. Yields
+    /// [`Frame::EQUILIBRIUM`] if there are no more actual [`Frame`]s to yield.
+    fn sig_next(&mut self) -> Self::Frame {
+        self.next().unwrap_or(Self::Frame::equil())
+    }
+
+    /// Returns the `n`th [`Frame`] from this [`Signal`], starting at 0. This
+    /// will advance the [`Signal`], so multiple calls to `nth` with the same
+    /// `n` will give different results.
+    ///
+    /// ```
+    /// use sampara::{signal, Signal};
+    ///
+    /// fn main() {
+    ///     let mut signal = signal::from_samples_fixed::<_, 1>(0..=9);
+    ///
+    ///     assert_eq!(signal.nth(3), Some([3].into()));
+    ///     assert_eq!(signal.nth(3), Some([7].into()));
+    ///     assert_eq!(signal.nth(3), None);
+    /// }
+    /// ```
+    fn nth(&mut self, n: usize) -> Option<Self::Frame> {
+        self.advance_by(n).ok()?;
+        self.next()
+    }
+
+    /// Borrows this [`Signal`] rather than consuming it.
+    ///
+    /// This is useful for applying adaptors while still retaining ownership of
+    /// the original [`Signal`].
+    ///
+    /// ```
+    /// use sampara::{signal, Signal};
+    ///
+    /// fn main() {
+    ///     let mut signal = signal::from_samples_fixed::<_, 1>(vec![0i16, 1, 2, 3]);
+    ///     assert_eq!(signal.next(), Some([0].into()));
+    ///     assert_eq!(signal.by_ref().mul_amp(2.0f32).next(), Some([2].into()));
+    ///     assert_eq!(signal.next(), Some([2].into()));
+    /// }
+    /// ```
+    fn by_ref(&mut self) -> &mut Self
+    where
+        Self: Sized,
+    {
+        self
+    }
+
+    /// Eagerly advances and discards `N` [`Frame`]s from [`Self`]. If there
+    /// are fewer than `N` [`Frame`]s found, this will return `Err(X)`, where
+    /// `X` is the number of [`Frame`]s actually advanced. Otherwise, returns
+    /// `Ok(())`.
+    ///
+    /// ```
+    /// use sampara::{signal, Signal};
+    ///
+    /// fn main() {
+    ///     let mut signal = signal::from_samples_fixed::<_, 1>(0u8..=9);
+    ///
+    ///     // Skip ahead 5 frames.
+    ///     assert_eq!(signal.advance_by(5), Ok(()));
+    ///
+    ///     assert_eq!(signal.next(), Some([5].into()));
+    ///     assert_eq!(signal.next(), Some([6].into()));
+    ///
+    ///     // Try to skip ahead 5 more frames.
+    ///     assert_eq!(signal.advance_by(5), Err(3));
+    ///
+    ///     assert_eq!(signal.next(), None);
+    /// }
+    /// ```
+    fn advance_by(&mut self, n: usize) -> Result<(), usize> {
+        let mut left = n;
+        while left > 0 {
+            self.next().ok_or_else(|| n - left)?;
+            left -= 1;
+        }
+
+        Ok(())
+    }
+
+    /// Creates a new [`Signal`] that can look at its next [`Frame`] without
+    /// advancing, with [`Peekable::peek`].
+    ///
+    /// ```
+    /// use sampara::{signal, Signal};
+    ///
+    /// fn main() {
+    ///     let mut signal = signal::from_samples_fixed::<_, 1>(vec![1, 2]).peekable();
+    ///
+    ///     assert_eq!(signal.peek(), Some(&[1].into()));
+    ///     assert_eq!(signal.next(), Some([1].into()));
+    ///     assert_eq!(signal.next(), Some([2].into()));
+    ///     assert_eq!(signal.peek(), None);
+    ///     assert_eq!(signal.next(), None);
+    /// }
+    /// ```
+    fn peekable(self) -> Peekable<Self>
+    where
+        Self: Sized,
+    {
+        Peekable {
+            signal: self,
+            peeked: None,
+        }
+    }
+
+    /// Creates a new [`Signal`] that maps each [`Frame`] of [`Self`] through a
+    /// closure, which may return a different type of [`Frame`].
+    ///
+    /// ```
+    /// use sampara::{signal, Fixed, Signal};
+    ///
+    /// fn main() {
+    ///     let mut signal = signal::from_samples_fixed::<_, 2>(vec![1, 2, 3, 4])
+    ///         .map(|f| Fixed::from([f.into_array().iter().sum::<i32>()]));
+    ///
+    ///     assert_eq!(signal.next(), Some([3].into()));
+    ///     assert_eq!(signal.next(), Some([7].into()));
+    ///     assert_eq!(signal.next(), None);
+    /// }
+    /// ```
+    fn map<M, F>(self, map: M) -> Map<Self, M>
+    where
+        Self: Sized,
+        M: FnMut(Self::Frame) -> F,
+        F: Frame,
+    {
+        Map { signal: self, map }
+    }
+
+    /// Creates a new [`Signal`] that multiplies the amplitude of each
+    /// [`Sample`] of [`Self`] by an amount of its [`Sample::Float`] type.
+    /// Each [`Sample`] is scaled as a float, and converted back.
+    ///
+    /// ```
+    /// use sampara::{signal, Signal};
+    ///
+    /// fn main() {
+    ///     let mut signal = signal::from_samples_fixed::<_, 2>(vec![0.5f32, -1.0]).mul_amp(0.5);
+    ///
+    ///     assert_eq!(signal.next(), Some([0.25, -0.5].into()));
+    ///     assert_eq!(signal.next(), None);
+    ///
+    ///     let mut signal = signal::from_samples_fixed::<_, 2>(vec![100i16, -200]).mul_amp(0.5);
+    ///
+    ///     assert_eq!(signal.next(), Some([50, -100].into()));
+    /// }
+    /// ```
+    fn mul_amp(self, amp: FloatOf<Self>) -> MulAmp<Self>
+    where
+        Self: Sized,
+        <Self::Frame as Frame>::Sample: FromSample<FloatOf<Self>>,
+    {
+        MulAmp { signal: self, amp }
+    }
+
+    /// Creates a new [`Signal`] that yields at most `n` [`Frame`]s of
+    /// [`Self`].
+    ///
+    /// ```
+    /// use sampara::{signal, Signal};
+    ///
+    /// fn main() {
+    ///     let mut signal = signal::from_samples_fixed::<_, 1>(0..).take(2);
+    ///
+    ///     assert_eq!(signal.next(), Some([0].into()));
+    ///     assert_eq!(signal.next(), Some([1].into()));
+    ///     assert_eq!(signal.next(), None);
+    /// }
+    /// ```
+    fn take(self, n: usize) -> Take<Self>
+    where
+        Self: Sized,
+    {
+        Take {
+            signal: self,
+            len: n,
+            taken: 0,
+        }
+    }
+
+    /// Creates a new [`Signal`] that passes each [`Frame`] of [`Self`] through
+    /// a [`Processor`], as a block of one [`Frame`]. This does not allocate.
+    ///
+    /// Any latency reported by the [`Processor`] is not compensated for.
+    ///
+    /// ```
+    /// use sampara::{signal, Fixed, Frame, Signal};
+    /// use sampara::processor;
+    ///
+    /// fn main() {
+    ///     let negate = processor::from_fn(|f: &mut Fixed<i8, 2>| {
+    ///         f.iter_mut().for_each(|s| *s = -*s);
+    ///     });
+    ///
+    ///     let mut signal = signal::from_samples_fixed::<_, 2>(vec![1, 2, 3, 4]).through(negate);
+    ///
+    ///     assert_eq!(signal.next(), Some([-1, -2].into()));
+    ///     assert_eq!(signal.next(), Some([-3, -4].into()));
+    ///     assert_eq!(signal.next(), None);
+    /// }
+    /// ```
+    fn through<P>(self, processor: P) -> Through<Self, P>
+    where
+        Self: Sized,
+        P: Processor<Frame = Self::Frame>,
+    {
+        Through {
+            signal: self,
+            processor,
+        }
+    }
+
+    /// Creates a new [`Signal`] that convolves each channel of [`Self`] with
+    /// an [`ImpulseResponse`], which can be created from a [`Vec`] of
+    /// coefficients to use the same impulse response for every channel.
+    ///
+    /// Once [`Self`] runs out, the output continues until the latency and
+    /// the tail of the impulse response have been flushed. See [`Convolve`]
+    /// and [`Method`](crate::fir::Method) for details.
+    ///
+    /// ```
+    /// use sampara::{signal, Signal};
+    /// use sampara::fir::{ImpulseResponse, Method};
+    ///
+    /// fn main() {
+    ///     let input = vec![1.0f32, 0.0, 0.5, 2.0];
+    ///
+    ///     let mut signal = signal::from_samples_fixed::<_, 2>(input.clone())
+    ///         .convolve(vec![0.5, 0.5]);
+    ///
+    ///     assert_eq!(signal.next(), Some([0.5, 0.0].into()));
+    ///     assert_eq!(signal.next(), Some([0.75, 1.0].into()));
+    ///     assert_eq!(signal.next(), Some([0.25, 1.0].into()));
+    ///     assert_eq!(signal.next(), None);
+    ///
+    ///     // Long impulse responses are better convolved in the frequency
+    ///     // domain, at the cost of some latency.
+    ///     let ir = ImpulseResponse::new(vec![0.5, 0.5])
+    ///         .with_method(Method::Partitioned { block_size: 4 });
+    ///
+    ///     let mut signal = signal::from_samples_fixed::<_, 2>(input).convolve(ir);
+    ///     assert_eq!(signal.latency(), 4);
+    ///
+    ///     let [l, r] = signal.nth(4).unwrap().into_array();
+    ///     assert!((l - 0.5).abs() < 1e-6 && r.abs() < 1e-6);
+    /// }
+    /// ```
+    fn convolve<I>(self, ir: I) -> Convolve<Self>
+    where
+        Self: Sized,
+        I: Into<ImpulseResponse<FloatOf<Self>>>,
+    {
+        Convolve::new(self, ir.into())
+    }
+
+    /// Creates a new [`Signal`] that applies a [`DynamicsProcessor`], such as
+    /// a [`Compressor`](crate::dynamics::Compressor), to [`Self`], keyed by
+    /// the levels of [`Self`].
+    ///
+    /// If the processor has lookahead, the output is delayed by its latency,
+    /// and is extended by the same amount at the end so that every input
+    /// [`Frame`] is output.
+    ///
+    /// ```
+    /// use sampara::{signal, Signal};
+    /// use sampara::dynamics::{Compressor, CompressorParams};
+    ///
+    /// fn main() {
+    ///     let compressor = Compressor::new(CompressorParams::default(), 48000.0);
+    ///     let mut signal = signal::from_samples_fixed::<_, 2>(vec![0.01f32, -0.01]).dynamics(compressor);
+    ///
+    ///     // Quiet signals are left alone.
+    ///     assert_eq!(signal.next(), Some([0.01, -0.01].into()));
+    ///     assert_eq!(signal.next(), None);
+    /// }
+    /// ```
+    fn dynamics<P>(self, processor: P) -> Dynamics<Self, P>
+    where
+        Self: Sized,
+        <Self::Frame as Frame>::Sample: FloatSample,
+        P: DynamicsProcessor,
+    {
+        Dynamics::new(self, processor)
+    }
+
+    /// Creates a new [`Signal`] that applies a [`DynamicsProcessor`] to
+    /// [`Self`], keyed by the levels of a separate sidechain [`Signal`]. The
+    /// sidechain must have either one channel, or the same number of channels
+    /// as [`Self`].
+    ///
+    /// # Panics
+    ///
+    /// Panics while iterating if a frame of the sidechain has a different
+    /// number of channels, other than one.
+    ///
+    /// ```
+    /// use sampara::{signal, Signal};
+    /// use sampara::dynamics::{Compressor, CompressorParams};
+    ///
+    /// fn main() {
+    ///     let params = CompressorParams {
+    ///         threshold: -20.0,
+    ///         ratio: f64::INFINITY,
+    ///         knee: 0.0,
+    ///         attack: 0.0,
+    ///         release: 0.0,
+    ///         ..Default::default()
+    ///     };
+    ///
+    ///     // Duck the music by 20 dB while the voice is at 0 dB.
+    ///     let music = signal::from_samples_fixed::<_, 2>(vec![0.5f64, 0.5, 0.5, 0.5]);
+    ///     let voice = signal::from_samples_fixed::<_, 1>(vec![0.0f32, 1.0]);
+    ///
+    ///     let mut signal = music.sidechain(voice, Compressor::new(params, 48000.0));
+    ///
+    ///     assert_eq!(signal.next(), Some([0.5, 0.5].into()));
+    ///     let [l, r] = signal.next().unwrap().into_array();
+    ///     assert!((l - 0.05).abs() < 1e-9 && (r - 0.05).abs() < 1e-9);
+    /// }
+    /// ```
+    fn sidechain<K, P>(self, key: K, processor: P) -> Sidechain<Self, K, P>
+    where
+        Self: Sized,
+        <Self::Frame as Frame>::Sample: FloatSample,
+        K: Signal,
+        P: DynamicsProcessor,
+    {
+        Sidechain::new(self, key, processor)
+    }
+
+    /// Creates a new [`Signal`] that delays [`Self`] by a whole number of
+    /// [`Frame`]s. The output starts with `samples` [`Frame`]s of
+    /// equilibrium, and is extended by `samples` [`Frame`]s so that the end
+    /// of [`Self`] is not cut off.
+    ///
+    /// ```
+    /// use sampara::{signal, Signal};
+    ///
+    /// fn main() {
+    ///     let mut signal = signal::from_samples_fixed::<_, 2>(vec![1i16, -1, 2, -2]).delay(1);
+    ///
+    ///     assert_eq!(signal.next(), Some([0, 0].into()));
+    ///     assert_eq!(signal.next(), Some([1, -1].into()));
+    ///     assert_eq!(signal.next(), Some([2, -2].into()));
+    ///     assert_eq!(signal.next(), None);
+    /// }
+    /// ```
+    fn delay(self, samples: usize) -> Delay<Self>
+    where
+        Self: Sized,
+    {
+        Delay::new(self, samples)
+    }
+
+    /// Creates a new [`Signal`] that yields the envelope of each channel of
+    /// [`Self`], as [`Frame`]s of its [`Sample::Float`] type. The attack and
+    /// release time constants and the window of [`Mode::Rms`] are in seconds,
+    /// and a time constant of 0 follows changes instantly.
+    ///
+    /// Channels can be linked with [`Envelope::link`], and the envelope can
+    /// be yielded in decibels with [`Envelope::decibels`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the window of [`Mode::Rms`] is shorter than half a
+    /// [`Frame`].
+    ///
+    /// ```
+    /// use sampara::{signal, Signal};
+    /// use sampara::envelope::{Link, Mode};
+    ///
+    /// fn main() {
+    ///     let signal = signal::from_samples_fixed::<_, 2>(vec![0i16, 16384, -32768, 0]);
+    ///     let mut signal = signal.envelope(0.0, 0.0, Mode::Peak, 48000.0);
+    ///
+    ///     assert_eq!(signal.next(), Some([0.0f32, 0.5].into()));
+    ///     assert_eq!(signal.next(), Some([1.0, 0.0].into()));
+    ///
+    ///     // A release time constant of 1 ms is 1 frame at 1 kHz.
+    ///     let signal = signal::from_samples_fixed::<_, 1>(vec![1.0f64, 0.0]);
+    ///     let mut signal = signal.envelope(0.0, 0.001, Mode::Peak, 1000.0);
+    ///
+    ///     assert_eq!(signal.next(), Some([1.0].into()));
+    ///     assert!((signal.next().unwrap().into_array()[0] - (-1.0f64).exp()).abs() < 1e-12);
+    ///
+    ///     let signal = signal::from_samples_fixed::<_, 2>(vec![0.5f64, -0.25]);
+    ///     let signal = signal.envelope(0.0, 0.0, Mode::Peak, 48000.0);
+    ///     let mut signal = signal.link(Link::Max).decibels();
+    ///
+    ///     let [l, r] = signal.next().unwrap().into_array();
+    ///     assert!((l - -6.0206).abs() < 1e-4 && l == r);
+    /// }
+    /// ```
+    fn envelope(self, attack: f64, release: f64, mode: Mode, sample_rate: f64) -> Envelope<Self>
+    where
+        Self: Sized,
+    {
+        Envelope::new(
+            self,
+            attack * sample_rate,
+            release * sample_rate,
+            mode.scaled(sample_rate),
+        )
+    }
+
+    /// Creates a new [`Signal`] that applies a [`Chorus`] to [`Self`].
+    ///
+    /// ```
+    /// use sampara::{signal, Signal};
+    /// use sampara::modulation::ChorusParams;
+    ///
+    /// fn main() {
+    ///     let input: Vec<f32> = (0..4800).map(|i| (i as f32 * 0.01).sin()).collect();
+    ///     let signal = signal::from_samples_fixed::<_, 2>(input);
+    ///
+    ///     let mut signal = signal.chorus(ChorusParams::default(), 48000.0);
+    ///
+    ///     // The output is extended by the longest delay of 20 ms, so that the
+    ///     // voices can finish.
+    ///     assert_eq!(std::iter::from_fn(|| signal.next()).count(), 2400 + 960);
+    /// }
+    /// ```
+    fn chorus(self, params: ChorusParams, sample_rate: f64) -> Chorus<Self>
+    where
+        Self: Sized,
+        <Self::Frame as Frame>::Sample: FloatSample,
+    {
+        Chorus::new(self, params, sample_rate)
+    }
+
+    /// Creates a new [`Signal`] that applies a [`Flanger`] to [`Self`].
+    ///
+    /// ```
+    /// use sampara::{signal, Signal};
+    /// use sampara::modulation::FlangerParams;
+    ///
+    /// fn main() {
+    ///     let params = FlangerParams { mix: 0.0, ..Default::default() };
+    ///     let mut signal = signal::from_samples_fixed::<_, 1>(vec![0.5f64, -0.5]).flanger(params, 48000.0);
+    ///
+    ///     // With a mix of 0, the signal is unchanged.
+    ///     assert_eq!(signal.next(), Some([0.5].into()));
+    ///     assert_eq!(signal.next(), Some([-0.5].into()));
+    /// }
+    /// ```
+    fn flanger(self, params: FlangerParams, sample_rate: f64) -> Flanger<Self>
+    where
+        Self: Sized,
+        <Self::Frame as Frame>::Sample: FloatSample,
+    {
+        Flanger::new(self, params, sample_rate)
+    }
+
+    /// Creates a new [`Signal`] that applies a [`Phaser`] to [`Self`].
+    ///
+    /// ```
+    /// use sampara::{signal, Signal};
+    /// use sampara::modulation::PhaserParams;
+    ///
+    /// fn main() {
+    ///     let params = PhaserParams { stages: 6, ..Default::default() };
+    ///     let mut signal = signal::from_samples_fixed::<_, 2>(vec![0.0f32; 4]).phaser(params, 48000.0);
+    ///
+    ///     assert_eq!(signal.next(), Some([0.0, 0.0].into()));
+    /// }
+    /// ```
+    fn phaser(self, params: PhaserParams, sample_rate: f64) -> Phaser<Self>
+    where
+        Self: Sized,
+        <Self::Frame as Frame>::Sample: FloatSample,
+    {
+        Phaser::new(self, params, sample_rate)
+    }
+
+    /// Creates a new [`Signal`] that applies a [`Tremolo`] to [`Self`].
+    ///
+    /// ```
+    /// use sampara::{signal, Signal};
+    /// use sampara::modulation::{Shape, TremoloParams};
+    ///
+    /// fn main() {
+    ///     let params = TremoloParams {
+    ///         shape: Shape::Triangle,
+    ///         rate: 1.0,
+    ///         depth: 0.5,
+    ///         spread: 0.0,
+    ///     };
+    ///
+    ///     let input = vec![1.0f64; 4];
+    ///     let mut signal = signal::from_samples_fixed::<_, 1>(input).tremolo(params, 4.0);
+    ///
+    ///     assert_eq!(signal.next(), Some([0.75].into()));
+    ///     assert_eq!(signal.next(), Some([1.0].into()));
+    ///     assert_eq!(signal.next(), Some([0.75].into()));
+    ///     assert_eq!(signal.next(), Some([0.5].into()));
+    /// }
+    /// ```
+    fn tremolo(self, params: TremoloParams, sample_rate: f64) -> Tremolo<Self>
+    where
+        Self: Sized,
+        <Self::Frame as Frame>::Sample: FloatSample,
+    {
+        Tremolo::new(self, params, sample_rate)
+    }
+
+    /// Creates a new [`Signal`] that applies a [`Vibrato`] to [`Self`].
+    ///
+    /// ```
+    /// use sampara::{signal, Signal};
+    /// use sampara::modulation::VibratoParams;
+    ///
+    /// fn main() {
+    ///     // Without modulation, the vibrato is a fixed delay of half its depth.
+    ///     let params = VibratoParams { rate: 0.0, depth: 4.0, ..Default::default() };
+    ///
+    ///     let input = vec![1.0f64, 2.0, 3.0, 4.0];
+    ///     let mut signal = signal::from_samples_fixed::<_, 1>(input).vibrato(params, 1.0);
+    ///
+    ///     assert_eq!(signal.nth(3), Some([2.0].into()));
+    ///
+    ///     // The delayed input is flushed once the input ends.
+    ///     assert_eq!(signal.next(), Some([3.0].into()));
+    ///     assert_eq!(signal.next(), Some([4.0].into()));
+    /// }
+    /// ```
+    fn vibrato(self, params: VibratoParams, sample_rate: f64) -> Vibrato<Self>
+    where
+        Self: Sized,
+        <Self::Frame as Frame>::Sample: FloatSample,
+    {
+        Vibrato::new(self, params, sample_rate)
+    }
+
+    /// Creates a new [`Signal`] that applies a [`ReverbProcessor`] to
+    /// [`Self`]. The output is extended by the tail of the reverb.
+    ///
+    /// ```
+    /// use sampara::{signal, Signal};
+    /// use sampara::reverb::{Fdn, FdnParams};
+    ///
+    /// fn main() {
+    ///     let params = FdnParams { rt60: 0.001, wet: 0.0, ..Default::default() };
+    ///     let fdn = Fdn::new(params, 48000.0);
+    ///
+    ///     let input = vec![0.5f32, -0.5];
+    ///     let mut signal = signal::from_samples_fixed::<_, 2>(input).reverb(fdn);
+    ///
+    ///     assert_eq!(signal.next(), Some([0.5, -0.5].into()));
+    ///     assert_eq!(std::iter::from_fn(|| signal.next()).count(), 48);
+    /// }
+    /// ```
+    fn reverb<R>(self, reverb: R) -> Reverb<Self, R>
+    where
+        Self: Sized,
+        <Self::Frame as Frame>::Sample: FloatSample,
+        R: ReverbProcessor,
+    {
+        Reverb::new(self, reverb)
+    }
+
+    /// Creates a new [`Signal`] that maps each sample of [`Self`] through a
+    /// [`Curve`], such as a [`Preset`](crate::waveshape::Preset), a
+    /// [`Table`](crate::waveshape::Table) or a closure. The curve can be
+    /// applied at a higher sample rate with [`Waveshape::oversample`].
+    ///
+    /// ```
+    /// use sampara::{signal, Signal};
+    /// use sampara::waveshape::{Oversampling, Preset};
+    ///
+    /// fn main() {
+    ///     let input = vec![0.5f32, 2.0, -3.0];
+    ///     let mut signal = signal::from_samples_fixed::<_, 1>(input).waveshape(Preset::HardClip);
+    ///
+    ///     assert_eq!(signal.next(), Some([0.5].into()));
+    ///     assert_eq!(signal.next(), Some([1.0].into()));
+    ///     assert_eq!(signal.next(), Some([-1.0].into()));
+    ///
+    ///     // A closure, at 4x the sample rate.
+    ///     let input = vec![0.25f64; 64];
+    ///     let mut signal = signal::from_samples_fixed::<_, 2>(input)
+    ///         .waveshape(|x: f64| 2.0 * x)
+    ///         .oversample(Oversampling::X4);
+    ///
+    ///     assert_eq!(signal.latency(), 22.5);
+    ///
+    ///     let [l, r] = signal.nth(31).unwrap().into_array();
+    ///     assert!((l - 0.5).abs() < 1e-6 && (r - 0.5).abs() < 1e-6);
+    /// }
+    /// ```
+    fn waveshape<C>(self, curve: C) -> Waveshape<Self, C>
+    where
+        Self: Sized,
+        <Self::Frame as Frame>::Sample: FloatSample,
+        C: Curve,
+    {
+        Waveshape::new(self, curve)
+    }
+
+    /// Creates a new [`Signal`] that carries the [`Layout`] of its channels,
+    /// so that they can be looked up by speaker and reordered.
+    ///
+    /// # Panics
+    ///
+    /// When iterating, panics if a [`Frame`] does not have the number of
+    /// channels of the layout.
+    ///
+    /// ```
+    /// use sampara::{signal, Signal};
+    /// use sampara::channel::Layout;
+    ///
+    /// fn main() {
+    ///     let input = vec![0.25f32, -0.25];
+    ///     let mut signal = signal::from_samples_fixed::<_, 2>(input).with_layout(Layout::stereo());
+    ///
+    ///     let frame = signal.next().unwrap();
+    ///     let speakers = signal.layout().speakers_of(&frame);
+    ///     assert_eq!(speakers.left(), Some(&0.25));
+    ///     assert_eq!(speakers.right(), Some(&-0.25));
+    ///     assert_eq!(speakers.center(), None);
+    /// }
+    /// ```
+    fn with_layout(self, layout: Layout) -> WithLayout<Self>
+    where
+        Self: Sized,
+    {
+        WithLayout::new(self, layout)
+    }
+
+    /// Creates a new [`Signal`] that maps each [`Frame`] through a
+    /// [`ChannelMatrix`], yielding [`Frame`]s of type `F`. This can change the
+    /// number of channels, for example to downmix 5.1 to stereo.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `F` cannot have the number of output channels of the matrix,
+    /// or, when iterating, if a [`Frame`] does not have its number of input
+    /// channels.
+    ///
+    /// ```
+    /// use sampara::{signal, Dynamic, Fixed, Signal};
+    /// use sampara::channel::ChannelMatrix;
+    ///
+    /// fn main() {
+    ///     let input = vec![0.5f32, 0.5, 0.0, 1.0, 0.0, 0.0];
+    ///     let mut signal = signal::from_samples_fixed::<_, 6>(input)
+    ///         .remix::<Fixed<f32, 2>>(ChannelMatrix::surround_5_1_to_stereo());
+    ///
+    ///     assert_eq!(signal.next(), Some([0.5, 0.5].into()));
+    ///     assert_eq!(signal.next(), None);
+    ///
+    ///     // Dynamic frames have as many channels as the matrix has outputs.
+    ///     let input = vec![0.25f64, 0.5, 1.0, 0.0];
+    ///     let mut signal = signal::from_samples_dynamic(input, 2)
+    ///         .remix::<Dynamic<f64>>(ChannelMatrix::from([[1.0, 1.0], [0.0, 1.0], [1.0, 0.0]]));
+    ///
+    ///     assert_eq!(signal.next(), Some(Dynamic::from([0.75, 0.5, 0.25])));
+    ///     assert_eq!(signal.next(), Some(Dynamic::from([1.0, 0.0, 1.0])));
+    /// }
+    /// ```
+    fn remix<F>(self, matrix: ChannelMatrix) -> Remix<Self, F>
+    where
+        Self: Sized,
+        F: Frame<Sample = <Self::Frame as Frame>::Sample>,
+        F::Sample: FromSample<f64>,
+    {
+        Remix::new(self, matrix)
+    }
+
+    /// Creates a new [`Signal`] that encodes the left and right channels of a
+    /// stereo [`Signal`] into mid and side channels. This is undone by
+    /// [`Signal::left_right`].
+    ///
+    /// ```
+    /// use sampara::{signal, Signal};
+    ///
+    /// fn main() {
+    ///     let input = vec![1.0f32, 0.5];
+    ///     let mut signal = signal::from_samples_fixed::<_, 2>(input).mid_side();
+    ///
+    ///     assert_eq!(signal.next(), Some([0.75, 0.25].into()));
+    /// }
+    /// ```
+    fn mid_side<X>(self) -> MidSide<Self>
+    where
+        Self: Sized + Signal<Frame = Fixed<X, 2>>,
+        X: FloatSample,
+    {
+        MidSide::new(self)
+    }
+
+    /// Creates a new [`Signal`] that decodes the mid and side channels of a
+    /// [`Signal`] into left and right channels.
+    ///
+    /// ```
+    /// use sampara::{signal, Signal};
+    ///
+    /// fn main() {
+    ///     let input = vec![0.75f32, 0.25];
+    ///     let mut signal = signal::from_samples_fixed::<_, 2>(input).left_right();
+    ///
+    ///     assert_eq!(signal.next(), Some([1.0, 0.5].into()));
+    /// }
+    /// ```
+    fn left_right<X>(self) -> LeftRight<Self>
+    where
+        Self: Sized + Signal<Frame = Fixed<X, 2>>,
+        X: FloatSample,
+    {
+        LeftRight::new(self)
+    }
+
+    /// Creates a new [`Signal`] that changes the width of a stereo
+    /// [`Signal`], by scaling its side channel. A width of 0 collapses it to
+    /// mono, and a width of 1 leaves it unchanged.
+    ///
+    /// ```
+    /// use sampara::{signal, Signal};
+    ///
+    /// fn main() {
+    ///     let input = vec![1.0f32, 0.5];
+    ///     let mut signal = signal::from_samples_fixed::<_, 2>(input).width(0.0);
+    ///
+    ///     assert_eq!(signal.next(), Some([0.75, 0.75].into()));
+    /// }
+    /// ```
+    fn width<X>(self, width: f64) -> Width<Self>
+    where
+        Self: Sized + Signal<Frame = Fixed<X, 2>>,
+        X: FloatSample,
+    {
+        Width::new(self, width)
+    }
+
+    /// Creates a new [`Signal`] that shifts the balance of a stereo
+    /// [`Signal`], from -1 (left only) to 1 (right only).
+    ///
+    /// ```
+    /// use sampara::{signal, Signal};
+    ///
+    /// fn main() {
+    ///     let input = vec![1.0f32, 1.0];
+    ///     let mut signal = signal::from_samples_fixed::<_, 2>(input).balance(0.25);
+    ///
+    ///     assert_eq!(signal.next(), Some([0.75, 1.0].into()));
+    /// }
+    /// ```
+    fn balance<X>(self, balance: f64) -> Balance<Self>
+    where
+        Self: Sized + Signal<Frame = Fixed<X, 2>>,
+        X: FloatSample,
+    {
+        Balance::new(self, balance)
+    }
+
+    /// Creates a new [`Signal`] that pans a mono [`Signal`] into stereo, at a
+    /// fixed position from -1 (left) to 1 (right).
+    ///
+    /// ```
+    /// use sampara::{signal, Signal};
+    /// use sampara::stereo::PanLaw;
+    ///
+    /// fn main() {
+    ///     let input = vec![1.0f32, 0.5];
+    ///     let mut signal = signal::from_samples_fixed::<_, 1>(input).pan(PanLaw::ConstantAmplitude, 0.5);
+    ///
+    ///     assert_eq!(signal.next(), Some([0.25, 0.75].into()));
+    ///     assert_eq!(signal.next(), Some([0.125, 0.375].into()));
+    ///     assert_eq!(signal.next(), None);
+    /// }
+    /// ```
+    fn pan<X>(self, law: PanLaw, position: f64) -> Pan<Self, Constant<Fixed<f64, 1>>>
+    where
+        Self: Sized + Signal<Frame = Fixed<X, 1>>,
+        X: FloatSample,
+    {
+        Pan::new(self, constant(Fixed::from([position])), law)
+    }
+
+    /// Creates a new [`Signal`] that pans a mono [`Signal`] into stereo, with
+    /// the position of each [`Frame`] taken from the first channel of another
+    /// [`Signal`].
+    ///
+    /// ```
+    /// use sampara::{signal, Signal};
+    /// use sampara::stereo::PanLaw;
+    ///
+    /// fn main() {
+    ///     let input = vec![1.0f32; 3];
+    ///     let positions = vec![-1.0f64, 0.0, 1.0];
+    ///     let mut signal = signal::from_samples_fixed::<_, 1>(input)
+    ///         .pan_with(PanLaw::Linear, signal::from_samples_fixed::<_, 1>(positions));
+    ///
+    ///     assert_eq!(signal.next(), Some([1.0, 0.0].into()));
+    ///     assert_eq!(signal.next(), Some([1.0, 1.0].into()));
+    ///     assert_eq!(signal.next(), Some([0.0, 1.0].into()));
+    ///     assert_eq!(signal.next(), None);
+    /// }
+    /// ```
+    fn pan_with<X, P>(self, law: PanLaw, positions: P) -> Pan<Self, P>
+    where
+        Self: Sized + Signal<Frame = Fixed<X, 1>>,
+        X: FloatSample,
+        P: Signal,
+    {
+        Pan::new(self, positions, law)
+    }
+
+    /// Creates a new [`Signal`] that pans a mono [`Signal`] back and forth
+    /// across the stereo field.
+    ///
+    /// ```
+    /// use sampara::{signal, Signal};
+    /// use sampara::modulation::Shape;
+    /// use sampara::stereo::{AutoPanParams, PanLaw};
+    ///
+    /// fn main() {
+    ///     let params = AutoPanParams {
+    ///         law: PanLaw::ConstantAmplitude,
+    ///         shape: Shape::Triangle,
+    ///         rate: 1.0,
+    ///         depth: 1.0,
+    ///     };
+    ///     let input = vec![1.0f64; 4];
+    ///     let mut signal = signal::from_samples_fixed::<_, 1>(input).auto_pan(params, 4.0);
+    ///
+    ///     assert_eq!(signal.next(), Some([0.5, 0.5].into()));
+    ///     assert_eq!(signal.next(), Some([0.0, 1.0].into()));
+    ///     assert_eq!(signal.next(), Some([0.5, 0.5].into()));
+    ///     assert_eq!(signal.next(), Some([1.0, 0.0].into()));
+    /// }
+    /// ```
+    fn auto_pan<X>(self, params: AutoPanParams, sample_rate: f64) -> AutoPan<Self>
+    where
+        Self: Sized + Signal<Frame = Fixed<X, 1>>,
+        X: FloatSample,
+    {
+        AutoPan::new(self, params, sample_rate)
+    }
+
+    /// Splits [`Self`] into two handles, which each yield every [`Frame`].
+    /// This is the same as [`Signal::split`] with two handles.
+    ///
+    /// ```
+    /// use sampara::{signal, Signal};
+    ///
+    /// fn main() {
+    ///     let (mut dry, mut meter) = signal::from_samples_fixed::<_, 1>(vec![1, 2, 3]).tee();
+    ///
+    ///     assert_eq!(dry.next(), Some([1].into()));
+    ///     assert_eq!(dry.next(), Some([2].into()));
+    ///     assert_eq!(meter.next(), Some([1].into()));
+    ///     assert_eq!(dry.next(), Some([3].into()));
+    ///     assert_eq!(dry.next(), None);
+    ///     assert_eq!(meter.next(), Some([2].into()));
+    /// }
+    /// ```
+    fn tee(self) -> (Split<Self>, Split<Self>)
+    where
+        Self: Sized,
+    {
+        let first = Split::new(self);
+        let second = first.clone();
+        (first, second)
+    }
+
+    /// Splits [`Self`] into `n` handles for use on a single thread, which each
+    /// yield every [`Frame`]. The [`Frame`]s are buffered until every handle
+    /// has yielded them, or has been dropped.
+    ///
+    /// ```
+    /// use sampara::{signal, Signal};
+    ///
+    /// fn main() {
+    ///     let mut handles = signal::from_samples_fixed::<_, 2>(vec![0.5f32, -0.5]).split(3);
+    ///
+    ///     for handle in handles.iter_mut() {
+    ///         assert_eq!(handle.next(), Some([0.5, -0.5].into()));
+    ///         assert_eq!(handle.next(), None);
+    ///     }
+    /// }
+    /// ```
+    fn split(self, n: usize) -> Vec<Split<Self>>
+    where
+        Self: Sized,
+    {
+        splitting::split(self, n)
+    }
+
+    /// Like [`Signal::split`], but returns handles that can be sent to other
+    /// threads, which buffer at most `capacity` [`Frame`]s. A handle that
+    /// runs ahead blocks until the others catch up.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is 0.
+    fn split_sync(self, n: usize, capacity: usize) -> Vec<SyncSplit<Self>>
+    where
+        Self: Sized,
+    {
+        splitting::split_sync(self, n, capacity)
+    }
+
+    /// Converts [`Self`] into an [`Iterator`] over its [`Frame`]s.
+    ///
+    /// ```
+    /// use sampara::{signal, Signal};
+    ///
+    /// fn main() {
+    ///     let signal = signal::from_samples_fixed::<_, 2>(vec![1, -1, 2, -2]);
+    ///     let frames: Vec<_> = signal.into_frames().map(|f| f.into_array()).collect();
+    ///
+    ///     assert_eq!(frames, vec![[1, -1], [2, -2]]);
+    /// }
+    /// ```
+    fn into_frames(self) -> IntoFrames<Self>
+    where
+        Self: Sized,
+    {
+        IntoFrames { signal: self }
+    }
+
+    /// Converts [`Self`] into an [`Iterator`] over its [`Sample`]s,
+    /// interleaved.
+    ///
+    /// ```
+    /// use sampara::{signal, Signal};
+    ///
+    /// fn main() {
+    ///     let signal = signal::from_samples_dynamic(vec![1.0, -1.0, 2.0, -2.0, 3.0, -3.0], 3);
+    ///     let samples: Vec<_> = signal.mul_amp(0.5).into_samples().collect();
+    ///
+    ///     assert_eq!(samples, vec![0.5, -0.5, 1.0, -1.0, 1.5, -1.5]);
+    /// }
+    /// ```
+    fn into_samples(self) -> IntoSamples<Self>
+    where
+        Self: Sized,
+    {
+        IntoSamples {
+            signal: self,
+            frame: None,
+        }
+    }
+
+    /// Consumes [`Self`], writing its [`Frame`]s into a [`CollectTarget`],
+    /// such as a [`Vec`], a preallocated slice of [`Frame`]s or a planar
+    /// buffer. Returns the number of [`Frame`]s written.
+    ///
+    /// Growable targets take every [`Frame`], and reserve space up front using
+    /// [`Self::len_hint`]. Fixed-size targets take [`Frame`]s until they are
+    /// full.
+    ///
+    /// ```
+    /// use sampara::{signal, Fixed, Signal};
+    /// use sampara::buffer::{AudioBuffer, Layout};
+    ///
+    /// fn main() {
+    ///     let input = vec![1, -1, 2, -2, 3, -3];
+    ///
+    ///     let mut frames = Vec::new();
+    ///     assert_eq!(signal::from_samples_fixed::<_, 2>(input.clone()).collect_into(&mut frames), 3);
+    ///     assert_eq!(frames[2], Fixed::from([3, -3]));
+    ///
+    ///     let mut slice = [Fixed::from([0, 0]); 2];
+    ///     assert_eq!(signal::from_samples_fixed::<_, 2>(input.clone()).collect_into(&mut slice[..]), 2);
+    ///     assert_eq!(slice[1], Fixed::from([2, -2]));
+    ///
+    ///     let mut channels = vec![Vec::new(), Vec::new()];
+    ///     signal::from_samples_fixed::<_, 2>(input.clone()).collect_into(&mut channels[..]);
+    ///     assert_eq!(channels, vec![vec![1, 2, 3], vec![-1, -2, -3]]);
+    ///
+    ///     let mut buffer = AudioBuffer::new(2, 3, Layout::Planar);
+    ///     signal::from_samples_fixed::<_, 2>(input).collect_into(&mut buffer);
+    ///     assert_eq!(buffer.as_slice(), &[1, 2, 3, -1, -2, -3]);
+    /// }
+    /// ```
+    fn collect_into<C>(self, target: &mut C) -> usize
+    where
+        Self: Sized,
+        C: CollectTarget<Self::Frame> + ?Sized,
+    {
+        target.collect_signal(self)
+    }
+
+    /// Boxes [`Self`] as a trait object, so that [`Signal`]s of different
+    /// types with the same [`Frame`] type can be used interchangeably, such as
+    /// when a processing chain is chosen at runtime.
+    ///
+    /// ```
+    /// use sampara::{signal, Fixed, Signal};
+    ///
+    /// fn main() {
+    ///     let input = || signal::from_samples_fixed::<_, 1>(vec![1.0f32, 0.5]);
+    ///
+    ///     let chain = |name: &str| -> Box<dyn Signal<Frame = Fixed<f32, 1>>> {
+    ///         match name {
+    ///             "quiet" => input().mul_amp(0.5).boxed(),
+    ///             "delayed" => input().delay(1).boxed(),
+    ///             _ => input().boxed(),
+    ///         }
+    ///     };
+    ///
+    ///     let mut quiet = chain("quiet");
+    ///     assert_eq!(quiet.next(), Some([0.5].into()));
+    ///
+    ///     let mut delayed = chain("delayed").take(2);
+    ///     assert_eq!(delayed.next(), Some([0.0].into()));
+    ///     assert_eq!(delayed.next(), Some([1.0].into()));
+    ///     assert_eq!(delayed.next(), None);
+    /// }
+    /// ```
+    fn boxed<'a>(self) -> Box<dyn Signal<Frame = Self::Frame> + 'a>
+    where
+        Self: Sized + 'a,
+    {
+        Box::new(self)
+    }
+
+    /// Erases the type of [`Self`] and the number of channels of its
+    /// [`Frame`]s, yielding [`Dynamic`](crate::Dynamic) frames instead. See
+    /// [`DynSignal`].
+    fn into_dyn<'a>(self) -> DynSignal<'a, <Self::Frame as Frame>::Sample>
+    where
+        Self: Sized + 'a,
+    {
+        DynSignal::new(self)
+    }
+
+    /// Consumes [`Self`], writing each [`Frame`] to a [`Write`] as interleaved
+    /// [`Sample`]s in a given byte order. Returns the number of [`Frame`]s
+    /// written.
+    ///
+    /// ```
+    /// use sampara::{signal, Signal};
+    /// use sampara::sample::LittleEndian;
+    ///
+    /// fn main() {
+    ///     let signal = signal::from_samples_fixed::<_, 2>(vec![1i16, -1, 256, -256]);
+    ///
+    ///     let mut bytes = Vec::new();
+    ///     let written = signal.write_bytes::<LittleEndian, _>(&mut bytes).unwrap();
+    ///
+    ///     assert_eq!(written, 2);
+    ///     assert_eq!(bytes, vec![0x01, 0x00, 0xFF, 0xFF, 0x00, 0x01, 0x00, 0xFF]);
+    /// }
+    /// ```
+    fn write_bytes<E, W>(mut self, writer: &mut W) -> std::io::Result<usize>
+    where
+        Self: Sized,
+        E: ByteOrder,
+        W: Write,
+        <Self::Frame as Frame>::Sample: ByteSample,
+    {
+        let mut count = 0;
+
+        while let Some(frame) = self.next() {
+            for sample in frame {
+                writer.write_all(E::to_bytes(sample).as_ref())?;
+            }
+
+            count += 1;
+        }
+
+        Ok(count)
+    }
+}
+
+impl<S> Signal for &mut S
+where
+    S: Signal + ?Sized,
+{
+    type Frame = S::Frame;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Frame> {
+        (**self).next()
+    }
+
+    #[inline]
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        (**self).len_hint()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Frame> {
+        (**self).nth(n)
+    }
+
+    #[inline]
+    fn advance_by(&mut self, n: usize) -> Result<(), usize> {
+        (**self).advance_by(n)
+    }
+}
+
+impl<S> Signal for Box<S>
+where
+    S: Signal + ?Sized,
+{
+    type Frame = S::Frame;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Frame> {
+        (**self).next()
+    }
+
+    #[inline]
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        (**self).len_hint()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Frame> {
+        (**self).nth(n)
+    }
+
+    #[inline]
+    fn advance_by(&mut self, n: usize) -> Result<(), usize> {
+        (**self).advance_by(n)
+    }
+}
+
+/// [`Signal`]s that can jump to any [`Frame`], such as those backed by
+/// slices, buffers or seekable readers.
+///
+/// Positions are counted in [`Frame`]s from the start of the [`Signal`].
+/// Seeking past the end leaves the [`Signal`] exhausted.
+///
+/// This is passed through by adapters that process each [`Frame`]
+/// independently, such as [`Signal::map`], [`Signal::mul_amp`] and
+/// [`Signal::take`].
+///
+/// ```
+/// use sampara::{signal, Fixed, SeekableSignal, Signal};
+///
+/// fn main() {
+///     let frames: Vec<Fixed<f32, 1>> = (0..8).map(|i| [i as f32].into()).collect();
+///     let mut signal = signal::from_slice(&frames).mul_amp(0.5).take(4);
+///
+///     assert_eq!(signal.next(), Some([0.0].into()));
+///     assert_eq!(signal.position(), 1);
+///
+///     signal.seek(3);
+///     assert_eq!(signal.next(), Some([1.5].into()));
+///     assert_eq!(signal.next(), None);
+///
+///     signal.seek(0);
+///     assert_eq!(signal.next(), Some([0.0].into()));
+/// }
+/// ```
+pub trait SeekableSignal: Signal {
+    /// Moves to a [`Frame`], so that it is the next one to be yielded.
+    fn seek(&mut self, position: usize);
 
-    /// Advances [`Self`] and returns the next [`Frame`], or [`None`] if there
-    /// are no more to yield.
-    fn next(&mut self) -> Option<Self::Frame>;
+    /// The index of the next [`Frame`] to be yielded.
+    fn position(&self) -> usize;
+}
 
-    /// Similar to [`Self::next`], but will always yield a [`Frame`]. Yields
-    /// [`Frame::EQUILIBRIUM`] if there are no more actual [`Frame`]s to yield.
-    fn sig_next(&mut self) -> Self::Frame {
-        self.next().unwrap_or(Self::Frame::equil())
+impl<S> SeekableSignal for &mut S
+where
+    S: SeekableSignal + ?Sized,
+{
+    fn seek(&mut self, position: usize) {
+        (**self).seek(position)
     }
 
-    /// Returns the `n`th [`Frame`] from this [`Signal`], starting at 0. This
-    /// will advance the [`Signal`], so multiple calls to `nth` with the same
-    /// `n` will give different results.
-    ///
-    /// ```
-    /// use sampara::{signal, Signal};
-    ///
-    /// fn main() {
-    ///     let mut signal = signal::from_frames(0..=9);
-    ///
-    ///     assert_eq!(signal.nth(3), Some(3));
-    ///     assert_eq!(signal.nth(3), Some(7));
-    ///     assert_eq!(signal.nth(3), None);
-    /// }
-    /// ```
-    fn nth(&mut self, n: usize) -> Option<Self::Frame> {
-        self.advance_by(n).ok()?;
-        self.next()
+    fn position(&self) -> usize {
+        (**self).position()
     }
+}
 
-    /// Borrows this [`Signal`] rather than consuming it.
-    ///
-    /// This is useful for applying adaptors while still retaining ownership of
-    /// the original [`Signal`].
+impl<S> SeekableSignal for Box<S>
+where
+    S: SeekableSignal + ?Sized,
+{
+    fn seek(&mut self, position: usize) {
+        (**self).seek(position)
+    }
+
+    fn position(&self) -> usize {
+        (**self).position()
+    }
+}
+
+/// [`Signal`]s that know exactly how many [`Frame`]s they have left, like
+/// [`ExactSizeIterator`].
+///
+/// [`Signal::len_hint`] must return the exact length as both of its bounds.
+/// Adapters that change the number of [`Frame`]s, such as those that flush a
+/// tail once their input ends, still know their exact length whenever their
+/// input does.
+///
+/// ```
+/// use sampara::{signal, ExactSizeSignal, Signal};
+///
+/// fn main() {
+///     let mut signal = signal::from_samples_fixed::<_, 1>(vec![0.5f32; 48000])
+///         .mul_amp(0.5)
+///         .delay(100);
+///
+///     assert_eq!(signal.len(), 48100);
+///
+///     signal.advance_by(48000).unwrap();
+///     assert_eq!(signal.len(), 100);
+/// }
+/// ```
+pub trait ExactSizeSignal: Signal {
+    /// The number of [`Frame`]s left to yield.
     ///
-    /// ```
-    /// use sampara::{signal, Signal};
+    /// # Panics
     ///
-    /// fn main() {
-    ///     let mut signal = signal::from_frames(vec![0, 1, 2, 3]);
-    ///     assert_eq!(signal.next(), Some(0));
-    ///     assert_eq!(signal.by_ref().add_amp(10).next(), Some(11));
-    ///     assert_eq!(signal.by_ref().mul_amp(2.5_f32).next(), Some(5));
-    ///     assert_eq!(signal.next(), Some(3));
-    /// }
-    /// ```
-    fn by_ref(&mut self) -> &mut Self
+    /// Panics if [`Signal::len_hint`] does not return an exact length.
+    fn len(&self) -> usize {
+        let (lower, upper) = self.len_hint();
+        assert_eq!(upper, Some(lower), "length hint must be exact");
+        lower
+    }
+
+    /// Whether there are no [`Frame`]s left to yield.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<S> ExactSizeSignal for &mut S where S: ExactSizeSignal + ?Sized {}
+
+impl<S> ExactSizeSignal for Box<S> where S: ExactSizeSignal + ?Sized {}
+
+/// Collections that the [`Frame`]s of a [`Signal`] can be written into, with
+/// [`Signal::collect_into`].
+pub trait CollectTarget<F>
+where
+    F: Frame,
+{
+    /// Writes the [`Frame`]s of a [`Signal`] into [`Self`], and returns the
+    /// number of [`Frame`]s written.
+    fn collect_signal<S>(&mut self, signal: S) -> usize
     where
-        Self: Sized,
+        S: Signal<Frame = F>;
+}
+
+/// Appends every [`Frame`].
+impl<F> CollectTarget<F> for Vec<F>
+where
+    F: Frame,
+{
+    fn collect_signal<S>(&mut self, signal: S) -> usize
+    where
+        S: Signal<Frame = F>,
     {
-        self
+        let len = self.len();
+        self.extend(signal.into_frames());
+        self.len() - len
     }
+}
 
-    /// Eagerly advances and discards `N` [`Frame`]s from [`Self`]. If there
-    /// are fewer than `N` [`Frame`]s found, this will return `Err(X)`, where
-    /// `X` is the number of [`Frame`]s actually advanced. Otherwise, returns
-    /// `Ok(())`.
-    ///
-    /// ```
-    /// use sampara::{signal, Signal};
-    ///
-    /// fn main() {
-    ///     let mut signal = signal::from_frames(0u8..=9);
-    ///
-    ///     // Skip ahead 5 frames.
-    ///     assert_eq!(signal.advance_by(5), Ok(()));
-    ///
-    ///     assert_eq!(signal.next(), Some(5));
-    ///     assert_eq!(signal.next(), Some(6));
-    ///
-    ///     // Try to skip ahead 5 more frames.
-    ///     assert_eq!(signal.advance_by(5), Err(3));
-    ///
-    ///     assert_eq!(signal.next(), None);
-    /// }
-    /// ```
-    fn advance_by(&mut self, n: usize) -> Result<(), usize> {
-        let mut left = n;
-        while left > 0 {
-            self.next().ok_or_else(|| n - left)?;
-            left -= 1;
+/// Overwrites [`Frame`]s from the start, until either the [`Signal`] is
+/// exhausted or the slice is full.
+impl<F> CollectTarget<F> for [F]
+where
+    F: Frame,
+{
+    fn collect_signal<S>(&mut self, mut signal: S) -> usize
+    where
+        S: Signal<Frame = F>,
+    {
+        for (written, slot) in self.iter_mut().enumerate() {
+            match signal.next() {
+                Some(frame) => *slot = frame,
+                None => return written,
+            }
         }
 
-        Ok(())
+        self.len()
+    }
+}
+
+/// Appends every [`Frame`] to a separate [`Vec`] for each channel. Channels
+/// that a [`Frame`] does not have are padded with equilibrium, and channels
+/// that there is no [`Vec`] for are dropped.
+impl<F> CollectTarget<F> for [Vec<F::Sample>]
+where
+    F: Frame,
+{
+    fn collect_signal<S>(&mut self, signal: S) -> usize
+    where
+        S: Signal<Frame = F>,
+    {
+        let (lower, _) = signal.len_hint();
+        for channel in self.iter_mut() {
+            channel.reserve(lower);
+        }
+
+        let mut written = 0;
+        for frame in signal.into_frames() {
+            let mut samples = frame.into_iter();
+            for channel in self.iter_mut() {
+                channel.push(samples.next().unwrap_or(F::Sample::EQUILIBRIUM));
+            }
+
+            written += 1;
+        }
+
+        written
     }
 }
 
+/// The length hint of the shorter of two [`Signal`]s.
+pub(crate) fn min_len_hint(
+    (a_lower, a_upper): (usize, Option<usize>),
+    (b_lower, b_upper): (usize, Option<usize>),
+) -> (usize, Option<usize>) {
+    let upper = match (a_upper, b_upper) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, None) | (None, a) => a,
+    };
+
+    (a_lower.min(b_lower), upper)
+}
+
+/// Adds a tail of `tail` [`Frame`]s to a length hint, for adapters that flush
+/// a tail once their input ends. The tail is only yielded after at least one
+/// [`Frame`] of input, so it is not added to a bound of 0 unless the adapter
+/// has `started`.
+pub(crate) fn len_hint_with_tail(
+    (lower, upper): (usize, Option<usize>),
+    tail: usize,
+    started: bool,
+) -> (usize, Option<usize>) {
+    let add = |n: usize| {
+        if n > 0 || started {
+            n.checked_add(tail)
+        } else {
+            Some(0)
+        }
+    };
+
+    (add(lower).unwrap_or(usize::MAX), upper.and_then(add))
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 /*                            MODULE-LEVEL_METHODS                            */
 ////////////////////////////////////////////////////////////////////////////////
@@ -106,22 +1422,22 @@ pub trait Signal {
 /// closure that produces a [`Option<Frame>`] for each iteration.
 ///
 /// ```
-/// use sampara::{signal, Signal};
+/// use sampara::{signal, Fixed, Signal};
 ///
 /// fn main() {
 ///     let mut state = 1;
 ///     let mut signal = signal::from_fn(|| {
 ///         if state < 4 {
-///             let frame = [state, state * 2, state * 3];
+///             let frame = Fixed::from([state, state * 2, state * 3]);
 ///             state += 1;
 ///             Some(frame)
 ///         }
 ///         else { None }
 ///     });
 ///
-///     assert_eq!(signal.next(), Some([1, 2, 3]));
-///     assert_eq!(signal.next(), Some([2, 4, 6]));
-///     assert_eq!(signal.next(), Some([3, 6, 9]));
+///     assert_eq!(signal.next(), Some([1, 2, 3].into()));
+///     assert_eq!(signal.next(), Some([2, 4, 6].into()));
+///     assert_eq!(signal.next(), Some([3, 6, 9].into()));
 ///     assert_eq!(signal.next(), None);
 /// }
 /// ```
@@ -133,84 +1449,107 @@ where
     FromFn(gen_fn)
 }
 
-// /// Creates a new [`Signal`] where each [`Frame`] is copied from a given
-// /// constant [`Frame`].
-// ///
-// /// ```
-// /// use sampara::{signal, Signal};
-// ///
-// /// fn main() {
-// ///     let mut signal = signal::constant([1, 2, 3, 4]);
-// ///
-// ///     assert_eq!(signal.next(), Some([1, 2, 3, 4]));
-// ///     assert_eq!(signal.next(), Some([1, 2, 3, 4]));
-// ///     assert_eq!(signal.next(), Some([1, 2, 3, 4]));
-// ///     assert_eq!(signal.next(), Some([1, 2, 3, 4]));
-// /// }
-// /// ```
-// pub fn constant<F, const N: usize>(frame: F) -> Constant<F, N>
-// where
-//     F: Frame<N>,
-// {
-//     Constant(frame)
-// }
-
-// /// Creates a new [`Signal`] that always yields [`Frame::EQUILIBRIUM`].
-// ///
-// /// ```
-// /// use sampara::{signal, Signal};
-// ///
-// /// fn main() {
-// ///     let mut signal = signal::equilibrium();
-// ///
-// ///     assert_eq!(signal.next(), Some([0, 0]));
-// ///     assert_eq!(signal.next(), Some([0, 0]));
-// ///     assert_eq!(signal.next(), Some([0, 0]));
-// ///     assert_eq!(signal.next(), Some([0, 0]));
-// /// }
-// /// ```
-// pub fn equilibrium<F, const N: usize>() -> Equilibrium<F, N>
-// where
-//     F: Frame<N>,
-// {
-//     Equilibrium(Default::default())
-// }
-
-// /// Creates an empty [`Signal`] that yields no [`Frame`]s.
-// ///
-// /// ```
-// /// use sampara::{signal, Signal};
-// ///
-// /// fn main() {
-// ///     // Need to have redundant number of channels, until associated consts
-// ///     // can be used as const generics.
-// ///     let mut signal = signal::empty::<[i8; 2], 2>();
-// ///
-// ///     assert_eq!(signal.next(), None);
-// ///     assert_eq!(signal.next(), None);
-// ///     assert_eq!(signal.next(), None);
-// ///     assert_eq!(signal.next(), None);
-// /// }
-// /// ```
-// pub fn empty<F, const N: usize>() -> Empty<F, N>
-// where
-//     F: Frame<N>,
-// {
-//     Empty(Default::default())
-// }
+/// Creates a new [`Signal`] where each [`Frame`] is copied from a given
+/// constant [`Frame`].
+///
+/// ```
+/// use sampara::{signal, Fixed, Signal};
+///
+/// fn main() {
+///     let mut signal = signal::constant(Fixed::from([1, 2, 3, 4]));
+///
+///     assert_eq!(signal.next(), Some([1, 2, 3, 4].into()));
+///     assert_eq!(signal.next(), Some([1, 2, 3, 4].into()));
+///     assert_eq!(signal.next(), Some([1, 2, 3, 4].into()));
+///     assert_eq!(signal.next(), Some([1, 2, 3, 4].into()));
+/// }
+/// ```
+pub fn constant<F>(frame: F) -> Constant<F>
+where
+    F: Frame,
+{
+    Constant(frame)
+}
+
+/// Creates a new [`Signal`] that always yields [`Frame::equil`].
+///
+/// ```
+/// use sampara::{signal, Fixed, Signal};
+///
+/// fn main() {
+///     let mut signal = signal::equilibrium::<Fixed<u8, 2>>();
+///
+///     assert_eq!(signal.next(), Some([128, 128].into()));
+///     assert_eq!(signal.next(), Some([128, 128].into()));
+///     assert_eq!(signal.next(), Some([128, 128].into()));
+///     assert_eq!(signal.next(), Some([128, 128].into()));
+/// }
+/// ```
+pub fn equilibrium<F>() -> Constant<F>
+where
+    F: Frame,
+{
+    Constant(F::equil())
+}
+
+/// Creates an empty [`Signal`] that yields no [`Frame`]s.
+///
+/// ```
+/// use sampara::{signal, Fixed, Signal};
+///
+/// fn main() {
+///     let mut signal = signal::empty::<Fixed<i8, 2>>();
+///
+///     assert_eq!(signal.next(), None);
+///     assert_eq!(signal.next(), None);
+///     assert_eq!(signal.next(), None);
+///     assert_eq!(signal.next(), None);
+/// }
+/// ```
+pub fn empty<F>() -> Empty<F>
+where
+    F: Frame,
+{
+    Empty(PhantomData)
+}
+
+/// Creates a new [`SeekableSignal`] that yields copies of the [`Frame`]s in a
+/// slice.
+///
+/// ```
+/// use sampara::{signal, Fixed, SeekableSignal, Signal};
+///
+/// fn main() {
+///     let frames = [Fixed::from([1, -1]), Fixed::from([2, -2])];
+///     let mut signal = signal::from_slice(&frames);
+///
+///     assert_eq!(signal.next(), Some([1, -1].into()));
+///     assert_eq!(signal.next(), Some([2, -2].into()));
+///     assert_eq!(signal.next(), None);
+///
+///     signal.seek(1);
+///     assert_eq!(signal.next(), Some([2, -2].into()));
+/// }
+/// ```
+pub fn from_slice<F>(frames: &[F]) -> FromSlice<'_, F>
+where
+    F: Frame,
+{
+    FromSlice { frames, pos: 0 }
+}
 
 /// Creates a new [`Signal`] by wrapping an iterable that yields [`Frame`]s.
 ///
 /// ```
-/// use sampara::{signal, Signal};
+/// use sampara::{signal, Fixed, Signal};
 ///
 /// fn main() {
 ///     let frames = vec![[0, 0], [16, -16], [32, -32]];
-///     let mut signal = signal::from_frames(frames);
+///     let mut signal = signal::from_frames(frames.into_iter().map(Fixed::from));
 ///
-///     assert_eq!(signal.next(), Some([0, 0]));
-///     assert_eq!(signal.next(), Some([16, -16]));
-///     assert_eq!(signal.next(), Some([32, -32]));
+///     assert_eq!(signal.next(), Some([0, 0].into()));
+///     assert_eq!(signal.next(), Some([16, -16].into()));
+///     assert_eq!(signal.next(), Some([32, -32].into()));
 ///     assert_eq!(signal.next(), None);
 /// }
 /// ```
@@ -257,4 +1596,188 @@ where
     I::Item: Sample,
 {
     FromSamplesDynamic(iter.into_iter(), n)
-}
\ No newline at end of file
+}
+
+/// Creates a new [`Signal`] that reads interleaved [`Sample`]s of a given byte
+/// order from a [`Read`], and yields them as fixed-size [`Frame`]s.
+///
+/// The [`Signal`] ends at the end of the reader, or at the first I/O error.
+/// The error can be taken from the underlying [`ReadSamples`], which is
+/// reachable with `get_mut`. Any trailing bytes that do not complete a
+/// [`Frame`] are discarded. Each [`Sample`] is read separately, so unbuffered
+/// readers should be wrapped in a [`std::io::BufReader`].
+///
+/// ```
+/// use sampara::{signal, Signal};
+/// use sampara::sample::LittleEndian;
+///
+/// fn main() {
+///     let bytes: &[u8] = &[0x01, 0x00, 0xFF, 0xFF, 0x00, 0x01, 0x00, 0xFF, 0x7F];
+///     let mut signal = signal::from_bytes::<i16, LittleEndian, 2, _>(bytes);
+///
+///     assert_eq!(signal.next(), Some([1, -1].into()));
+///     assert_eq!(signal.next(), Some([256, -256].into()));
+///     assert_eq!(signal.next(), None);
+///     assert!(signal.get_mut().take_error().is_none());
+/// }
+/// ```
+pub fn from_bytes<S, E, const N: usize, R>(reader: R) -> FromSamplesFixed<ReadSamples<R, S, E>, N>
+where
+    S: ByteSample,
+    E: ByteOrder,
+    R: Read,
+{
+    from_samples_fixed(ReadSamples::new(reader))
+}
+
+/// Like [`from_bytes`], but yields dynamically-sized [`Frame`]s with `n`
+/// channels.
+///
+/// ```
+/// use sampara::{signal, Signal};
+/// use sampara::sample::{BigEndian, Q23};
+///
+/// fn main() {
+///     let bytes: &[u8] = &[0x40, 0x00, 0x00, 0xC0, 0x00, 0x00, 0x00, 0x00, 0x01];
+///     let mut signal = signal::from_bytes_dynamic::<Q23, BigEndian, _>(bytes, 3);
+///
+///     let expected = vec![Q23::from_bits(0x40_0000), Q23::from_bits(-0x40_0000), Q23::from_bits(1)];
+///     assert_eq!(signal.next(), Some(expected.into()));
+///     assert_eq!(signal.next(), None);
+/// }
+/// ```
+pub fn from_bytes_dynamic<S, E, R>(reader: R, n: usize) -> FromSamplesDynamic<ReadSamples<R, S, E>>
+where
+    S: ByteSample,
+    E: ByteOrder,
+    R: Read,
+{
+    from_samples_dynamic(ReadSamples::new(reader), n)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::fir::Method;
+    use crate::sample::LittleEndian;
+    use crate::spectrum::{Complex, Istft, Spectral, Window};
+
+    /// Checks that the length of a signal is exact after every frame.
+    fn assert_exact_len<S: ExactSizeSignal>(mut signal: S, len: usize) {
+        assert_eq!(signal.len(), len);
+
+        for left in (0..len).rev() {
+            assert!(signal.next().is_some());
+            assert_eq!(signal.len(), left);
+        }
+
+        assert!(signal.next().is_none());
+    }
+
+    #[test]
+    fn seek_through_adapters() {
+        let bytes: Vec<u8> = (0..8i16).flat_map(|s| s.to_le_bytes()).collect();
+        let mut signal = from_bytes::<i16, LittleEndian, 2, _>(Cursor::new(bytes))
+            .map(|f| Fixed::from([f.into_array()[0]]))
+            .peekable();
+
+        assert_eq!(signal.peek(), Some(&[0].into()));
+        assert_eq!(signal.position(), 0);
+
+        signal.seek(2);
+        assert_eq!(signal.peek(), Some(&[4].into()));
+        assert_eq!(signal.position(), 2);
+        assert_eq!(signal.next(), Some([4].into()));
+        assert_eq!(signal.next(), Some([6].into()));
+        assert_eq!(signal.next(), None);
+
+        // Seeking past the end leaves the signal exhausted.
+        signal.seek(10);
+        assert_eq!(signal.next(), None);
+
+        signal.seek(1);
+        let mut taken = signal.take(2);
+        taken.seek(5);
+        assert_eq!(taken.position(), 2);
+        assert_eq!(taken.next(), None);
+
+        taken.seek(1);
+        assert_eq!(taken.next(), Some([4].into()));
+
+        // A take that is longer than its signal stops at the end of it.
+        let frames: Vec<Fixed<i16, 1>> = (0..6).map(|s| [s].into()).collect();
+        let mut taken = from_slice(&frames).take(10);
+        taken.seek(8);
+        assert_eq!(taken.position(), 6);
+        assert_eq!(taken.next(), None);
+
+        taken.seek(0);
+        assert_eq!(taken.next(), Some([0].into()));
+    }
+
+    #[test]
+    fn exact_lengths() {
+        let input = || from_samples_fixed::<_, 2>(vec![0.5f32; 20]);
+
+        assert_exact_len(input().peekable().take(3).delay(5), 8);
+        assert_exact_len(input().take(0).delay(5), 0);
+
+        let ir =
+            ImpulseResponse::new(vec![0.5; 3]).with_method(Method::Partitioned { block_size: 4 });
+        assert_exact_len(input().convolve(ir), 16);
+
+        assert_exact_len(Spectral::new(input(), 16, 4, Window::Hann, |_| {}), 22);
+
+        let spectra = vec![vec![vec![Complex::<f32>::ZERO; 5]]; 3];
+        assert_exact_len(Istft::new(spectra, 8, 4, Window::Hann), 16);
+
+        let mut peekable = input().peekable();
+        peekable.peek();
+        assert_exact_len(peekable, 10);
+    }
+
+    #[test]
+    fn into_iterators() {
+        let input = || from_samples_fixed::<_, 2>(vec![1, -1, 2, -2, 3]);
+
+        let mut frames = input().into_frames();
+        assert_eq!(frames.len(), 2);
+        frames.next();
+        assert_eq!(frames.len(), 1);
+
+        let mut samples = input().into_samples();
+        assert_eq!(samples.size_hint(), (0, None));
+        samples.next();
+        assert_eq!(samples.size_hint(), (1, None));
+        assert_eq!(samples.collect::<Vec<_>>(), vec![-1, 2, -2]);
+
+        // Frames are only written while there is room.
+        let mut slice = [Fixed::from([0, 0]); 3];
+        assert_eq!(input().collect_into(&mut slice[..]), 2);
+        assert_eq!(slice[2], Fixed::from([0, 0]));
+    }
+
+    #[test]
+    fn borrowed_and_boxed() {
+        let frames: Vec<Fixed<i32, 1>> = (0..6).map(|i| [i].into()).collect();
+        let mut signal = from_slice(&frames);
+
+        // Adapters can borrow a signal, and leave it where they stopped.
+        let mut head = signal.by_ref().take(2);
+        assert_eq!(head.len(), 2);
+        head.seek(1);
+        assert_eq!(head.next(), Some([1].into()));
+        assert_eq!(signal.next(), Some([2].into()));
+
+        let mut boxed: Box<dyn Signal<Frame = Fixed<i32, 1>>> = signal.boxed();
+        assert_eq!(boxed.len_hint(), (3, Some(3)));
+        assert_eq!(boxed.nth(1), Some([4].into()));
+
+        let mut erased = boxed.into_dyn();
+        assert_eq!(erased.len_hint(), (1, Some(1)));
+        assert_eq!(erased.next(), Some(vec![5].into()));
+        assert_eq!(erased.next(), None);
+    }
+}
@@ -1,6 +1,10 @@
+use std::marker::PhantomData;
+
+use std::io::{Read, Seek};
+
 use crate::frame::{Dynamic, Fixed, Frame};
-use crate::sample::Sample;
-use crate::signal::Signal;
+use crate::sample::{ByteOrder, ByteSample, ReadSamples, Sample};
+use crate::signal::{ExactSizeSignal, SeekableSignal, Signal};
 
 /// A [`Signal`] that yields [`Frame`]s by calling a closure for each iteration.
 /// This closure should return [`Option<Frame>`].
@@ -22,6 +26,99 @@ where
     }
 }
 
+/// A [`Signal`] that always yields the same [`Frame`].
+#[derive(Clone)]
+pub struct Constant<F>(pub(super) F)
+where
+    F: Frame;
+
+impl<F> Signal for Constant<F>
+where
+    F: Frame,
+{
+    type Frame = F;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Frame> {
+        Some(self.0.clone())
+    }
+
+    #[inline]
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+/// A [`Signal`] that yields no [`Frame`]s.
+#[derive(Clone)]
+pub struct Empty<F>(pub(super) PhantomData<F>)
+where
+    F: Frame;
+
+impl<F> Signal for Empty<F>
+where
+    F: Frame,
+{
+    type Frame = F;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Frame> {
+        None
+    }
+
+    #[inline]
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(0))
+    }
+}
+
+impl<F> ExactSizeSignal for Empty<F> where F: Frame {}
+
+/// A [`Signal`] that yields copies of the [`Frame`]s in a borrowed slice.
+#[derive(Clone)]
+pub struct FromSlice<'a, F>
+where
+    F: Frame,
+{
+    pub(super) frames: &'a [F],
+    pub(super) pos: usize,
+}
+
+impl<'a, F> Signal for FromSlice<'a, F>
+where
+    F: Frame,
+{
+    type Frame = F;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Frame> {
+        let frame = self.frames.get(self.pos)?.clone();
+        self.pos += 1;
+        Some(frame)
+    }
+
+    #[inline]
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        let len = self.frames.len() - self.pos;
+        (len, Some(len))
+    }
+}
+
+impl<'a, F> ExactSizeSignal for FromSlice<'a, F> where F: Frame {}
+
+impl<'a, F> SeekableSignal for FromSlice<'a, F>
+where
+    F: Frame,
+{
+    fn seek(&mut self, position: usize) {
+        self.pos = position.min(self.frames.len());
+    }
+
+    fn position(&self) -> usize {
+        self.pos
+    }
+}
+
 /// A [`Signal`] that is powered by an underlying [`Iterator`] that yields
 /// [`Frame`]s.
 pub struct FromFrames<I>(pub(super) I)
@@ -40,6 +137,18 @@ where
     fn next(&mut self) -> Option<Self::Frame> {
         self.0.next()
     }
+
+    #[inline]
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<I> ExactSizeSignal for FromFrames<I>
+where
+    I: ExactSizeIterator,
+    I::Item: Frame,
+{
 }
 
 /// A [`Signal`] that is powered by an underlying [`Iterator`] that yields
@@ -49,6 +158,27 @@ where
     I: Iterator,
     I::Item: Sample;
 
+impl<I, const N: usize> FromSamplesFixed<I, N>
+where
+    I: Iterator,
+    I::Item: Sample,
+{
+    /// Returns a reference to the underlying [`Iterator`].
+    pub fn get_ref(&self) -> &I {
+        &self.0
+    }
+
+    /// Returns a mutable reference to the underlying [`Iterator`].
+    pub fn get_mut(&mut self) -> &mut I {
+        &mut self.0
+    }
+
+    /// Consumes this [`Signal`], returning the underlying [`Iterator`].
+    pub fn into_inner(self) -> I {
+        self.0
+    }
+}
+
 impl<I, const N: usize> Signal for FromSamplesFixed<I, N>
 where
     I: Iterator,
@@ -60,6 +190,18 @@ where
     fn next(&mut self) -> Option<Self::Frame> {
         Fixed::from_samples(&mut self.0)
     }
+
+    #[inline]
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        frames_hint(self.0.size_hint(), N)
+    }
+}
+
+impl<I, const N: usize> ExactSizeSignal for FromSamplesFixed<I, N>
+where
+    I: ExactSizeIterator,
+    I::Item: Sample,
+{
 }
 
 /// A [`Signal`] that is powered by an underlying [`Iterator`] that yields
@@ -69,6 +211,27 @@ where
     I: Iterator,
     I::Item: Sample;
 
+impl<I> FromSamplesDynamic<I>
+where
+    I: Iterator,
+    I::Item: Sample,
+{
+    /// Returns a reference to the underlying [`Iterator`].
+    pub fn get_ref(&self) -> &I {
+        &self.0
+    }
+
+    /// Returns a mutable reference to the underlying [`Iterator`].
+    pub fn get_mut(&mut self) -> &mut I {
+        &mut self.0
+    }
+
+    /// Consumes this [`Signal`], returning the underlying [`Iterator`].
+    pub fn into_inner(self) -> I {
+        self.0
+    }
+}
+
 impl<I> Signal for FromSamplesDynamic<I>
 where
     I: Iterator,
@@ -80,4 +243,56 @@ where
     fn next(&mut self) -> Option<Self::Frame> {
         Dynamic::from_samples(&mut self.0, self.1)
     }
-}
\ No newline at end of file
+
+    #[inline]
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        frames_hint(self.0.size_hint(), self.1)
+    }
+}
+
+impl<I> ExactSizeSignal for FromSamplesDynamic<I>
+where
+    I: ExactSizeIterator,
+    I::Item: Sample,
+{
+}
+
+impl<R, S, E, const N: usize> SeekableSignal for FromSamplesFixed<ReadSamples<R, S, E>, N>
+where
+    R: Read + Seek,
+    S: ByteSample,
+    E: ByteOrder,
+{
+    fn seek(&mut self, position: usize) {
+        self.0.seek(position * N);
+    }
+
+    fn position(&self) -> usize {
+        self.0.position() / N.max(1)
+    }
+}
+
+impl<R, S, E> SeekableSignal for FromSamplesDynamic<ReadSamples<R, S, E>>
+where
+    R: Read + Seek,
+    S: ByteSample,
+    E: ByteOrder,
+{
+    fn seek(&mut self, position: usize) {
+        self.0.seek(position * self.1);
+    }
+
+    fn position(&self) -> usize {
+        self.0.position() / self.1.max(1)
+    }
+}
+
+/// Converts the size hint of an [`Iterator`] of [`Sample`]s into the length
+/// hint of the [`Frame`]s that they fill, dropping any incomplete [`Frame`].
+/// Frames without channels never run out.
+fn frames_hint((lower, upper): (usize, Option<usize>), channels: usize) -> (usize, Option<usize>) {
+    match channels {
+        0 => (usize::MAX, None),
+        n => (lower / n, upper.map(|upper| upper / n)),
+    }
+}
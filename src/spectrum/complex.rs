@@ -0,0 +1,161 @@
+use core::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::sample::FloatSample;
+
+/// A complex number, with real and imaginary parts of a [`FloatSample`] type.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Complex<T>
+where
+    T: FloatSample,
+{
+    pub re: T,
+    pub im: T,
+}
+
+impl<T> Complex<T>
+where
+    T: FloatSample,
+{
+    /// The complex number 0 + 0i.
+    pub const ZERO: Self = Complex {
+        re: T::EQUILIBRIUM,
+        im: T::EQUILIBRIUM,
+    };
+
+    /// Creates a new [`Complex`] from its real and imaginary parts.
+    #[inline]
+    pub fn new(re: T, im: T) -> Self {
+        Complex { re, im }
+    }
+
+    /// Creates a new [`Complex`] from polar coordinates.
+    ///
+    /// ```
+    /// use sampara::spectrum::Complex;
+    ///
+    /// fn main() {
+    ///     let c = Complex::from_polar(2.0f64, core::f64::consts::FRAC_PI_2);
+    ///     assert!(c.re.abs() < 1e-12);
+    ///     assert!((c.im - 2.0).abs() < 1e-12);
+    /// }
+    /// ```
+    #[inline]
+    pub fn from_polar(norm: T, arg: T) -> Self {
+        let (sin, cos) = arg.sin_cos();
+        Complex::new(norm * cos, norm * sin)
+    }
+
+    /// Returns the complex conjugate.
+    #[inline]
+    pub fn conj(self) -> Self {
+        Complex::new(self.re, -self.im)
+    }
+
+    /// Returns the squared magnitude, which is cheaper to compute than
+    /// [`Self::norm`].
+    #[inline]
+    pub fn norm_sqr(self) -> T {
+        self.re * self.re + self.im * self.im
+    }
+
+    /// Returns the magnitude.
+    #[inline]
+    pub fn norm(self) -> T {
+        self.re.hypot(self.im)
+    }
+
+    /// Returns the phase angle, in radians, in the interval [-π, π].
+    #[inline]
+    pub fn arg(self) -> T {
+        self.im.atan2(self.re)
+    }
+
+    /// Multiplies both parts by a real scalar.
+    #[inline]
+    pub fn scale(self, t: T) -> Self {
+        Complex::new(self.re * t, self.im * t)
+    }
+
+    /// Multiplies by the imaginary unit.
+    #[inline]
+    pub(crate) fn mul_i(self) -> Self {
+        Complex::new(-self.im, self.re)
+    }
+}
+
+impl<T: FloatSample> Add for Complex<T> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Complex::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+impl<T: FloatSample> Sub for Complex<T> {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Complex::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+impl<T: FloatSample> Mul for Complex<T> {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        Complex::new(
+            self.re * rhs.re - self.im * rhs.im,
+            self.re * rhs.im + self.im * rhs.re,
+        )
+    }
+}
+
+impl<T: FloatSample> Div for Complex<T> {
+    type Output = Self;
+
+    #[inline]
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        (self * rhs.conj()).scale(rhs.norm_sqr().recip())
+    }
+}
+
+impl<T: FloatSample> Neg for Complex<T> {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Complex::new(-self.re, -self.im)
+    }
+}
+
+impl<T: FloatSample> AddAssign for Complex<T> {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: FloatSample> SubAssign for Complex<T> {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: FloatSample> MulAssign for Complex<T> {
+    #[inline]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T: FloatSample> From<T> for Complex<T> {
+    #[inline]
+    fn from(re: T) -> Self {
+        Complex::new(re, T::EQUILIBRIUM)
+    }
+}
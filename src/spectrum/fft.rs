@@ -0,0 +1,398 @@
+use crate::sample::FloatSample;
+use crate::spectrum::Complex;
+
+/// A complex-to-complex FFT of a fixed size. This is the extension point for
+/// plugging alternative FFT implementations into [`RealFft`] and the rest of
+/// the [`spectrum`](crate::spectrum) module.
+///
+/// Implementors must compute the unnormalized forward transform, and an
+/// inverse transform that is normalized by `1 / len`, so that applying
+/// [`Self::forward`] followed by [`Self::inverse`] is the identity.
+pub trait FftBackend<T>
+where
+    T: FloatSample,
+{
+    /// The number of points transformed.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if this transforms zero points.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Computes the forward FFT in place. `data` must have a length of
+    /// [`Self::len`].
+    fn forward(&self, data: &mut [Complex<T>]);
+
+    /// Computes the normalized inverse FFT in place. `data` must have a length
+    /// of [`Self::len`].
+    fn inverse(&self, data: &mut [Complex<T>]);
+}
+
+/// A pure-Rust, iterative radix-2 Cooley-Tukey FFT, for sizes that are powers
+/// of two. Twiddle factors and the bit-reversal permutation are precomputed,
+/// so transforms do not allocate.
+///
+/// ```
+/// use sampara::spectrum::{Complex, Fft, FftBackend};
+///
+/// fn main() {
+///     let fft = Fft::<f64>::new(4);
+///
+///     let mut data = [1.0, 0.0, 0.0, 0.0].map(Complex::from);
+///     fft.forward(&mut data);
+///     assert_eq!(data, [Complex::new(1.0, 0.0); 4]);
+///
+///     fft.inverse(&mut data);
+///     assert_eq!(data, [1.0, 0.0, 0.0, 0.0].map(Complex::from));
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Fft<T>
+where
+    T: FloatSample,
+{
+    // Twiddle factors `e^(-2πik/n)` for `k` in `0..n/2`.
+    twiddles: Vec<Complex<T>>,
+    // The bit-reversed index for each index.
+    bit_rev: Vec<usize>,
+}
+
+impl<T> Fft<T>
+where
+    T: FloatSample,
+{
+    /// Creates a new [`Fft`] for a given number of points.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is not a power of two.
+    pub fn new(len: usize) -> Self {
+        assert!(len.is_power_of_two(), "FFT length must be a power of two");
+
+        let bits = len.trailing_zeros();
+        let bit_rev = (0..len)
+            .map(|i| {
+                if bits == 0 {
+                    0
+                } else {
+                    i.reverse_bits() >> (usize::BITS - bits)
+                }
+            })
+            .collect();
+
+        let twiddles = (0..len / 2)
+            .map(|k| {
+                let angle = -2.0 * core::f64::consts::PI * k as f64 / len as f64;
+                Complex::new(T::from_sample(angle.cos()), T::from_sample(angle.sin()))
+            })
+            .collect();
+
+        Self { twiddles, bit_rev }
+    }
+
+    fn transform(&self, data: &mut [Complex<T>], inverse: bool) {
+        let n = self.bit_rev.len();
+        assert_eq!(data.len(), n, "data length must match FFT length");
+
+        for (i, &j) in self.bit_rev.iter().enumerate() {
+            if i < j {
+                data.swap(i, j);
+            }
+        }
+
+        let mut size = 2;
+        while size <= n {
+            let half = size / 2;
+            let step = n / size;
+
+            for chunk in data.chunks_exact_mut(size) {
+                let (lo, hi) = chunk.split_at_mut(half);
+                for (k, (a, b)) in lo.iter_mut().zip(hi.iter_mut()).enumerate() {
+                    let w = self.twiddles[k * step];
+                    let w = if inverse { w.conj() } else { w };
+
+                    let t = *b * w;
+                    *b = *a - t;
+                    *a += t;
+                }
+            }
+
+            size *= 2;
+        }
+
+        if inverse {
+            let scale = T::from_sample(n as f64).recip();
+            for c in data.iter_mut() {
+                *c = c.scale(scale);
+            }
+        }
+    }
+}
+
+impl<T> FftBackend<T> for Fft<T>
+where
+    T: FloatSample,
+{
+    fn len(&self) -> usize {
+        self.bit_rev.len()
+    }
+
+    fn forward(&self, data: &mut [Complex<T>]) {
+        self.transform(data, false)
+    }
+
+    fn inverse(&self, data: &mut [Complex<T>]) {
+        self.transform(data, true)
+    }
+}
+
+/// An FFT of real-valued input, computed with a complex FFT of half the size.
+/// The spectrum of `n` real points is represented by its `n / 2 + 1`
+/// non-negative frequency bins.
+///
+/// ```
+/// use sampara::spectrum::RealFft;
+///
+/// fn main() {
+///     let mut fft = RealFft::<f64>::new(8);
+///     assert_eq!(fft.bins(), 5);
+///
+///     // A cosine that completes two cycles over the input.
+///     let input: Vec<f64> = (0..8)
+///         .map(|i| (2.0 * core::f64::consts::PI * 2.0 * i as f64 / 8.0).cos())
+///         .collect();
+///
+///     let mut spectrum = vec![Default::default(); fft.bins()];
+///     fft.forward(&input, &mut spectrum);
+///
+///     let mags: Vec<f64> = spectrum.iter().map(|c| c.norm().round()).collect();
+///     assert_eq!(mags, vec![0.0, 0.0, 4.0, 0.0, 0.0]);
+///
+///     let mut output = vec![0.0; 8];
+///     fft.inverse(&spectrum, &mut output);
+///     for (x, y) in input.iter().zip(output.iter()) {
+///         assert!((x - y).abs() < 1e-12);
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct RealFft<T, B = Fft<T>>
+where
+    T: FloatSample,
+    B: FftBackend<T>,
+{
+    backend: B,
+    // Twiddle factors `e^(-2πik/n)` for `k` in `0..n/2`.
+    twiddles: Vec<Complex<T>>,
+    scratch: Vec<Complex<T>>,
+}
+
+impl<T> RealFft<T>
+where
+    T: FloatSample,
+{
+    /// Creates a new [`RealFft`] for a given number of real points, using the
+    /// built-in [`Fft`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is not a power of two, or is less than 2.
+    pub fn new(len: usize) -> Self {
+        assert!(len >= 2, "real FFT length must be at least 2");
+        Self::with_backend(Fft::new(len / 2))
+    }
+}
+
+impl<T, B> RealFft<T, B>
+where
+    T: FloatSample,
+    B: FftBackend<T>,
+{
+    /// Creates a new [`RealFft`] of `2 * backend.len()` real points, using a
+    /// given [`FftBackend`].
+    pub fn with_backend(backend: B) -> Self {
+        let half = backend.len();
+        let len = 2 * half;
+
+        let twiddles = (0..half)
+            .map(|k| {
+                let angle = -2.0 * core::f64::consts::PI * k as f64 / len as f64;
+                Complex::new(T::from_sample(angle.cos()), T::from_sample(angle.sin()))
+            })
+            .collect();
+
+        Self {
+            backend,
+            twiddles,
+            scratch: vec![Complex::ZERO; half],
+        }
+    }
+
+    /// The number of real points transformed.
+    pub fn len(&self) -> usize {
+        2 * self.backend.len()
+    }
+
+    /// Returns `true` if this transforms zero points.
+    pub fn is_empty(&self) -> bool {
+        self.backend.is_empty()
+    }
+
+    /// The number of frequency bins in the spectrum, equal to
+    /// `self.len() / 2 + 1`.
+    pub fn bins(&self) -> usize {
+        self.backend.len() + 1
+    }
+
+    /// Computes the unnormalized spectrum of `input`, which must have a length
+    /// of [`Self::len`], into `output`, which must have a length of
+    /// [`Self::bins`]. This does not allocate.
+    pub fn forward(&mut self, input: &[T], output: &mut [Complex<T>]) {
+        let half = self.backend.len();
+        assert_eq!(input.len(), 2 * half, "input length must match FFT length");
+        assert_eq!(output.len(), half + 1, "output length must match bin count");
+
+        // Pack even samples into the real parts and odd samples into the
+        // imaginary parts, and transform at half size.
+        for (z, pair) in self.scratch.iter_mut().zip(input.chunks_exact(2)) {
+            *z = Complex::new(pair[0], pair[1]);
+        }
+        self.backend.forward(&mut self.scratch);
+
+        let half_t = T::from_sample(0.5f32);
+        let z0 = self.scratch[0];
+        output[0] = Complex::from(z0.re + z0.im);
+        output[half] = Complex::from(z0.re - z0.im);
+
+        for (k, out) in output.iter_mut().enumerate().take(half).skip(1) {
+            let zk = self.scratch[k];
+            let zn = self.scratch[half - k].conj();
+
+            // Multiplying by `-i / 2` separates out the odd samples.
+            let even = (zk + zn).scale(half_t);
+            let diff = (zk - zn).scale(half_t);
+            let odd = Complex::new(diff.im, -diff.re);
+
+            *out = even + self.twiddles[k] * odd;
+        }
+    }
+
+    /// Computes the real signal whose spectrum is `input`, which must have a
+    /// length of [`Self::bins`], into `output`, which must have a length of
+    /// [`Self::len`]. This is normalized, so that it exactly inverts
+    /// [`Self::forward`]. This does not allocate.
+    ///
+    /// The imaginary parts of the first and last bins are ignored.
+    pub fn inverse(&mut self, input: &[Complex<T>], output: &mut [T]) {
+        let half = self.backend.len();
+        assert_eq!(input.len(), half + 1, "input length must match bin count");
+        assert_eq!(
+            output.len(),
+            2 * half,
+            "output length must match FFT length"
+        );
+
+        let half_t = T::from_sample(0.5f32);
+
+        // The first and last bins of a real spectrum are purely real.
+        let (x0, xn) = (input[0].re, input[half].re);
+        self.scratch[0] = Complex::new((x0 + xn) * half_t, (x0 - xn) * half_t);
+
+        for k in 1..half {
+            let xk = input[k];
+            let xn = input[half - k].conj();
+
+            let even = (xk + xn).scale(half_t);
+            let odd = (xk - xn).scale(half_t) * self.twiddles[k].conj();
+
+            self.scratch[k] = even + odd.mul_i();
+        }
+
+        self.backend.inverse(&mut self.scratch);
+
+        for (pair, z) in output.chunks_exact_mut(2).zip(self.scratch.iter()) {
+            pair[0] = z.re;
+            pair[1] = z.im;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive_dft(input: &[Complex<f64>]) -> Vec<Complex<f64>> {
+        let n = input.len();
+        (0..n)
+            .map(|k| {
+                input
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &x)| {
+                        let angle = -2.0 * core::f64::consts::PI * (i * k) as f64 / n as f64;
+                        x * Complex::from_polar(1.0, angle)
+                    })
+                    .fold(Complex::ZERO, |a, b| a + b)
+            })
+            .collect()
+    }
+
+    fn test_input(n: usize) -> Vec<f64> {
+        (0..n)
+            .map(|i| ((i * 7 + 3) % 11) as f64 / 11.0 - 0.5)
+            .collect()
+    }
+
+    fn assert_close(a: Complex<f64>, b: Complex<f64>) {
+        assert!((a - b).norm() < 1e-9, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn complex_matches_naive() {
+        for n in [1, 2, 4, 8, 32, 256] {
+            let input: Vec<_> = test_input(n)
+                .into_iter()
+                .zip(test_input(n).into_iter().rev())
+                .map(|(re, im)| Complex::new(re, im))
+                .collect();
+
+            let expected = naive_dft(&input);
+
+            let fft = Fft::new(n);
+            let mut actual = input.clone();
+            fft.forward(&mut actual);
+
+            for (&a, &e) in actual.iter().zip(expected.iter()) {
+                assert_close(a, e);
+            }
+
+            fft.inverse(&mut actual);
+            for (&a, &e) in actual.iter().zip(input.iter()) {
+                assert_close(a, e);
+            }
+        }
+    }
+
+    #[test]
+    fn real_matches_naive() {
+        for n in [2, 4, 16, 128] {
+            let input = test_input(n);
+            let expected = naive_dft(&input.iter().copied().map(Complex::from).collect::<Vec<_>>());
+
+            let mut fft = RealFft::new(n);
+            let mut actual = vec![Complex::ZERO; fft.bins()];
+            fft.forward(&input, &mut actual);
+
+            for (&a, &e) in actual.iter().zip(expected.iter()) {
+                assert_close(a, e);
+            }
+
+            let mut output = vec![0.0; n];
+            fft.inverse(&actual, &mut output);
+            for (&a, &e) in output.iter().zip(input.iter()) {
+                assert!((a - e).abs() < 1e-9);
+            }
+        }
+    }
+}
@@ -0,0 +1,14 @@
+//! Frequency-domain analysis and resynthesis: FFTs, window functions, the
+//! short-time Fourier transform and its inverse.
+
+mod complex;
+mod fft;
+mod ola;
+mod stft;
+mod window;
+
+pub use self::complex::Complex;
+pub use self::fft::{Fft, FftBackend, RealFft};
+pub use self::ola::{Istft, Spectral};
+pub use self::stft::{Stft, bin_frequency};
+pub use self::window::Window;
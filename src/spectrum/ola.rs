@@ -0,0 +1,553 @@
+use crate::frame::{Dynamic, Frame};
+use crate::sample::{FloatSample, FromSample, IntoSample, Sample};
+use crate::signal::{ExactSizeSignal, FloatOf, Signal, len_hint_with_tail};
+use crate::spectrum::{Complex, RealFft, Window};
+
+/// A [`Signal`] adapter that processes an underlying [`Signal`] in the
+/// frequency domain, using weighted overlap-add (WOLA).
+///
+/// Every `hop` [`Frame`]s, the most recent `size` [`Frame`]s of input are
+/// windowed and transformed into one spectrum per channel, which are passed
+/// together to a closure that can edit them in place. The edited spectra are
+/// transformed back, windowed again, and overlapped and added into the output.
+/// The [`Sample`]s of the input are converted to their [`Sample::Float`] type
+/// for processing, and back again for output.
+///
+/// The window must satisfy the constant overlap-add condition for its square
+/// at the given hop size (see [`Window::wola_gain`]), so that unmodified
+/// spectra reconstruct the input exactly. For example, [`Window::Hann`] needs
+/// a hop size of at most a quarter of the transform size.
+///
+/// The output is delayed by [`Self::latency`] [`Frame`]s, and is extended by
+/// the same amount at the end so that every input [`Frame`] is output.
+///
+/// ```
+/// use sampara::{signal, Signal};
+/// use sampara::spectrum::{Complex, Spectral, Window};
+///
+/// fn main() {
+///     let input: Vec<f32> = (0..64).map(|i| [0.5, 0.0, -0.5, 0.0][i % 4]).collect();
+///     let signal = signal::from_samples_fixed::<_, 1>(input.iter().copied());
+///
+///     // Zero out the bins around a quarter of the sample rate.
+///     let mut spectral = Spectral::new(signal, 16, 4, Window::Hann, |spectra| {
+///         for spectrum in spectra.iter_mut() {
+///             spectrum[3..6].fill(Complex::ZERO);
+///         }
+///     });
+///     assert_eq!(spectral.latency(), 12);
+///
+///     let mut output = Vec::new();
+///     while let Some(frame) = spectral.next() {
+///         output.push(frame.into_array()[0]);
+///     }
+///     assert_eq!(output.len(), 64 + 12);
+///
+///     // Once the delay line has filled up, the tone is removed.
+///     assert!(output[32..64].iter().all(|x| x.abs() < 1e-6));
+/// }
+/// ```
+pub struct Spectral<S, F>
+where
+    S: Signal,
+{
+    signal: S,
+    func: F,
+    fft: RealFft<FloatOf<S>>,
+    window: Vec<FloatOf<S>>,
+    hop: usize,
+    // The reciprocal of the overlap-add gain of the squared window.
+    scale: FloatOf<S>,
+    // The first input frame, reused to build output frames with the same
+    // number of channels.
+    template: Option<S::Frame>,
+    // One buffer of the last `size` input samples for each channel.
+    inputs: Vec<Vec<FloatOf<S>>>,
+    // One buffer of `size` partially summed output samples for each channel,
+    // of which the first `hop` are complete after each block.
+    outputs: Vec<Vec<FloatOf<S>>>,
+    spectra: Vec<Vec<Complex<FloatOf<S>>>>,
+    scratch: Vec<FloatOf<S>>,
+    // The read position within the complete part of the output buffers.
+    position: usize,
+    consumed: usize,
+    emitted: usize,
+    done: bool,
+}
+
+impl<S, F> Spectral<S, F>
+where
+    S: Signal,
+    F: FnMut(&mut [Vec<Complex<FloatOf<S>>>]),
+{
+    /// Creates a new [`Spectral`] processor over a [`Signal`], with a given
+    /// transform size, hop size and window, and a closure that is called with
+    /// the spectra of all channels for each block. The window is generated in
+    /// its periodic form.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is not a power of two that is at least 2, if `hop` is
+    /// 0 or larger than `size`, or if the squared window does not overlap-add
+    /// to a constant at this hop size.
+    pub fn new(signal: S, size: usize, hop: usize, window: Window, func: F) -> Self {
+        assert!(hop > 0 && hop <= size, "hop size must be in 1..=size");
+
+        let gain = window
+            .wola_gain(size, hop)
+            .expect("squared window must overlap-add to a constant at this hop size");
+
+        let zero = FloatOf::<S>::EQUILIBRIUM;
+
+        Self {
+            signal,
+            func,
+            fft: RealFft::new(size),
+            window: window.periodic(size),
+            hop,
+            scale: FloatOf::<S>::from_sample(gain.recip()),
+            template: None,
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            spectra: Vec::new(),
+            scratch: vec![zero; size],
+            position: hop,
+            consumed: 0,
+            emitted: 0,
+            done: false,
+        }
+    }
+
+    /// The transform size, in [`Frame`]s.
+    pub fn size(&self) -> usize {
+        self.window.len()
+    }
+
+    /// The hop size, in [`Frame`]s.
+    pub fn hop(&self) -> usize {
+        self.hop
+    }
+
+    /// The number of [`Frame`]s that the output is delayed by, relative to
+    /// the input.
+    pub fn latency(&self) -> usize {
+        self.size() - self.hop
+    }
+
+    /// Reads the next `hop` input frames, and processes a block. Returns
+    /// `false` if the input was empty.
+    fn process_block(&mut self) -> bool {
+        let size = self.size();
+        let hop = self.hop;
+        let zero = FloatOf::<S>::EQUILIBRIUM;
+
+        let mut first = None;
+        if self.template.is_none() {
+            match self.signal.next() {
+                Some(frame) => {
+                    let channels = frame.len();
+                    self.inputs = vec![vec![zero; size]; channels];
+                    self.outputs = vec![vec![zero; size]; channels];
+                    self.spectra = vec![vec![Complex::ZERO; self.fft.bins()]; channels];
+                    self.template = Some(frame.clone());
+                    first = Some(frame);
+                }
+                None => return false,
+            }
+        }
+
+        for buffer in self.inputs.iter_mut().chain(self.outputs.iter_mut()) {
+            buffer.copy_within(hop..size, 0);
+            buffer[size - hop..].fill(zero);
+        }
+
+        for i in size - hop..size {
+            let frame = match first.take() {
+                Some(frame) => Some(frame),
+                None if self.done => None,
+                None => self.signal.next(),
+            };
+
+            match frame {
+                Some(frame) => {
+                    for (buffer, sample) in self.inputs.iter_mut().zip(frame) {
+                        buffer[i] = sample.into_sample();
+                    }
+
+                    self.consumed += 1;
+                }
+                None => self.done = true,
+            }
+        }
+
+        for (input, spectrum) in self.inputs.iter().zip(self.spectra.iter_mut()) {
+            for ((s, &x), &w) in self.scratch.iter_mut().zip(input).zip(&self.window) {
+                *s = x * w;
+            }
+
+            self.fft.forward(&self.scratch, spectrum);
+        }
+
+        (self.func)(&mut self.spectra);
+
+        for (spectrum, output) in self.spectra.iter().zip(self.outputs.iter_mut()) {
+            self.fft.inverse(spectrum, &mut self.scratch);
+
+            for ((o, &x), &w) in output.iter_mut().zip(&self.scratch).zip(&self.window) {
+                *o = *o + x * w * self.scale;
+            }
+        }
+
+        true
+    }
+}
+
+impl<S, F> Signal for Spectral<S, F>
+where
+    S: Signal,
+    <S::Frame as Frame>::Sample: FromSample<FloatOf<S>>,
+    F: FnMut(&mut [Vec<Complex<FloatOf<S>>>]),
+{
+    type Frame = S::Frame;
+
+    fn next(&mut self) -> Option<Self::Frame> {
+        if self.done && self.emitted >= self.consumed + self.latency() {
+            return None;
+        }
+
+        if self.position == self.hop {
+            if !self.process_block() {
+                return None;
+            }
+
+            self.position = 0;
+        }
+
+        let mut frame = self.template.clone()?;
+        for (sample, output) in frame.iter_mut().zip(&self.outputs) {
+            *sample = output[self.position].into_sample();
+        }
+
+        self.position += 1;
+        self.emitted += 1;
+
+        Some(frame)
+    }
+
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        // The output is as long as the input, plus the latency.
+        let input = if self.done {
+            (0, Some(0))
+        } else {
+            self.signal.len_hint()
+        };
+        let tail = (self.consumed + self.latency()).saturating_sub(self.emitted);
+
+        len_hint_with_tail(input, tail, self.template.is_some())
+    }
+}
+
+impl<S, F> ExactSizeSignal for Spectral<S, F>
+where
+    S: ExactSizeSignal,
+    <S::Frame as Frame>::Sample: FromSample<FloatOf<S>>,
+    F: FnMut(&mut [Vec<Complex<FloatOf<S>>>]),
+{
+}
+
+/// An inverse short-time Fourier transform, which resynthesizes a [`Signal`]
+/// from spectral frames such as those yielded by [`Stft`](crate::spectrum::Stft).
+///
+/// Each spectral frame contains the spectrum of each channel, as
+/// `size / 2 + 1` complex bins, and the spectral frames are spaced `hop`
+/// [`Frame`]s apart. Each spectrum is transformed back, windowed with the
+/// synthesis window, and overlapped and added into the output, which is
+/// then divided by the sum of the squared window at each point.
+///
+/// Unlike [`Spectral`], this does not require the window to satisfy the
+/// constant overlap-add condition, but points where the summed window is
+/// near zero (such as the very first point with [`Window::Hann`]) cannot be
+/// recovered and are output as zero.
+///
+/// The output has `hop` [`Frame`]s for each spectral frame, followed by a
+/// final `size - hop` [`Frame`]s once the spectral frames run out.
+///
+/// ```
+/// use sampara::{signal, Frame, Signal};
+/// use sampara::spectrum::{Istft, Stft, Window};
+///
+/// fn main() {
+///     let input: Vec<f64> = (0..32).map(|i| (i as f64 * 0.3).sin()).collect();
+///     let signal = signal::from_samples_fixed::<_, 1>(input.iter().copied());
+///
+///     let stft = Stft::new(signal, 8, 2, Window::Hann);
+///     let mut istft = Istft::new(stft, 8, 2, Window::Hann);
+///
+///     // The first point is lost, since the window is zero there.
+///     istft.next();
+///
+///     for &x in &input[1..] {
+///         let y = *istft.next().unwrap().get(0).unwrap();
+///         assert!((x - y).abs() < 1e-12);
+///     }
+/// }
+/// ```
+pub struct Istft<I, T>
+where
+    T: FloatSample,
+{
+    spectra: I,
+    fft: RealFft<T>,
+    window: Vec<T>,
+    hop: usize,
+    // One buffer of `size` partially summed output samples for each channel.
+    outputs: Vec<Vec<T>>,
+    // The summed squared window for each point of the output buffers.
+    weights: Vec<T>,
+    scratch: Vec<T>,
+    // The read position and end of the complete part of the output buffers.
+    position: usize,
+    ready: usize,
+    done: bool,
+}
+
+impl<I, T> Istft<I, T>
+where
+    I: Iterator<Item = Vec<Vec<Complex<T>>>>,
+    T: FloatSample,
+{
+    /// Creates a new [`Istft`] over spectral frames, with a given transform
+    /// size, hop size and synthesis window. The window is generated in its
+    /// periodic form.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is not a power of two that is at least 2, or if `hop`
+    /// is 0 or larger than `size`.
+    pub fn new<J>(spectra: J, size: usize, hop: usize, window: Window) -> Self
+    where
+        J: IntoIterator<IntoIter = I>,
+    {
+        assert!(hop > 0 && hop <= size, "hop size must be in 1..=size");
+
+        Self {
+            spectra: spectra.into_iter(),
+            fft: RealFft::new(size),
+            window: window.periodic(size),
+            hop,
+            outputs: Vec::new(),
+            weights: vec![T::EQUILIBRIUM; size],
+            scratch: vec![T::EQUILIBRIUM; size],
+            position: 0,
+            ready: 0,
+            done: false,
+        }
+    }
+
+    /// The transform size, in [`Frame`]s.
+    pub fn size(&self) -> usize {
+        self.window.len()
+    }
+
+    /// The hop size, in [`Frame`]s.
+    pub fn hop(&self) -> usize {
+        self.hop
+    }
+
+    /// Overlaps and adds the next spectral frame, or marks the rest of the
+    /// output buffers as complete if there are none left. Returns `false` if
+    /// there is no output left.
+    fn refill(&mut self) -> bool {
+        if self.done {
+            return false;
+        }
+
+        let size = self.size();
+        let hop = self.hop;
+
+        // Discard the output that has been read.
+        for buffer in self.outputs.iter_mut().chain(Some(&mut self.weights)) {
+            buffer.copy_within(self.ready..size, 0);
+            buffer[size - self.ready..].fill(T::EQUILIBRIUM);
+        }
+
+        match self.spectra.next() {
+            Some(spectra) => {
+                if self.outputs.is_empty() {
+                    self.outputs = vec![vec![T::EQUILIBRIUM; size]; spectra.len()];
+                }
+
+                for (spectrum, output) in spectra.iter().zip(self.outputs.iter_mut()) {
+                    self.fft.inverse(spectrum, &mut self.scratch);
+
+                    for ((o, &x), &w) in output.iter_mut().zip(&self.scratch).zip(&self.window) {
+                        *o = *o + x * w;
+                    }
+                }
+
+                for (weight, &w) in self.weights.iter_mut().zip(&self.window) {
+                    *weight = *weight + w * w;
+                }
+
+                self.ready = hop;
+            }
+            None => {
+                self.done = true;
+
+                // Nothing was ever output, so there is no tail to flush.
+                if self.outputs.is_empty() {
+                    return false;
+                }
+
+                self.ready = size - hop;
+            }
+        }
+
+        self.position = 0;
+        self.ready > 0
+    }
+}
+
+impl<I, T> Signal for Istft<I, T>
+where
+    I: Iterator<Item = Vec<Vec<Complex<T>>>>,
+    T: FloatSample,
+{
+    type Frame = Dynamic<T>;
+
+    fn next(&mut self) -> Option<Self::Frame> {
+        if self.position == self.ready && !self.refill() {
+            return None;
+        }
+
+        let weight = self.weights[self.position];
+        let threshold = T::from_sample(1e-10f64);
+
+        let frame = self
+            .outputs
+            .iter()
+            .map(|output| {
+                if weight > threshold {
+                    output[self.position] / weight
+                } else {
+                    T::EQUILIBRIUM
+                }
+            })
+            .collect::<Vec<_>>();
+
+        self.position += 1;
+
+        Some(Dynamic::from(frame))
+    }
+
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        let ready = self.ready - self.position;
+        if self.done {
+            return (ready, Some(ready));
+        }
+
+        // Each spectral frame completes `hop` frames, and the rest of the
+        // output buffers are flushed at the end.
+        let (lower, upper) = self.spectra.size_hint();
+        let spectra = (
+            lower.saturating_mul(self.hop),
+            upper.and_then(|n| n.checked_mul(self.hop)),
+        );
+        let (lower, upper) =
+            len_hint_with_tail(spectra, self.size() - self.hop, !self.outputs.is_empty());
+
+        (
+            lower.saturating_add(ready),
+            upper.and_then(|n| n.checked_add(ready)),
+        )
+    }
+}
+
+impl<I, T> ExactSizeSignal for Istft<I, T>
+where
+    I: ExactSizeIterator<Item = Vec<Vec<Complex<T>>>>,
+    T: FloatSample,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::signal;
+
+    #[test]
+    fn spectral_identity() {
+        let input: Vec<[f64; 2]> = (0..200)
+            .map(|i| {
+                let t = i as f64;
+                [(t * 0.1).sin(), (t * 0.37).cos() * 0.5]
+            })
+            .collect();
+
+        for (size, hop, window) in [
+            (32, 8, Window::Hann),
+            (32, 16, Window::Rectangular),
+            (64, 8, Window::Hann),
+            (16, 16, Window::Rectangular),
+        ] {
+            let signal = signal::from_frames(input.iter().map(|&f| crate::Fixed::from(f)));
+            let mut spectral = Spectral::new(signal, size, hop, window, |_| {});
+            let latency = spectral.latency();
+
+            let mut output = Vec::new();
+            while let Some(frame) = spectral.next() {
+                output.push(frame.into_array());
+            }
+
+            assert_eq!(output.len(), input.len() + latency, "{window:?}");
+            assert!(output[..latency].iter().flatten().all(|x| x.abs() < 1e-12));
+
+            for (x, y) in input.iter().zip(&output[latency..]) {
+                assert!((x[0] - y[0]).abs() < 1e-12, "{window:?}");
+                assert!((x[1] - y[1]).abs() < 1e-12, "{window:?}");
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn spectral_rejects_non_cola() {
+        let signal = signal::from_samples_fixed::<_, 1>([0.0f32; 4]);
+        Spectral::new(signal, 32, 16, Window::Hann, |_| {});
+    }
+
+    #[test]
+    fn istft_dynamic_channels() {
+        let size = 16;
+        let frames = 5;
+
+        // Spectra of a constant 1.0 in one channel, and 0.0 in the other.
+        let mut dc = vec![Complex::ZERO; size / 2 + 1];
+        dc[0] = Complex::from(size as f32);
+        let silence = vec![Complex::ZERO; size / 2 + 1];
+
+        let spectra = vec![vec![dc, silence]; frames];
+        let mut istft = Istft::new(spectra, size, 4, Window::Rectangular);
+
+        let mut count = 0;
+        while let Some(frame) = istft.next() {
+            assert_eq!(frame.len(), 2);
+            assert!((frame.get(0).unwrap() - 1.0).abs() < 1e-6);
+            assert_eq!(frame.get(1), Some(&0.0));
+            count += 1;
+        }
+
+        assert_eq!(count, frames * 4 + size - 4);
+    }
+
+    #[test]
+    fn empty_inputs() {
+        let signal = signal::from_samples_fixed::<_, 1>(Vec::<f32>::new());
+        let mut spectral = Spectral::new(signal, 8, 4, Window::Rectangular, |_| {});
+        assert_eq!(spectral.next(), None);
+
+        let mut istft = Istft::new(Vec::<Vec<Vec<Complex<f32>>>>::new(), 8, 4, Window::Hann);
+        assert_eq!(istft.next(), None);
+    }
+}
@@ -0,0 +1,196 @@
+use crate::frame::Frame;
+use crate::sample::{IntoSample, Sample};
+use crate::signal::{FloatOf, Signal};
+use crate::spectrum::{Complex, RealFft, Window};
+
+/// A short-time Fourier transform of a [`Signal`].
+///
+/// This is an [`Iterator`] that yields one spectral frame for every `hop`
+/// [`Frame`]s of input. Each spectral frame contains the spectrum of each
+/// channel, as `size / 2 + 1` complex bins. The [`Sample`]s of the input are
+/// converted to their [`Sample::Float`] type for analysis.
+///
+/// Spectral frames are only yielded once they are full, except at the end of
+/// the input, where the final spectral frame is padded with zeros so that
+/// every input [`Frame`] is analyzed at least once.
+///
+/// ```
+/// use sampara::signal;
+/// use sampara::spectrum::{Stft, Window};
+///
+/// fn main() {
+///     // A stereo signal with a constant in the left channel, and a cosine at
+///     // a quarter of the sample rate in the right channel.
+///     let samples = (0..16).flat_map(|i| [1.0f32, [1.0, 0.0, -1.0, 0.0][i % 4]]);
+///     let signal = signal::from_samples_fixed::<_, 2>(samples);
+///
+///     let mut stft = Stft::new(signal, 8, 4, Window::Rectangular);
+///
+///     let frame = stft.next().unwrap();
+///     assert_eq!(frame.len(), 2);
+///
+///     let left: Vec<f32> = frame[0].iter().map(|c| c.norm()).collect();
+///     let right: Vec<f32> = frame[1].iter().map(|c| c.norm()).collect();
+///     assert_eq!(left, vec![8.0, 0.0, 0.0, 0.0, 0.0]);
+///     assert_eq!(right, vec![0.0, 0.0, 4.0, 0.0, 0.0]);
+///
+///     // 16 frames of input with a hop of 4 yield 3 full spectral frames.
+///     assert_eq!(stft.count(), 2);
+/// }
+/// ```
+pub struct Stft<S>
+where
+    S: Signal,
+{
+    signal: S,
+    fft: RealFft<FloatOf<S>>,
+    window: Vec<FloatOf<S>>,
+    hop: usize,
+    // One buffer of `size` samples for each channel.
+    buffers: Vec<Vec<FloatOf<S>>>,
+    // The number of samples currently held in each buffer.
+    filled: usize,
+    // The number of samples held that have not yet been part of a yielded
+    // spectral frame.
+    pending: usize,
+    scratch: Vec<FloatOf<S>>,
+    done: bool,
+}
+
+impl<S> Stft<S>
+where
+    S: Signal,
+{
+    /// Creates a new [`Stft`] over a [`Signal`], with a given transform size,
+    /// hop size and analysis window. The window is generated in its periodic
+    /// form.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is not a power of two that is at least 2, or if `hop`
+    /// is 0 or larger than `size`.
+    pub fn new(signal: S, size: usize, hop: usize, window: Window) -> Self {
+        assert!(hop > 0 && hop <= size, "hop size must be in 1..=size");
+
+        Self {
+            signal,
+            fft: RealFft::new(size),
+            window: window.periodic(size),
+            hop,
+            buffers: Vec::new(),
+            filled: 0,
+            pending: 0,
+            scratch: vec![FloatOf::<S>::EQUILIBRIUM; size],
+            done: false,
+        }
+    }
+
+    /// The transform size, in [`Frame`]s.
+    pub fn size(&self) -> usize {
+        self.window.len()
+    }
+
+    /// The hop size, in [`Frame`]s.
+    pub fn hop(&self) -> usize {
+        self.hop
+    }
+
+    /// The number of complex bins in the spectrum of each channel.
+    pub fn bins(&self) -> usize {
+        self.fft.bins()
+    }
+
+    /// Fills the channel buffers with input, returning `false` if there is no
+    /// input left to analyze.
+    fn fill(&mut self) -> bool {
+        let size = self.size();
+
+        while self.filled < size {
+            match self.signal.next() {
+                Some(frame) => {
+                    if self.buffers.is_empty() {
+                        let zero = FloatOf::<S>::EQUILIBRIUM;
+                        self.buffers = vec![vec![zero; size]; frame.len()];
+                    }
+
+                    for (buffer, sample) in self.buffers.iter_mut().zip(frame) {
+                        buffer[self.filled] = sample.into_sample();
+                    }
+
+                    self.filled += 1;
+                    self.pending += 1;
+                }
+                None => {
+                    self.done = true;
+
+                    if self.pending == 0 {
+                        return false;
+                    }
+
+                    for buffer in self.buffers.iter_mut() {
+                        buffer[self.filled..].fill(FloatOf::<S>::EQUILIBRIUM);
+                    }
+
+                    return true;
+                }
+            }
+        }
+
+        true
+    }
+}
+
+impl<S> Iterator for Stft<S>
+where
+    S: Signal,
+{
+    type Item = Vec<Vec<Complex<FloatOf<S>>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || !self.fill() {
+            return None;
+        }
+
+        let size = self.size();
+        let hop = self.hop;
+        let bins = self.bins();
+
+        let spectra = self
+            .buffers
+            .iter_mut()
+            .map(|buffer| {
+                for ((s, &x), &w) in self.scratch.iter_mut().zip(buffer.iter()).zip(&self.window) {
+                    *s = x * w;
+                }
+
+                let mut spectrum = vec![Complex::ZERO; bins];
+                self.fft.forward(&self.scratch, &mut spectrum);
+
+                // Slide the buffer forward by one hop.
+                buffer.copy_within(hop..size, 0);
+
+                spectrum
+            })
+            .collect();
+
+        self.filled = size - hop;
+        self.pending = 0;
+
+        Some(spectra)
+    }
+}
+
+/// Returns the center frequency of a bin of an FFT of a given size, in the
+/// same units as `sample_rate`.
+///
+/// ```
+/// use sampara::spectrum;
+///
+/// fn main() {
+///     assert_eq!(spectrum::bin_frequency(0, 1024, 48000.0), 0.0);
+///     assert_eq!(spectrum::bin_frequency(512, 1024, 48000.0), 24000.0);
+/// }
+/// ```
+pub fn bin_frequency(bin: usize, size: usize, sample_rate: f64) -> f64 {
+    bin as f64 * sample_rate / size as f64
+}
@@ -0,0 +1,266 @@
+use core::f64::consts::PI;
+
+use crate::sample::FloatSample;
+
+/// Window functions, used to taper blocks of samples before spectral analysis
+/// and in FIR filter design.
+///
+/// Windows can be generated in two forms: [`Window::periodic`] windows are
+/// intended for spectral analysis, where the window is implicitly repeated,
+/// while [`Window::symmetric`] windows are intended for filter design.
+///
+/// ```
+/// use sampara::spectrum::Window;
+///
+/// fn main() {
+///     let round = |w: Vec<f64>| w.iter().map(|x| (x * 1e6).round() / 1e6).collect::<Vec<_>>();
+///
+///     let w = Window::Hann.periodic(4);
+///     assert_eq!(round(w), vec![0.0, 0.5, 1.0, 0.5]);
+///
+///     let w = Window::Hann.symmetric(5);
+///     assert_eq!(round(w), vec![0.0, 0.5, 1.0, 0.5, 0.0]);
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Window {
+    /// A window of all ones, which does not taper at all.
+    Rectangular,
+
+    /// The raised cosine window, with zeros at both ends.
+    Hann,
+
+    /// A raised cosine window that is optimized to minimize the nearest side
+    /// lobe, and does not reach zero at either end.
+    Hamming,
+
+    /// The 4-term Blackman-Harris window, with side lobes below -92 dB.
+    BlackmanHarris,
+
+    /// The Kaiser-Bessel window, where `beta` trades main lobe width for side
+    /// lobe level. A `beta` of 0 is equivalent to [`Window::Rectangular`].
+    Kaiser { beta: f64 },
+
+    /// The 5-term flat-top window, with the same coefficients as MATLAB's
+    /// `flattopwin`. It has negligible scalloping loss, and is used for
+    /// accurate amplitude measurement.
+    FlatTop,
+
+    /// The tapered cosine window, where `alpha` is the fraction of the window
+    /// that is tapered. An `alpha` of 0 is equivalent to
+    /// [`Window::Rectangular`], and an `alpha` of 1 is equivalent to
+    /// [`Window::Hann`].
+    Tukey { alpha: f64 },
+}
+
+impl Window {
+    /// Evaluates this window at a position `x` in the interval [0.0, 1.0],
+    /// where 0.0 and 1.0 are the two ends of the window.
+    pub fn eval(&self, x: f64) -> f64 {
+        let cosine_sum = |coeffs: &[f64]| -> f64 {
+            coeffs
+                .iter()
+                .enumerate()
+                .map(|(k, a)| {
+                    let sign = if k % 2 == 0 { 1.0 } else { -1.0 };
+                    sign * a * (2.0 * PI * k as f64 * x).cos()
+                })
+                .sum()
+        };
+
+        match *self {
+            Window::Rectangular => 1.0,
+            Window::Hann => cosine_sum(&[0.5, 0.5]),
+            Window::Hamming => cosine_sum(&[0.54, 0.46]),
+            Window::BlackmanHarris => cosine_sum(&[0.35875, 0.48829, 0.14128, 0.01168]),
+            Window::Kaiser { beta } => {
+                let r = 2.0 * x - 1.0;
+                bessel_i0(beta * (1.0 - r * r).max(0.0).sqrt()) / bessel_i0(beta)
+            }
+            Window::FlatTop => cosine_sum(&[
+                0.21557895,
+                0.41663158,
+                0.277263158,
+                0.083578947,
+                0.006947368,
+            ]),
+            Window::Tukey { alpha } => {
+                let alpha = alpha.clamp(0.0, 1.0);
+                let edge = alpha / 2.0;
+                let d = x.min(1.0 - x);
+
+                if alpha == 0.0 || d >= edge {
+                    1.0
+                } else {
+                    0.5 * (1.0 - (PI * d / edge).cos())
+                }
+            }
+        }
+    }
+
+    /// Generates a periodic window of length `len`, which is the first `len`
+    /// points of a symmetric window of length `len + 1`. Periodic windows are
+    /// used for spectral analysis.
+    pub fn periodic<T: FloatSample>(&self, len: usize) -> Vec<T> {
+        (0..len)
+            .map(|i| T::from_sample(self.eval(i as f64 / len as f64)))
+            .collect()
+    }
+
+    /// Generates a symmetric window of length `len`, whose first and last
+    /// points are the two ends of the window. Symmetric windows are used for
+    /// FIR filter design.
+    pub fn symmetric<T: FloatSample>(&self, len: usize) -> Vec<T> {
+        if len == 1 {
+            return vec![T::from_sample(1.0f64)];
+        }
+
+        (0..len)
+            .map(|i| T::from_sample(self.eval(i as f64 / (len - 1) as f64)))
+            .collect()
+    }
+
+    /// Returns the constant that copies of the periodic form of this window
+    /// sum to when overlapped every `hop` points, or [`None`] if the sum is
+    /// not constant. Windows with a constant overlap-add (COLA) can be used to
+    /// resynthesize a signal from its unmodified spectra without distortion.
+    ///
+    /// ```
+    /// use sampara::spectrum::Window;
+    ///
+    /// fn main() {
+    ///     assert_eq!(Window::Hann.cola_gain(1024, 512), Some(1.0));
+    ///     assert_eq!(Window::Hann.cola_gain(1024, 400), None);
+    ///     assert_eq!(Window::Rectangular.cola_gain(1024, 256), Some(4.0));
+    /// }
+    /// ```
+    pub fn cola_gain(&self, size: usize, hop: usize) -> Option<f64> {
+        overlap_gain(&self.periodic::<f64>(size), hop)
+    }
+
+    /// Like [`Self::cola_gain`], but for the squared window. This is the
+    /// condition for weighted overlap-add (WOLA), where the same window is
+    /// applied both before analysis and after resynthesis.
+    ///
+    /// ```
+    /// use sampara::spectrum::Window;
+    ///
+    /// fn main() {
+    ///     assert_eq!(Window::Hann.wola_gain(1024, 512), None);
+    ///     assert_eq!(Window::Hann.wola_gain(1024, 256), Some(1.5));
+    /// }
+    /// ```
+    pub fn wola_gain(&self, size: usize, hop: usize) -> Option<f64> {
+        let squared: Vec<f64> = self.periodic::<f64>(size).iter().map(|w| w * w).collect();
+        overlap_gain(&squared, hop)
+    }
+
+    /// Returns the `beta` parameter for a [`Window::Kaiser`] window that
+    /// achieves a given stopband attenuation, in positive decibels, using
+    /// Kaiser's empirical formula.
+    ///
+    /// ```
+    /// use sampara::spectrum::Window;
+    ///
+    /// fn main() {
+    ///     assert_eq!(Window::kaiser_beta(20.0), 0.0);
+    ///     assert!((Window::kaiser_beta(60.0) - 5.6533).abs() < 1e-4);
+    /// }
+    /// ```
+    pub fn kaiser_beta(attenuation: f64) -> f64 {
+        if attenuation > 50.0 {
+            0.1102 * (attenuation - 8.7)
+        } else if attenuation >= 21.0 {
+            0.5842 * (attenuation - 21.0).powf(0.4) + 0.07886 * (attenuation - 21.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Returns the constant that copies of a window sum to when overlapped every
+/// `hop` points, or [`None`] if the sum is not constant.
+fn overlap_gain(window: &[f64], hop: usize) -> Option<f64> {
+    if hop == 0 || hop > window.len() {
+        return None;
+    }
+
+    let sums: Vec<f64> = (0..hop)
+        .map(|offset| window.iter().skip(offset).step_by(hop).sum())
+        .collect();
+
+    let max = sums.iter().copied().fold(f64::MIN, f64::max);
+    let min = sums.iter().copied().fold(f64::MAX, f64::min);
+
+    // Round away floating-point error, so that exact gains compare equal.
+    (max - min <= 1e-9 * max.abs().max(1.0)).then(|| (max * 1e9).round() / 1e9)
+}
+
+/// The zeroth-order modified Bessel function of the first kind, evaluated with
+/// its power series.
+pub(crate) fn bessel_i0(x: f64) -> f64 {
+    let half_x = x / 2.0;
+
+    let mut sum = 1.0;
+    let mut term = 1.0;
+    let mut k = 1.0;
+
+    while term > sum * 1e-17 {
+        term *= (half_x / k) * (half_x / k);
+        sum += term;
+        k += 1.0;
+    }
+
+    sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOWS: [Window; 7] = [
+        Window::Rectangular,
+        Window::Hann,
+        Window::Hamming,
+        Window::BlackmanHarris,
+        Window::Kaiser { beta: 8.6 },
+        Window::FlatTop,
+        Window::Tukey { alpha: 0.5 },
+    ];
+
+    #[test]
+    fn symmetric_windows() {
+        for window in WINDOWS {
+            let w: Vec<f64> = window.symmetric(33);
+
+            for (a, b) in w.iter().zip(w.iter().rev()) {
+                assert!((a - b).abs() < 1e-12, "{window:?}");
+            }
+
+            // All windows peak at 1.0 in the middle.
+            assert!((w[16] - 1.0).abs() < 1e-3, "{window:?}: {}", w[16]);
+        }
+    }
+
+    #[test]
+    fn degenerate_parameters() {
+        let rect: Vec<f64> = Window::Rectangular.symmetric(16);
+        let kaiser: Vec<f64> = Window::Kaiser { beta: 0.0 }.symmetric(16);
+        let tukey: Vec<f64> = Window::Tukey { alpha: 0.0 }.symmetric(16);
+        assert_eq!(rect, kaiser);
+        assert_eq!(rect, tukey);
+
+        let hann: Vec<f64> = Window::Hann.periodic(16);
+        let tukey: Vec<f64> = Window::Tukey { alpha: 1.0 }.periodic(16);
+        for (a, b) in hann.iter().zip(tukey.iter()) {
+            assert!((a - b).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn bessel() {
+        assert_eq!(bessel_i0(0.0), 1.0);
+        assert!((bessel_i0(1.0) - 1.2660658777520084).abs() < 1e-14);
+        assert!((bessel_i0(10.0) - 2815.716628466254).abs() < 1e-9);
+    }
+}
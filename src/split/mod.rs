@@ -0,0 +1,403 @@
+//! Splitting one [`Signal`] into several handles, which each yield every
+//! [`Frame`](crate::Frame) of the original.
+//!
+//! The handles share a buffer of the frames that have been pulled from the
+//! original [`Signal`], but not yet yielded by every handle. A handle that
+//! runs ahead of the others grows the buffer, so handles should be advanced
+//! at similar rates.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+use std::sync::{Arc, Condvar, Mutex};
+
+use crate::signal::{ExactSizeSignal, Signal};
+
+/// The state shared between the handles of a split [`Signal`].
+struct Shared<S>
+where
+    S: Signal,
+{
+    signal: S,
+    // The frames that have not been yielded by every handle.
+    buffer: VecDeque<S::Frame>,
+    // The position of the first frame of the buffer in the signal.
+    offset: usize,
+    // The position of each handle, or `None` if it has been dropped.
+    positions: Vec<Option<usize>>,
+    ended: bool,
+}
+
+impl<S> Shared<S>
+where
+    S: Signal,
+{
+    fn new(signal: S) -> Self {
+        Self {
+            signal,
+            buffer: VecDeque::new(),
+            offset: 0,
+            positions: Vec::new(),
+            ended: false,
+        }
+    }
+
+    /// Adds a handle at a position, returning its index.
+    fn add(&mut self, position: usize) -> usize {
+        self.positions.push(Some(position));
+        self.positions.len() - 1
+    }
+
+    fn remove(&mut self, handle: usize) {
+        self.positions[handle] = None;
+        self.trim();
+    }
+
+    /// Whether a handle needs a new frame from the signal.
+    fn is_ahead(&self, handle: usize) -> bool {
+        self.positions[handle].is_some_and(|p| p - self.offset == self.buffer.len())
+    }
+
+    /// Yields the next frame for a handle.
+    fn next(&mut self, handle: usize) -> Option<S::Frame> {
+        let position = self.positions[handle]?;
+        let index = position - self.offset;
+
+        if index == self.buffer.len() {
+            if self.ended {
+                return None;
+            }
+
+            match self.signal.next() {
+                Some(frame) => self.buffer.push_back(frame),
+                None => {
+                    self.ended = true;
+                    return None;
+                }
+            }
+        }
+
+        let frame = self.buffer[index].clone();
+        self.positions[handle] = Some(position + 1);
+        self.trim();
+
+        Some(frame)
+    }
+
+    /// The length hint of the frames left for a handle.
+    fn len_hint(&self, handle: usize) -> (usize, Option<usize>) {
+        let Some(position) = self.positions[handle] else {
+            return (0, Some(0));
+        };
+        let buffered = self.offset + self.buffer.len() - position;
+
+        let (lower, upper) = if self.ended {
+            (0, Some(0))
+        } else {
+            self.signal.len_hint()
+        };
+
+        (
+            lower.saturating_add(buffered),
+            upper.and_then(|n| n.checked_add(buffered)),
+        )
+    }
+
+    /// Drops the frames that every handle has yielded.
+    fn trim(&mut self) {
+        let slowest = self
+            .positions
+            .iter()
+            .flatten()
+            .min()
+            .copied()
+            .unwrap_or(self.offset + self.buffer.len());
+
+        let consumed = slowest - self.offset;
+        self.buffer.drain(..consumed);
+        self.offset = slowest;
+    }
+}
+
+/// A handle to a split [`Signal`], for use on a single thread.
+///
+/// Cloning a handle creates a new handle at the same position.
+///
+/// This is created by [`Signal::tee`] and [`Signal::split`].
+pub struct Split<S>
+where
+    S: Signal,
+{
+    shared: Rc<RefCell<Shared<S>>>,
+    handle: usize,
+}
+
+impl<S> Split<S>
+where
+    S: Signal,
+{
+    pub(crate) fn new(signal: S) -> Self {
+        let mut shared = Shared::new(signal);
+        let handle = shared.add(0);
+
+        Self {
+            shared: Rc::new(RefCell::new(shared)),
+            handle,
+        }
+    }
+
+    /// The number of frames buffered for the handles that are behind.
+    pub fn buffered(&self) -> usize {
+        self.shared.borrow().buffer.len()
+    }
+}
+
+impl<S> Clone for Split<S>
+where
+    S: Signal,
+{
+    fn clone(&self) -> Self {
+        let mut shared = self.shared.borrow_mut();
+        let position = shared.positions[self.handle].unwrap_or(shared.offset);
+        let handle = shared.add(position);
+
+        Self {
+            shared: Rc::clone(&self.shared),
+            handle,
+        }
+    }
+}
+
+impl<S> Drop for Split<S>
+where
+    S: Signal,
+{
+    fn drop(&mut self) {
+        self.shared.borrow_mut().remove(self.handle);
+    }
+}
+
+impl<S> Signal for Split<S>
+where
+    S: Signal,
+{
+    type Frame = S::Frame;
+
+    fn next(&mut self) -> Option<Self::Frame> {
+        self.shared.borrow_mut().next(self.handle)
+    }
+
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        self.shared.borrow().len_hint(self.handle)
+    }
+}
+
+impl<S> ExactSizeSignal for Split<S> where S: ExactSizeSignal {}
+
+/// A handle to a split [`Signal`], which can be sent to another thread.
+///
+/// At most `capacity` frames are buffered. A handle that would need to buffer
+/// more blocks, until the handles that are behind catch up. Because of this,
+/// all handles on one thread must be advanced in step, or the thread will
+/// deadlock.
+///
+/// Cloning a handle creates a new handle at the same position.
+///
+/// This is created by [`Signal::split_sync`].
+///
+/// ```
+/// use std::thread;
+///
+/// use sampara::{signal, Signal};
+///
+/// fn main() {
+///     let source = signal::from_samples_fixed::<_, 1>(0..1000u32);
+///     let mut handles = source.split_sync(2, 16);
+///
+///     let mut other = handles.pop().unwrap();
+///     let thread = thread::spawn(move || std::iter::from_fn(|| other.next()).count());
+///
+///     let mut own = handles.pop().unwrap();
+///     let sum: u32 = std::iter::from_fn(|| own.next())
+///         .map(|f| f.into_array()[0])
+///         .sum();
+///
+///     assert_eq!(sum, 499500);
+///     assert_eq!(thread.join().unwrap(), 1000);
+/// }
+/// ```
+pub struct SyncSplit<S>
+where
+    S: Signal,
+{
+    shared: Arc<(Mutex<Shared<S>>, Condvar)>,
+    handle: usize,
+    capacity: usize,
+}
+
+impl<S> SyncSplit<S>
+where
+    S: Signal,
+{
+    /// # Panics
+    ///
+    /// Panics if `capacity` is 0.
+    pub(crate) fn new(signal: S, capacity: usize) -> Self {
+        assert!(capacity > 0, "the capacity must be at least one frame");
+
+        let mut shared = Shared::new(signal);
+        let handle = shared.add(0);
+
+        Self {
+            shared: Arc::new((Mutex::new(shared), Condvar::new())),
+            handle,
+            capacity,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The number of frames buffered for the handles that are behind.
+    pub fn buffered(&self) -> usize {
+        self.lock().buffer.len()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Shared<S>> {
+        // A handle that panicked while holding the lock leaves the buffer
+        // consistent, so the poison can be ignored.
+        self.shared.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<S> Clone for SyncSplit<S>
+where
+    S: Signal,
+{
+    fn clone(&self) -> Self {
+        let handle = {
+            let mut shared = self.lock();
+            let position = shared.positions[self.handle].unwrap_or(shared.offset);
+            shared.add(position)
+        };
+
+        Self {
+            shared: Arc::clone(&self.shared),
+            handle,
+            capacity: self.capacity,
+        }
+    }
+}
+
+impl<S> Drop for SyncSplit<S>
+where
+    S: Signal,
+{
+    fn drop(&mut self) {
+        self.lock().remove(self.handle);
+        self.shared.1.notify_all();
+    }
+}
+
+impl<S> Signal for SyncSplit<S>
+where
+    S: Signal,
+{
+    type Frame = S::Frame;
+
+    fn next(&mut self) -> Option<Self::Frame> {
+        let mut shared = self.lock();
+
+        while !shared.ended && shared.is_ahead(self.handle) && shared.buffer.len() >= self.capacity
+        {
+            shared = self
+                .shared
+                .1
+                .wait(shared)
+                .unwrap_or_else(|e| e.into_inner());
+        }
+
+        let frame = shared.next(self.handle);
+        drop(shared);
+        self.shared.1.notify_all();
+
+        frame
+    }
+
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        self.lock().len_hint(self.handle)
+    }
+}
+
+impl<S> ExactSizeSignal for SyncSplit<S> where S: ExactSizeSignal {}
+
+/// Splits a [`Signal`] into `n` handles for a single thread.
+pub(crate) fn split<S: Signal>(signal: S, n: usize) -> Vec<Split<S>> {
+    let first = Split::new(signal);
+    let mut handles: Vec<Split<S>> = (1..n).map(|_| first.clone()).collect();
+
+    if n > 0 {
+        handles.insert(0, first);
+    }
+
+    handles
+}
+
+/// Splits a [`Signal`] into `n` handles that can be sent to other threads.
+pub(crate) fn split_sync<S: Signal>(signal: S, n: usize, capacity: usize) -> Vec<SyncSplit<S>> {
+    let first = SyncSplit::new(signal, capacity);
+    let mut handles: Vec<SyncSplit<S>> = (1..n).map(|_| first.clone()).collect();
+
+    if n > 0 {
+        handles.insert(0, first);
+    }
+
+    handles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signal;
+
+    #[test]
+    fn buffers_only_unconsumed_frames() {
+        let (mut a, mut b) = signal::from_samples_fixed::<_, 1>(0..10i32).tee();
+
+        assert_eq!(a.nth(3), Some([3].into()));
+        assert_eq!(a.buffered(), 4);
+
+        assert_eq!(b.next(), Some([0].into()));
+        assert_eq!(b.buffered(), 3);
+
+        // A clone starts where its original is.
+        let mut c = b.clone();
+        assert_eq!(c.next(), Some([1].into()));
+        assert_eq!(b.buffered(), 3);
+
+        // Dropping the slowest handle releases its frames.
+        drop(b);
+        assert_eq!(a.buffered(), 2);
+        drop(c);
+        assert_eq!(a.buffered(), 0);
+
+        assert_eq!(std::iter::from_fn(|| a.next()).count(), 6);
+    }
+
+    #[test]
+    fn sync_handles_stay_bounded() {
+        let mut handles = signal::from_samples_fixed::<_, 1>(0..100u8).split_sync(3, 4);
+        assert_eq!(handles.len(), 3);
+
+        // Advancing in step never blocks.
+        for i in 0..100 {
+            for handle in handles.iter_mut() {
+                assert_eq!(handle.next(), Some([i].into()));
+                assert!(handle.buffered() <= 4);
+            }
+        }
+
+        assert!(handles.iter_mut().all(|h| h.next().is_none()));
+    }
+}
@@ -0,0 +1,234 @@
+//! Stereo utilities: mid/side encoding, width and balance for stereo signals,
+//! and panners that place mono signals in a stereo field.
+//!
+//! Stereo signals have [`Fixed`] frames of two channels, left then right.
+
+mod pan;
+
+pub use self::pan::{AutoPan, AutoPanParams, Pan, PanLaw};
+
+use crate::frame::Fixed;
+use crate::sample::{FloatSample, FromSample};
+use crate::signal::{ExactSizeSignal, Signal};
+
+/// Applies a 2x2 matrix of gains to a stereo frame.
+fn apply<X: FloatSample>(frame: Fixed<X, 2>, [[ll, lr], [rl, rr]]: [[f64; 2]; 2]) -> Fixed<X, 2> {
+    let [l, r] = frame.into_array();
+    let gain = |g: f64| -> X { FromSample::from_sample(g) };
+
+    Fixed::from([l * gain(ll) + r * gain(lr), l * gain(rl) + r * gain(rr)])
+}
+
+/// A [`Signal`] that encodes the left and right channels of an underlying
+/// [`Signal`] into mid and side channels, where `mid = (left + right) / 2`
+/// and `side = (left - right) / 2`.
+///
+/// This is created by [`Signal::mid_side`].
+pub struct MidSide<S> {
+    signal: S,
+}
+
+impl<S> MidSide<S> {
+    pub(crate) fn new(signal: S) -> Self {
+        Self { signal }
+    }
+}
+
+impl<S, X> Signal for MidSide<S>
+where
+    S: Signal<Frame = Fixed<X, 2>>,
+    X: FloatSample,
+{
+    type Frame = Fixed<X, 2>;
+
+    fn next(&mut self) -> Option<Self::Frame> {
+        let frame = self.signal.next()?;
+        Some(apply(frame, [[0.5, 0.5], [0.5, -0.5]]))
+    }
+
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        self.signal.len_hint()
+    }
+}
+
+impl<S, X> ExactSizeSignal for MidSide<S>
+where
+    S: ExactSizeSignal<Frame = Fixed<X, 2>>,
+    X: FloatSample,
+{
+}
+
+/// A [`Signal`] that decodes the mid and side channels of an underlying
+/// [`Signal`] into left and right channels, where `left = mid + side` and
+/// `right = mid - side`. This is the inverse of [`MidSide`].
+///
+/// This is created by [`Signal::left_right`].
+pub struct LeftRight<S> {
+    signal: S,
+}
+
+impl<S> LeftRight<S> {
+    pub(crate) fn new(signal: S) -> Self {
+        Self { signal }
+    }
+}
+
+impl<S, X> Signal for LeftRight<S>
+where
+    S: Signal<Frame = Fixed<X, 2>>,
+    X: FloatSample,
+{
+    type Frame = Fixed<X, 2>;
+
+    fn next(&mut self) -> Option<Self::Frame> {
+        let frame = self.signal.next()?;
+        Some(apply(frame, [[1.0, 1.0], [1.0, -1.0]]))
+    }
+
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        self.signal.len_hint()
+    }
+}
+
+impl<S, X> ExactSizeSignal for LeftRight<S>
+where
+    S: ExactSizeSignal<Frame = Fixed<X, 2>>,
+    X: FloatSample,
+{
+}
+
+/// A [`Signal`] that scales the side channel of an underlying stereo
+/// [`Signal`], leaving its mid channel unchanged.
+///
+/// A width of 0 collapses the signal to mono, a width of 1 leaves it
+/// unchanged, and widths above 1 widen it.
+///
+/// This is created by [`Signal::width`].
+pub struct Width<S> {
+    signal: S,
+    width: f64,
+}
+
+impl<S> Width<S> {
+    pub(crate) fn new(signal: S, width: f64) -> Self {
+        Self { signal, width }
+    }
+
+    pub fn width(&self) -> f64 {
+        self.width
+    }
+
+    pub fn set_width(&mut self, width: f64) {
+        self.width = width;
+    }
+}
+
+impl<S, X> Signal for Width<S>
+where
+    S: Signal<Frame = Fixed<X, 2>>,
+    X: FloatSample,
+{
+    type Frame = Fixed<X, 2>;
+
+    fn next(&mut self) -> Option<Self::Frame> {
+        let frame = self.signal.next()?;
+
+        let (mid, side) = (0.5, 0.5 * self.width);
+        Some(apply(
+            frame,
+            [[mid + side, mid - side], [mid - side, mid + side]],
+        ))
+    }
+
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        self.signal.len_hint()
+    }
+}
+
+impl<S, X> ExactSizeSignal for Width<S>
+where
+    S: ExactSizeSignal<Frame = Fixed<X, 2>>,
+    X: FloatSample,
+{
+}
+
+/// A [`Signal`] that shifts the balance of an underlying stereo [`Signal`],
+/// by attenuating one channel and leaving the other unchanged.
+///
+/// A balance of -1 silences the right channel, 0 leaves both channels
+/// unchanged, and 1 silences the left channel.
+///
+/// This is created by [`Signal::balance`].
+pub struct Balance<S> {
+    signal: S,
+    balance: f64,
+}
+
+impl<S> Balance<S> {
+    pub(crate) fn new(signal: S, balance: f64) -> Self {
+        Self { signal, balance }
+    }
+
+    pub fn balance(&self) -> f64 {
+        self.balance
+    }
+
+    pub fn set_balance(&mut self, balance: f64) {
+        self.balance = balance;
+    }
+}
+
+impl<S, X> Signal for Balance<S>
+where
+    S: Signal<Frame = Fixed<X, 2>>,
+    X: FloatSample,
+{
+    type Frame = Fixed<X, 2>;
+
+    fn next(&mut self) -> Option<Self::Frame> {
+        let frame = self.signal.next()?;
+
+        let [left, right] = PanLaw::Linear.gains(self.balance);
+        Some(apply(frame, [[left, 0.0], [0.0, right]]))
+    }
+
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        self.signal.len_hint()
+    }
+}
+
+impl<S, X> ExactSizeSignal for Balance<S>
+where
+    S: ExactSizeSignal<Frame = Fixed<X, 2>>,
+    X: FloatSample,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::signal::{self, Signal};
+
+    #[test]
+    fn mid_side_round_trip() {
+        let input = vec![0.5f64, 0.25, -1.0, 0.75];
+        let mut signal = signal::from_samples_fixed::<_, 2>(input.clone())
+            .mid_side()
+            .left_right();
+
+        let output: Vec<f64> = std::iter::from_fn(|| signal.next())
+            .flat_map(|f| f.into_array())
+            .collect();
+        assert_eq!(output, input);
+    }
+
+    #[test]
+    fn width() {
+        let input = vec![1.0f64, 0.0];
+
+        let mut mono = signal::from_samples_fixed::<_, 2>(input.clone()).width(0.0);
+        assert_eq!(mono.next(), Some([0.5, 0.5].into()));
+
+        let mut wide = signal::from_samples_fixed::<_, 2>(input).width(2.0);
+        assert_eq!(wide.next(), Some([1.5, -0.5].into()));
+    }
+}
@@ -0,0 +1,253 @@
+use std::f64::consts::FRAC_PI_2;
+
+use crate::frame::{Fixed, Frame};
+use crate::modulation::{Lfo, Shape};
+use crate::sample::to_f64;
+use crate::sample::{FloatSample, FromSample};
+use crate::signal::{ExactSizeSignal, Signal, min_len_hint};
+
+/// How the gains of the left and right channels follow the pan position,
+/// named after the gain of both channels at the center.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PanLaw {
+    /// 0 dB at the center: the near channel stays at unity gain, and the far
+    /// channel fades out linearly.
+    Linear,
+
+    /// -3 dB at the center: the gains follow a quarter of a sine and cosine,
+    /// so that their powers sum to 1.
+    ConstantPower,
+
+    /// -4.5 dB at the center: the geometric mean of the
+    /// [`ConstantPower`](PanLaw::ConstantPower) and
+    /// [`ConstantAmplitude`](PanLaw::ConstantAmplitude) laws.
+    Compromise,
+
+    /// -6 dB at the center: the gains change linearly, so that they sum to 1.
+    ConstantAmplitude,
+}
+
+impl PanLaw {
+    /// The gains of the left and right channels, for a pan position from -1
+    /// (left) to 1 (right). Positions outside of this interval are clamped.
+    ///
+    /// ```
+    /// use sampara::stereo::PanLaw;
+    ///
+    /// fn main() {
+    ///     assert_eq!(PanLaw::Linear.gains(0.0), [1.0, 1.0]);
+    ///     assert_eq!(PanLaw::ConstantAmplitude.gains(0.0), [0.5, 0.5]);
+    ///     assert_eq!(PanLaw::ConstantPower.gains(-1.0), [1.0, 0.0]);
+    ///
+    ///     let [left, right] = PanLaw::Compromise.gains(0.0);
+    ///     assert!((20.0 * left.log10() + 4.5).abs() < 0.1);
+    ///     assert_eq!(left, right);
+    /// }
+    /// ```
+    pub fn gains(&self, position: f64) -> [f64; 2] {
+        let position = position.clamp(-1.0, 1.0);
+        let p = 0.5 * (position + 1.0);
+        let power = [(p * FRAC_PI_2).cos(), (p * FRAC_PI_2).sin()];
+
+        match self {
+            PanLaw::Linear => [(1.0 - position).min(1.0), (1.0 + position).min(1.0)],
+            PanLaw::ConstantPower => power,
+            PanLaw::Compromise => [((1.0 - p) * power[0]).sqrt(), (p * power[1]).sqrt()],
+            PanLaw::ConstantAmplitude => [1.0 - p, p],
+        }
+    }
+}
+
+/// Places a mono sample in the stereo field.
+fn pan<X: FloatSample>(frame: Fixed<X, 1>, law: PanLaw, position: f64) -> Fixed<X, 2> {
+    let [x] = frame.into_array();
+    let [left, right] = law.gains(position);
+
+    Fixed::from([
+        x * FromSample::from_sample(left),
+        x * FromSample::from_sample(right),
+    ])
+}
+
+/// A [`Signal`] that pans an underlying mono [`Signal`] into stereo, with the
+/// pan position taken from the first channel of another [`Signal`]. It ends
+/// when either [`Signal`] ends.
+///
+/// This is created by [`Signal::pan`] and [`Signal::pan_with`].
+pub struct Pan<S, P> {
+    signal: S,
+    positions: P,
+    law: PanLaw,
+}
+
+impl<S, P> Pan<S, P> {
+    pub(crate) fn new(signal: S, positions: P, law: PanLaw) -> Self {
+        Self {
+            signal,
+            positions,
+            law,
+        }
+    }
+
+    pub fn law(&self) -> PanLaw {
+        self.law
+    }
+
+    pub fn set_law(&mut self, law: PanLaw) {
+        self.law = law;
+    }
+
+    pub fn positions(&self) -> &P {
+        &self.positions
+    }
+
+    pub fn positions_mut(&mut self) -> &mut P {
+        &mut self.positions
+    }
+}
+
+impl<S, P, X> Signal for Pan<S, P>
+where
+    S: Signal<Frame = Fixed<X, 1>>,
+    P: Signal,
+    X: FloatSample,
+{
+    type Frame = Fixed<X, 2>;
+
+    fn next(&mut self) -> Option<Self::Frame> {
+        let frame = self.signal.next()?;
+        let position = self.positions.next()?.get(0).map_or(0.0, to_f64);
+
+        Some(pan(frame, self.law, position))
+    }
+
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        min_len_hint(self.signal.len_hint(), self.positions.len_hint())
+    }
+}
+
+impl<S, P, X> ExactSizeSignal for Pan<S, P>
+where
+    S: ExactSizeSignal<Frame = Fixed<X, 1>>,
+    P: ExactSizeSignal,
+    X: FloatSample,
+{
+}
+
+/// The parameters of an [`AutoPan`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AutoPanParams {
+    /// The pan law.
+    pub law: PanLaw,
+
+    /// The shape of the modulation.
+    pub shape: Shape,
+
+    /// The frequency of the modulation, in Hz.
+    pub rate: f64,
+
+    /// How far the signal is panned, from 0 (always at the center) to 1 (all
+    /// the way to each side).
+    pub depth: f64,
+}
+
+impl Default for AutoPanParams {
+    fn default() -> Self {
+        Self {
+            law: PanLaw::ConstantPower,
+            shape: Shape::Sine,
+            rate: 1.0,
+            depth: 1.0,
+        }
+    }
+}
+
+/// A [`Signal`] that pans an underlying mono [`Signal`] back and forth
+/// across the stereo field, following an [`Lfo`]. It starts at the center,
+/// and moves right first.
+///
+/// This is created by [`Signal::auto_pan`].
+pub struct AutoPan<S> {
+    signal: S,
+    params: AutoPanParams,
+    lfo: Lfo,
+}
+
+impl<S> AutoPan<S> {
+    pub(crate) fn new(signal: S, params: AutoPanParams, sample_rate: f64) -> Self {
+        Self {
+            signal,
+            params,
+            lfo: Lfo::new(params.shape, params.rate, sample_rate),
+        }
+    }
+
+    pub fn params(&self) -> &AutoPanParams {
+        &self.params
+    }
+
+    pub fn set_params(&mut self, params: AutoPanParams) {
+        self.lfo.set_shape(params.shape);
+        self.lfo.set_frequency(params.rate);
+        self.params = params;
+    }
+}
+
+impl<S, X> Signal for AutoPan<S>
+where
+    S: Signal<Frame = Fixed<X, 1>>,
+    X: FloatSample,
+{
+    type Frame = Fixed<X, 2>;
+
+    fn next(&mut self) -> Option<Self::Frame> {
+        let frame = self.signal.next()?;
+        let position = self.params.depth * self.lfo.tick();
+
+        Some(pan(frame, self.params.law, position))
+    }
+
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        self.signal.len_hint()
+    }
+}
+
+impl<S, X> ExactSizeSignal for AutoPan<S>
+where
+    S: ExactSizeSignal<Frame = Fixed<X, 1>>,
+    X: FloatSample,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn laws() {
+        let laws = [
+            (PanLaw::Linear, 0.0),
+            (PanLaw::ConstantPower, -3.0),
+            (PanLaw::Compromise, -4.5),
+            (PanLaw::ConstantAmplitude, -6.0),
+        ];
+
+        for (law, center) in laws {
+            assert_eq!(law.gains(-1.0), [1.0, 0.0], "{law:?}");
+            assert!(law.gains(1.0)[0].abs() < 1e-12, "{law:?}");
+            assert!((law.gains(1.0)[1] - 1.0).abs() < 1e-12, "{law:?}");
+
+            let [left, right] = law.gains(0.0);
+            assert!((left - right).abs() < 1e-12, "{law:?}");
+            assert!((20.0 * left.log10() - center).abs() < 0.1, "{law:?}");
+
+            // The left gain falls as the position moves right.
+            let gains: Vec<[f64; 2]> = (-10..=10).map(|i| law.gains(i as f64 / 10.0)).collect();
+            assert!(
+                gains
+                    .windows(2)
+                    .all(|w| w[0][0] >= w[1][0] && w[0][1] <= w[1][1])
+            );
+        }
+    }
+}
@@ -0,0 +1,301 @@
+//! Waveshaping and saturation, with optional oversampling.
+//!
+//! A waveshaper maps each sample through a [`Curve`]. Since most curves add
+//! harmonics, which alias back below the Nyquist frequency, the curve can be
+//! applied at a multiple of the sample rate, with half-band filters to
+//! upsample and downsample the signal.
+
+mod oversample;
+
+pub use self::oversample::Oversampling;
+
+use self::oversample::Oversampler;
+use crate::frame::Frame;
+use crate::sample::to_f64;
+use crate::sample::{FloatSample, FromSample, Sample};
+use crate::signal::{ExactSizeSignal, Signal, len_hint_with_tail};
+
+/// Types that map input samples to output samples.
+///
+/// This is implemented for all closures that map an `f64` to an `f64`.
+pub trait Curve {
+    fn apply(&self, x: f64) -> f64;
+}
+
+impl<F> Curve for F
+where
+    F: Fn(f64) -> f64,
+{
+    fn apply(&self, x: f64) -> f64 {
+        self(x)
+    }
+}
+
+/// Common waveshaping [`Curve`]s. Each preset passes small signals at unity
+/// gain, and keeps the output in the interval [-1.0, 1.0].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Preset {
+    /// The hyperbolic tangent, which saturates smoothly.
+    Tanh,
+
+    /// A cubic curve, which flattens out at an input of 1.5, and clips
+    /// above it.
+    SoftClip,
+
+    /// Clamps the signal to the interval [-1.0, 1.0].
+    HardClip,
+
+    /// An asymmetric curve, like an overdriven tube stage, which clips the
+    /// positive half earlier than the negative half and adds even harmonics.
+    Tube,
+
+    /// Reflects the signal back each time it exceeds 1.0 or -1.0.
+    Foldback,
+}
+
+impl Curve for Preset {
+    fn apply(&self, x: f64) -> f64 {
+        match self {
+            Preset::Tanh => x.tanh(),
+            Preset::SoftClip => {
+                let x = (x / 1.5).clamp(-1.0, 1.0);
+                1.5 * (x - x * x * x / 3.0)
+            }
+            Preset::HardClip => x.clamp(-1.0, 1.0),
+            Preset::Tube => {
+                // A biased tanh, with the bias removed at rest, and scaled so
+                // that the negative half saturates at -1.
+                const BIAS: f64 = 0.25;
+                let (offset, gain) = (BIAS.tanh(), 1.0 + BIAS.tanh());
+                let drive = gain / (1.0 - offset * offset);
+                ((x * drive + BIAS).tanh() - offset) / gain
+            }
+            Preset::Foldback => ((x - 1.0).rem_euclid(4.0) - 2.0).abs() - 1.0,
+        }
+    }
+}
+
+/// A [`Curve`] defined by a lookup table of output values, evenly spaced over
+/// inputs in the interval [-1.0, 1.0]. Inputs between the entries are
+/// interpolated linearly, and inputs outside of the interval are clamped.
+///
+/// ```
+/// use sampara::waveshape::{Curve, Table};
+///
+/// fn main() {
+///     let table = Table::new(vec![-1.0, 0.0, 0.5]);
+///
+///     assert_eq!(table.apply(-1.0), -1.0);
+///     assert_eq!(table.apply(0.5), 0.25);
+///     assert_eq!(table.apply(2.0), 0.5);
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Table {
+    values: Vec<f64>,
+}
+
+impl Table {
+    /// Creates a new [`Table`] from its output values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are fewer than two values.
+    pub fn new(values: Vec<f64>) -> Self {
+        assert!(values.len() >= 2, "a table must have at least two values");
+        Self { values }
+    }
+
+    /// Creates a new [`Table`] of `len` entries, by sampling a [`Curve`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is less than two.
+    pub fn from_curve<C: Curve>(len: usize, curve: C) -> Self {
+        let step = 2.0 / (len.max(2) - 1) as f64;
+        Self::new(
+            (0..len)
+                .map(|i| curve.apply(i as f64 * step - 1.0))
+                .collect(),
+        )
+    }
+
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+}
+
+impl Curve for Table {
+    fn apply(&self, x: f64) -> f64 {
+        let last = self.values.len() - 1;
+        let position = (x.clamp(-1.0, 1.0) + 1.0) * 0.5 * last as f64;
+
+        let index = (position.floor() as usize).min(last - 1);
+        let frac = position - index as f64;
+        let (a, b) = (self.values[index], self.values[index + 1]);
+
+        a + (b - a) * frac
+    }
+}
+
+/// A [`Signal`] that maps each sample of an underlying [`Signal`] through a
+/// [`Curve`].
+///
+/// This is created by [`Signal::waveshape`].
+pub struct Waveshape<S, C>
+where
+    S: Signal,
+{
+    signal: S,
+    curve: C,
+    oversampling: Oversampling,
+    oversamplers: Vec<Oversampler>,
+    // A frame of equilibrium, used to flush the filters once the input ends.
+    silence: Option<S::Frame>,
+    // The number of frames left to output, once the input has run out.
+    tail: Option<usize>,
+}
+
+impl<S, C> Waveshape<S, C>
+where
+    S: Signal,
+    <S::Frame as Frame>::Sample: FloatSample,
+    C: Curve,
+{
+    pub(crate) fn new(signal: S, curve: C) -> Self {
+        Self {
+            signal,
+            curve,
+            oversampling: Oversampling::None,
+            oversamplers: Vec::new(),
+            silence: None,
+            tail: None,
+        }
+    }
+
+    /// Applies the [`Curve`] at a multiple of the sample rate. This delays the
+    /// output by [`Oversampling::latency`], and extends it by the same amount,
+    /// rounded up.
+    pub fn oversample(mut self, oversampling: Oversampling) -> Self {
+        self.oversampling = oversampling;
+        self.oversamplers.clear();
+        self
+    }
+
+    pub fn curve(&self) -> &C {
+        &self.curve
+    }
+
+    pub fn curve_mut(&mut self) -> &mut C {
+        &mut self.curve
+    }
+
+    /// The delay added by oversampling, in [`Frame`]s.
+    pub fn latency(&self) -> f64 {
+        self.oversampling.latency()
+    }
+}
+
+impl<S, C> Signal for Waveshape<S, C>
+where
+    S: Signal,
+    <S::Frame as Frame>::Sample: FloatSample,
+    C: Curve,
+{
+    type Frame = S::Frame;
+
+    fn next(&mut self) -> Option<Self::Frame> {
+        let mut frame = match self.tail {
+            Some(0) => return None,
+            Some(ref mut tail) => {
+                *tail -= 1;
+                self.silence.clone()?
+            }
+            None => match self.signal.next() {
+                Some(frame) => frame,
+                None => {
+                    self.tail = Some(self.latency().ceil() as usize);
+                    return self.next();
+                }
+            },
+        };
+
+        if self.oversampling == Oversampling::None {
+            for sample in frame.iter_mut() {
+                *sample = FromSample::from_sample(self.curve.apply(to_f64(sample)));
+            }
+
+            return Some(frame);
+        }
+
+        if self.oversamplers.len() != frame.len() {
+            self.oversamplers = vec![Oversampler::new(self.oversampling); frame.len()];
+
+            let mut silence = frame.clone();
+            silence
+                .iter_mut()
+                .for_each(|s| *s = <S::Frame as Frame>::Sample::EQUILIBRIUM);
+            self.silence = Some(silence);
+        }
+
+        for (sample, oversampler) in frame.iter_mut().zip(self.oversamplers.iter_mut()) {
+            let y = oversampler.process(to_f64(sample), |x| self.curve.apply(x));
+            *sample = FromSample::from_sample(y);
+        }
+
+        Some(frame)
+    }
+
+    fn len_hint(&self) -> (usize, Option<usize>) {
+        let (input, tail) = match self.tail {
+            Some(tail) => ((0, Some(0)), tail),
+            None => (self.signal.len_hint(), self.latency().ceil() as usize),
+        };
+
+        len_hint_with_tail(input, tail, self.silence.is_some())
+    }
+}
+
+impl<S, C> ExactSizeSignal for Waveshape<S, C>
+where
+    S: ExactSizeSignal,
+    <S::Frame as Frame>::Sample: FloatSample,
+    C: Curve,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets() {
+        let presets = [
+            Preset::Tanh,
+            Preset::SoftClip,
+            Preset::HardClip,
+            Preset::Tube,
+            Preset::Foldback,
+        ];
+
+        for preset in presets {
+            // Unity gain and no offset at rest.
+            assert!(preset.apply(0.0).abs() < 1e-12, "{preset:?}");
+            let slope = (preset.apply(1e-6) - preset.apply(-1e-6)) / 2e-6;
+            assert!((slope - 1.0).abs() < 1e-6, "{preset:?}");
+
+            // Bounded output.
+            for i in -100..=100 {
+                let y = preset.apply(i as f64 * 0.1);
+                assert!(y.abs() <= 1.0 + 1e-12, "{preset:?}");
+            }
+        }
+
+        assert_eq!(Preset::Foldback.apply(1.5), 0.5);
+        assert_eq!(Preset::Foldback.apply(-2.5), 0.5);
+        assert_eq!(Preset::SoftClip.apply(3.0), 1.0);
+
+        // The tube curve clips the positive half earlier.
+        assert!(Preset::Tube.apply(2.0) < -Preset::Tube.apply(-2.0));
+    }
+}
@@ -0,0 +1,203 @@
+use crate::delay::DelayLine;
+use crate::fir::{self, Response};
+use crate::spectrum::Window;
+
+/// The number of non-zero taps on each side of the center of a half-band
+/// filter, excluding the center. The filter has `4 * HALF_TAPS + 3` taps.
+const HALF_TAPS: usize = 7;
+
+/// The oversampling factor of a [`Waveshape`](crate::waveshape::Waveshape).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Oversampling {
+    None,
+    X2,
+    X4,
+    X8,
+}
+
+impl Oversampling {
+    /// The number of half-band stages, each of which doubles the rate.
+    fn stages(&self) -> usize {
+        match self {
+            Oversampling::None => 0,
+            Oversampling::X2 => 1,
+            Oversampling::X4 => 2,
+            Oversampling::X8 => 3,
+        }
+    }
+
+    /// The oversampling factor.
+    pub fn factor(&self) -> usize {
+        1 << self.stages()
+    }
+
+    /// The delay added by oversampling, in [`Frame`](crate::Frame)s at the
+    /// original rate. Each stage delays by the center of its filter, once
+    /// when upsampling and once when downsampling, at twice its input rate.
+    pub fn latency(&self) -> f64 {
+        let center = (2 * HALF_TAPS + 1) as f64;
+        (0..self.stages()).map(|s| center / (1 << s) as f64).sum()
+    }
+}
+
+/// The even taps of a half-band low-pass filter, whose odd taps are all zero
+/// apart from the center tap of 0.5.
+fn half_band() -> Vec<f64> {
+    let len = 4 * HALF_TAPS + 3;
+    let kernel: Vec<f64> = fir::windowed_sinc(
+        Response::LowPass { cutoff: 0.25 },
+        len,
+        Window::Kaiser { beta: 8.0 },
+    );
+
+    // Each phase is normalized to a gain of 0.5, so that a constant input
+    // is interpolated without ripple.
+    let even: Vec<f64> = kernel.into_iter().step_by(2).collect();
+    let sum: f64 = even.iter().sum();
+    even.into_iter().map(|h| 0.5 * h / sum).collect()
+}
+
+/// One half-band stage of an [`Oversampler`], which doubles the rate and
+/// then halves it again.
+#[derive(Clone, Debug)]
+struct Stage {
+    // The input history, for upsampling.
+    up: DelayLine<f64>,
+    // The even and odd sample histories, for downsampling.
+    even: DelayLine<f64>,
+    odd: DelayLine<f64>,
+}
+
+impl Stage {
+    fn new() -> Self {
+        Self {
+            up: DelayLine::new(2 * HALF_TAPS + 1),
+            even: DelayLine::new(2 * HALF_TAPS + 1),
+            odd: DelayLine::new(HALF_TAPS + 1),
+        }
+    }
+
+    /// Upsamples one input into two outputs.
+    fn upsample(&mut self, taps: &[f64], x: f64) -> [f64; 2] {
+        self.up.push(x);
+
+        let even: f64 = taps
+            .iter()
+            .enumerate()
+            .map(|(j, h)| h * self.up.read(j))
+            .sum();
+
+        // The odd phase is a pure delay, through the center tap.
+        [2.0 * even, self.up.read(HALF_TAPS)]
+    }
+
+    /// Downsamples two inputs into one output.
+    fn downsample(&mut self, taps: &[f64], [even, odd]: [f64; 2]) -> f64 {
+        self.even.push(even);
+        self.odd.push(odd);
+
+        let even: f64 = taps
+            .iter()
+            .enumerate()
+            .map(|(j, h)| h * self.even.read(j))
+            .sum();
+        even + 0.5 * self.odd.read(HALF_TAPS + 1)
+    }
+}
+
+/// Runs a function at a multiple of the sample rate of a single channel.
+#[derive(Clone, Debug)]
+pub(super) struct Oversampler {
+    taps: Vec<f64>,
+    stages: Vec<Stage>,
+    buffer: Vec<f64>,
+    scratch: Vec<f64>,
+}
+
+impl Oversampler {
+    pub(super) fn new(oversampling: Oversampling) -> Self {
+        Self {
+            taps: half_band(),
+            stages: vec![Stage::new(); oversampling.stages()],
+            buffer: Vec::new(),
+            scratch: Vec::new(),
+        }
+    }
+
+    /// Processes one sample, by upsampling it, applying `f` to each
+    /// upsampled sample, and downsampling the result.
+    pub(super) fn process(&mut self, x: f64, mut f: impl FnMut(f64) -> f64) -> f64 {
+        self.buffer.clear();
+        self.buffer.push(x);
+
+        for stage in self.stages.iter_mut() {
+            self.scratch.clear();
+            for &x in self.buffer.iter() {
+                self.scratch.extend(stage.upsample(&self.taps, x));
+            }
+            std::mem::swap(&mut self.buffer, &mut self.scratch);
+        }
+
+        self.buffer.iter_mut().for_each(|x| *x = f(*x));
+
+        for stage in self.stages.iter_mut().rev() {
+            self.scratch.clear();
+            for pair in self.buffer.chunks_exact(2) {
+                self.scratch
+                    .push(stage.downsample(&self.taps, [pair[0], pair[1]]));
+            }
+            std::mem::swap(&mut self.buffer, &mut self.scratch);
+        }
+
+        self.buffer[0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::f64::consts::PI;
+
+    #[test]
+    fn round_trip() {
+        // Without any processing, a low frequency sine comes back delayed by
+        // the latency.
+        for oversampling in [Oversampling::X2, Oversampling::X4, Oversampling::X8] {
+            let mut oversampler = Oversampler::new(oversampling);
+            let latency = oversampling.latency();
+            let freq = 0.05;
+
+            for i in 0..200 {
+                let y = oversampler.process((2.0 * PI * freq * i as f64).sin(), |x| x);
+
+                if i >= 100 {
+                    let expected = (2.0 * PI * freq * (i as f64 - latency)).sin();
+                    assert!(
+                        (y - expected).abs() < 1e-3,
+                        "{oversampling:?} {y} {expected}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn harmonics_do_not_alias() {
+        // Cubing a sine adds its third harmonic, which at this frequency is
+        // above the original Nyquist frequency, and would otherwise alias
+        // back down to 0.1.
+        let mut oversampler = Oversampler::new(Oversampling::X2);
+        let latency = Oversampling::X2.latency();
+        let freq = 0.3;
+
+        for i in 0..400 {
+            let y = oversampler.process((2.0 * PI * freq * i as f64).sin(), |x| x * x * x);
+
+            if i >= 200 {
+                let expected = 0.75 * (2.0 * PI * freq * (i as f64 - latency)).sin();
+                assert!((y - expected).abs() < 1e-2, "{y} {expected}");
+            }
+        }
+    }
+}